@@ -0,0 +1,104 @@
+//! Multi-symbol arbitrage signal generation from raw per-exchange quotes
+//!
+//! Unlike `arbitrage::ArbitrageDetector`, which scans a single `ConsolidatedBook`'s
+//! NBBO, `Detector` here ingests the latest `ExchangeQuote` per `(Symbol, ExchangeId)`
+//! directly (e.g. from exchange WebSocket feeds) and can scan any number of symbols
+//! across all exchange pairs at once, netting out each venue's taker fee.
+
+use crate::core::types::*;
+use hashbrown::HashMap;
+
+/// Default taker fee, in bps, assumed for an exchange missing from the table
+const DEFAULT_TAKER_FEE_BPS: f64 = 5.0;
+
+/// Per-exchange taker fee schedule used to net fees out of detected profit
+#[derive(Debug, Clone, Default)]
+pub struct TakerFeeTable {
+    fees_bps: HashMap<ExchangeId, f64>,
+}
+
+impl TakerFeeTable {
+    pub fn new() -> Self {
+        TakerFeeTable { fees_bps: HashMap::new() }
+    }
+
+    pub fn set_fee(&mut self, exchange: ExchangeId, fee_bps: f64) {
+        self.fees_bps.insert(exchange, fee_bps);
+    }
+
+    pub fn fee_bps(&self, exchange: ExchangeId) -> f64 {
+        self.fees_bps.get(&exchange).copied().unwrap_or(DEFAULT_TAKER_FEE_BPS)
+    }
+}
+
+/// Scans the latest quote per `(Symbol, ExchangeId)` for cross-exchange arbitrage
+pub struct Detector {
+    quotes: HashMap<(Symbol, ExchangeId), ExchangeQuote>,
+    fees: TakerFeeTable,
+    min_profit_bps: f64,
+}
+
+impl Detector {
+    pub fn new(fees: TakerFeeTable, min_profit_bps: f64) -> Self {
+        Detector {
+            quotes: HashMap::new(),
+            fees,
+            min_profit_bps,
+        }
+    }
+
+    /// Record the latest quote seen for `symbol` on `quote.exchange`
+    pub fn update_quote(&mut self, symbol: Symbol, quote: ExchangeQuote) {
+        self.quotes.insert((symbol, quote.exchange), quote);
+    }
+
+    /// Scan every exchange pair currently quoting `symbol` for a profitable cross
+    /// (buy on one venue's ask, sell on another's higher bid), net of both venues'
+    /// taker fees, returning opportunities that clear `min_profit_bps`
+    pub fn scan(&self, symbol: &Symbol) -> Vec<ArbitrageOpportunity> {
+        let venues: Vec<&ExchangeQuote> = self
+            .quotes
+            .iter()
+            .filter(|((sym, _), _)| sym == symbol)
+            .map(|(_, quote)| quote)
+            .collect();
+
+        let mut opportunities = Vec::new();
+
+        for buy in &venues {
+            for sell in &venues {
+                if buy.exchange == sell.exchange {
+                    continue;
+                }
+                if buy.ask <= 0 || sell.bid <= 0 || buy.ask >= sell.bid {
+                    continue;
+                }
+
+                let mid = (buy.ask + sell.bid) / 2;
+                if mid == 0 {
+                    continue;
+                }
+
+                let gross_bps = 10000.0 * (sell.bid - buy.ask) as f64 / mid as f64;
+                let net_bps = gross_bps - self.fees.fee_bps(buy.exchange) - self.fees.fee_bps(sell.exchange);
+
+                if net_bps < self.min_profit_bps {
+                    continue;
+                }
+
+                opportunities.push(ArbitrageOpportunity {
+                    symbol: symbol.clone(),
+                    buy_exchange: buy.exchange,
+                    sell_exchange: sell.exchange,
+                    buy_price: buy.ask,
+                    sell_price: sell.bid,
+                    quantity: buy.ask_qty.min(sell.bid_qty),
+                    profit_bps: net_bps,
+                    timestamp: now_nanos(),
+                });
+            }
+        }
+
+        opportunities
+    }
+}
@@ -0,0 +1,240 @@
+//! Arbitrage detection and execution
+
+use crate::core::types::*;
+use crate::exchange::{ExchangeClient, ExchangeManager};
+use crate::orderbook::ConsolidatedBook;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+pub use crate::core::types::ArbitrageOpportunity;
+
+/// Configuration for arbitrage detection
+#[derive(Debug, Clone)]
+pub struct ArbitrageConfig {
+    pub min_profit_bps: f64,
+    pub max_slippage_bps: f64,
+    pub min_quantity: Quantity,
+    pub max_quantity: Quantity,
+    pub max_age_ns: Timestamp,
+}
+
+impl Default for ArbitrageConfig {
+    fn default() -> Self {
+        ArbitrageConfig {
+            min_profit_bps: 5.0,
+            max_slippage_bps: 2.0,
+            min_quantity: to_qty(0.001),
+            max_quantity: to_qty(0.1),
+            max_age_ns: 100_000_000, // 100ms
+        }
+    }
+}
+
+/// Arbitrage detector
+pub struct ArbitrageDetector {
+    config: ArbitrageConfig,
+    opportunities_found: AtomicU64,
+    opportunities_executed: AtomicU64,
+}
+
+impl ArbitrageDetector {
+    pub fn new(config: ArbitrageConfig) -> Self {
+        ArbitrageDetector {
+            config,
+            opportunities_found: AtomicU64::new(0),
+            opportunities_executed: AtomicU64::new(0),
+        }
+    }
+
+    /// Detect arbitrage opportunities from consolidated book
+    pub fn detect(&self, book: &ConsolidatedBook) -> Option<ArbitrageOpportunity> {
+        if let Some(mut opp) = book.find_arbitrage() {
+            // Filter by minimum profit
+            if opp.profit_bps < self.config.min_profit_bps {
+                return None;
+            }
+
+            // Clamp quantity
+            opp.quantity = opp.quantity
+                .max(self.config.min_quantity)
+                .min(self.config.max_quantity);
+
+            self.opportunities_found.fetch_add(1, Ordering::Relaxed);
+            return Some(opp);
+        }
+
+        None
+    }
+
+    /// Record executed opportunity
+    pub fn record_execution(&self, _success: bool) {
+        self.opportunities_executed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn opportunities_found(&self) -> u64 {
+        self.opportunities_found.load(Ordering::Relaxed)
+    }
+
+    pub fn opportunities_executed(&self) -> u64 {
+        self.opportunities_executed.load(Ordering::Relaxed)
+    }
+}
+
+/// Outcome of executing both legs of an arbitrage opportunity
+#[derive(Debug, Clone, Default)]
+pub struct ArbitrageExecutionResult {
+    pub buy_filled_qty: Quantity,
+    pub sell_filled_qty: Quantity,
+    pub realized_profit: f64,
+    pub rolled_back: bool,
+}
+
+/// Arbitrage executor
+pub struct ArbitrageExecutor {
+    max_retries: u32,
+    rollbacks: AtomicU64,
+}
+
+impl ArbitrageExecutor {
+    pub fn new() -> Self {
+        ArbitrageExecutor {
+            max_retries: 3,
+            rollbacks: AtomicU64::new(0),
+        }
+    }
+
+    pub fn rollback_count(&self) -> u64 {
+        self.rollbacks.load(Ordering::Relaxed)
+    }
+
+    /// Execute both legs of an arbitrage opportunity concurrently through `exchanges`,
+    /// optimistically treating a successful `send_order` as a full fill. If one leg
+    /// fails, the missing leg is retried up to `max_retries`; if it still can't be
+    /// filled, the completed leg is flattened with a compensating market order.
+    pub async fn execute(
+        &self,
+        opp: &ArbitrageOpportunity,
+        exchanges: &ExchangeManager,
+    ) -> Result<ArbitrageExecutionResult, String> {
+        tracing::info!(
+            "Executing arbitrage: buy on {} @ {}, sell on {} @ {}, profit={:.2} bps",
+            opp.buy_exchange,
+            from_price(opp.buy_price),
+            opp.sell_exchange,
+            from_price(opp.sell_price),
+            opp.profit_bps
+        );
+
+        let buy_client = exchanges
+            .get_client(opp.buy_exchange)
+            .ok_or_else(|| format!("no client configured for {}", opp.buy_exchange))?;
+        let sell_client = exchanges
+            .get_client(opp.sell_exchange)
+            .ok_or_else(|| format!("no client configured for {}", opp.sell_exchange))?;
+
+        let buy_order = Self::leg_order(opp, opp.buy_exchange, Side::Buy, opp.buy_price);
+        let sell_order = Self::leg_order(opp, opp.sell_exchange, Side::Sell, opp.sell_price);
+
+        let (buy_result, sell_result) = tokio::join!(
+            buy_client.send_order(&buy_order),
+            sell_client.send_order(&sell_order),
+        );
+
+        let fully_filled = |result: &mut ArbitrageExecutionResult| {
+            result.buy_filled_qty = opp.quantity;
+            result.sell_filled_qty = opp.quantity;
+            result.realized_profit =
+                from_qty(opp.quantity) * (from_price(opp.sell_price) - from_price(opp.buy_price));
+        };
+
+        let mut result = ArbitrageExecutionResult::default();
+
+        match (buy_result, sell_result) {
+            (Ok(_), Ok(_)) => {
+                fully_filled(&mut result);
+                Ok(result)
+            }
+            (Ok(_), Err(e)) => {
+                if self.retry_leg(&*sell_client, &sell_order).await {
+                    fully_filled(&mut result);
+                    Ok(result)
+                } else {
+                    result.buy_filled_qty = opp.quantity;
+                    result.rolled_back = true;
+                    self.rollbacks.fetch_add(1, Ordering::Relaxed);
+                    self.rollback_leg(&*buy_client, opp.buy_exchange, &opp.symbol, Side::Sell, opp.quantity)
+                        .await;
+                    Err(format!("sell leg failed after retries ({e}), rolled back buy leg"))
+                }
+            }
+            (Err(e), Ok(_)) => {
+                if self.retry_leg(&*buy_client, &buy_order).await {
+                    fully_filled(&mut result);
+                    Ok(result)
+                } else {
+                    result.sell_filled_qty = opp.quantity;
+                    result.rolled_back = true;
+                    self.rollbacks.fetch_add(1, Ordering::Relaxed);
+                    self.rollback_leg(&*sell_client, opp.sell_exchange, &opp.symbol, Side::Buy, opp.quantity)
+                        .await;
+                    Err(format!("buy leg failed after retries ({e}), rolled back sell leg"))
+                }
+            }
+            (Err(be), Err(se)) => Err(format!("both legs failed: buy={be}, sell={se}")),
+        }
+    }
+
+    fn leg_order(opp: &ArbitrageOpportunity, exchange: ExchangeId, side: Side, price: Price) -> Order {
+        Order {
+            id: 0,
+            exchange,
+            symbol: opp.symbol.clone(),
+            side,
+            order_type: OrderType::Limit,
+            price,
+            stop_price: None,
+            quantity: opp.quantity,
+            filled_qty: 0,
+            status: OrderStatus::New,
+            timestamp: now_nanos(),
+        }
+    }
+
+    /// Retry sending an order up to `max_retries` times
+    async fn retry_leg(&self, client: &dyn ExchangeClient, order: &Order) -> bool {
+        for attempt in 1..=self.max_retries {
+            match client.send_order(order).await {
+                Ok(_) => return true,
+                Err(e) => tracing::warn!("Retry {}/{} for missing leg failed: {}", attempt, self.max_retries, e),
+            }
+        }
+        false
+    }
+
+    /// Submit a compensating market order to flatten a leg whose counterpart never filled
+    async fn rollback_leg(&self, client: &dyn ExchangeClient, exchange: ExchangeId, symbol: &Symbol, side: Side, qty: Quantity) {
+        let compensating = Order {
+            id: 0,
+            exchange,
+            symbol: symbol.clone(),
+            side,
+            order_type: OrderType::Market,
+            price: 0,
+            stop_price: None,
+            quantity: qty,
+            filled_qty: 0,
+            status: OrderStatus::New,
+            timestamp: now_nanos(),
+        };
+
+        if let Err(e) = client.send_order(&compensating).await {
+            tracing::error!("Rollback order on {} failed: {}", exchange, e);
+        }
+    }
+}
+
+impl Default for ArbitrageExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,11 +1,12 @@
 //! Standalone arbitrage bot
 
 use hft_multi::prelude::*;
+use hft_multi::exchange::{bybit::BybitClient, ExchangeClient, ExchangeManager};
 use hft_multi::orderbook::ConsolidatedBook;
 use hft_multi::arbitrage::{ArbitrageDetector, ArbitrageConfig, ArbitrageExecutor};
 use std::sync::Arc;
 use tokio::signal;
-use tracing::info;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -17,6 +18,12 @@ async fn main() -> anyhow::Result<()> {
     let symbol = Symbol::new("BTCUSDT");
     let book = Arc::new(ConsolidatedBook::new(symbol.clone()));
 
+    let exchange_manager = ExchangeManager::new();
+    let mut bybit = BybitClient::new(true, book.clone());
+    bybit.connect().await?;
+    bybit.subscribe(&symbol).await?;
+    exchange_manager.add_client(Arc::new(bybit) as Arc<dyn ExchangeClient>);
+
     let config = ArbitrageConfig {
         min_profit_bps: 3.0,
         ..Default::default()
@@ -35,8 +42,18 @@ async fn main() -> anyhow::Result<()> {
             // Occasionally create arbitrage opportunity
             let arb_spread = if i % 20 == 0 { to_price(5.0) } else { to_price(0.0) };
 
-            book_clone.update(ExchangeId::Binance, base - to_price(1.0), to_qty(1.0), base + to_price(1.0), to_qty(1.0));
-            book_clone.update(ExchangeId::Bybit, base - to_price(1.0) - arb_spread, to_qty(0.5), base + to_price(1.0) + arb_spread, to_qty(0.5));
+            book_clone
+                .update(ExchangeId::Binance, base - to_price(1.0), to_qty(1.0), base + to_price(1.0), to_qty(1.0))
+                .ok();
+            book_clone
+                .update(
+                    ExchangeId::Bybit,
+                    base - to_price(1.0) - arb_spread,
+                    to_qty(0.5),
+                    base + to_price(1.0) + arb_spread,
+                    to_qty(0.5),
+                )
+                .ok();
 
             i += 1;
             tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
@@ -49,8 +66,15 @@ async fn main() -> anyhow::Result<()> {
             _ = tokio::time::sleep(tokio::time::Duration::from_millis(10)) => {
                 if let Some(opp) = detector.detect(&book) {
                     info!("ARB: {} -> {}, profit={:.2} bps", opp.buy_exchange, opp.sell_exchange, opp.profit_bps);
-                    if let Ok(profit) = executor.execute(&opp).await {
-                        info!("Executed: profit=${:.4}", profit);
+                    match executor.execute(&opp, &exchange_manager).await {
+                        Ok(result) => {
+                            info!("Executed: profit=${:.4}", result.realized_profit);
+                            detector.record_execution(true);
+                        }
+                        Err(e) => {
+                            warn!("Execution failed: {}", e);
+                            detector.record_execution(false);
+                        }
                     }
                 }
             }
@@ -0,0 +1,3 @@
+//! Core data types shared across exchanges
+
+pub mod types;
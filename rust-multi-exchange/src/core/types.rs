@@ -19,6 +19,8 @@ pub enum ExchangeId {
     Okx = 3,
     Coinbase = 4,
     Kraken = 5,
+    /// Synthetic constant-product AMM liquidity source, not a real venue
+    Amm = 6,
 }
 
 impl fmt::Display for ExchangeId {
@@ -30,6 +32,7 @@ impl fmt::Display for ExchangeId {
             ExchangeId::Coinbase => write!(f, "COINBASE"),
             ExchangeId::Kraken => write!(f, "KRAKEN"),
             ExchangeId::Unknown => write!(f, "UNKNOWN"),
+            ExchangeId::Amm => write!(f, "AMM"),
         }
     }
 }
@@ -40,6 +43,9 @@ pub enum Side { Buy, Sell }
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderStatus { New, PartiallyFilled, Filled, Canceled, Rejected }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderType { Limit, Market, Stop }
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Symbol(pub String);
 
@@ -61,7 +67,10 @@ pub struct Order {
     pub exchange: ExchangeId,
     pub symbol: Symbol,
     pub side: Side,
+    pub order_type: OrderType,
     pub price: Price,
+    /// Trigger price for `OrderType::Stop`; unused otherwise
+    pub stop_price: Option<Price>,
     pub quantity: Quantity,
     pub filled_qty: Quantity,
     pub status: OrderStatus,
@@ -93,11 +102,40 @@ pub struct ArbitrageOpportunity {
     pub timestamp: Timestamp,
 }
 
+/// Fixed-point money value (PnL, loss limits) scaled by `PRECISION`, so a
+/// risk ledger that gates a kill switch doesn't accrue `f64` rounding drift
+/// across many fills and stays reproducible across platforms. Mirrors the
+/// scaled-integer convention `Price`/`Quantity` already use rather than
+/// pulling in an external bignum crate for one field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct Money(pub i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    /// Checked addition; `None` on overflow rather than silently wrapping
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    /// Checked subtraction; `None` on overflow rather than silently wrapping
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+}
+
+impl std::ops::Neg for Money {
+    type Output = Money;
+    fn neg(self) -> Money { Money(-self.0) }
+}
+
 // Conversion functions
 pub fn to_price(v: f64) -> Price { (v * PRECISION as f64) as Price }
 pub fn from_price(p: Price) -> f64 { p as f64 / PRECISION as f64 }
 pub fn to_qty(v: f64) -> Quantity { (v * PRECISION as f64) as Quantity }
 pub fn from_qty(q: Quantity) -> f64 { q as f64 / PRECISION as f64 }
+pub fn to_money(v: f64) -> Money { Money((v * PRECISION as f64) as i64) }
+pub fn from_money(m: Money) -> f64 { m.0 as f64 / PRECISION as f64 }
 
 pub fn now_nanos() -> Timestamp {
     std::time::SystemTime::now()
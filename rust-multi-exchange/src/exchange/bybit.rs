@@ -0,0 +1,215 @@
+//! Bybit client for multi-exchange system
+
+use super::{ExchangeClient, ExchangeError, MarketDataEvent};
+use crate::core::types::*;
+use crate::orderbook::ConsolidatedBook;
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex as AsyncMutex;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+const MAINNET_WS_URL: &str = "wss://stream.bybit.com/v5/public/spot";
+const TESTNET_WS_URL: &str = "wss://stream-testnet.bybit.com/v5/public/spot";
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+pub struct BybitClient {
+    connected: AtomicBool,
+    latency_ns: Arc<AtomicU64>,
+    testnet: bool,
+    book: Arc<ConsolidatedBook>,
+    stream_task: AsyncMutex<Option<JoinHandle<()>>>,
+}
+
+impl BybitClient {
+    pub fn new(testnet: bool, book: Arc<ConsolidatedBook>) -> Self {
+        BybitClient {
+            connected: AtomicBool::new(false),
+            latency_ns: Arc::new(AtomicU64::new(0)),
+            testnet,
+            book,
+            stream_task: AsyncMutex::new(None),
+        }
+    }
+
+    fn ws_url(&self) -> &'static str {
+        if self.testnet {
+            TESTNET_WS_URL
+        } else {
+            MAINNET_WS_URL
+        }
+    }
+
+    /// Parse one tagged JSON frame from the `orderbook.1.{symbol}` topic, distinguishing
+    /// control events (ping/pong, subscription ack) from data frames whose bid/ask levels
+    /// arrive as string-encoded decimals
+    fn parse_frame(raw: &str) -> Option<MarketDataEvent> {
+        let v: Value = serde_json::from_str(raw).ok()?;
+
+        if let Some(op) = v.get("op").and_then(Value::as_str) {
+            return match op {
+                "ping" | "pong" => Some(MarketDataEvent::Heartbeat),
+                "subscribe" => Some(MarketDataEvent::SubscriptionAck {
+                    success: v.get("success").and_then(Value::as_bool).unwrap_or(false),
+                }),
+                _ => None,
+            };
+        }
+
+        if let Some(status) = v.get("systemStatus").and_then(Value::as_str) {
+            return Some(MarketDataEvent::SystemStatus { status: status.to_string() });
+        }
+
+        let topic = v.get("topic").and_then(Value::as_str)?;
+        if !topic.starts_with("orderbook") {
+            return None;
+        }
+
+        let data = v.get("data")?;
+        let bid = data.get("b")?.as_array()?.first()?;
+        let ask = data.get("a")?.as_array()?.first()?;
+        let bid_price: f64 = bid.get(0)?.as_str()?.parse().ok()?;
+        let bid_qty: f64 = bid.get(1)?.as_str()?.parse().ok()?;
+        let ask_price: f64 = ask.get(0)?.as_str()?.parse().ok()?;
+        let ask_qty: f64 = ask.get(1)?.as_str()?.parse().ok()?;
+
+        Some(MarketDataEvent::Quote(ExchangeQuote {
+            exchange: ExchangeId::Bybit,
+            bid: to_price(bid_price),
+            ask: to_price(ask_price),
+            bid_qty: to_qty(bid_qty),
+            ask_qty: to_qty(ask_qty),
+            timestamp: now_nanos(),
+            latency_ns: 0,
+        }))
+    }
+
+    /// Connect, subscribe to the depth topic for `symbol`, and stream updates into
+    /// `book` until the socket closes, reconnecting with exponential backoff each time
+    async fn run_stream(symbol: Symbol, url: &'static str, book: Arc<ConsolidatedBook>, latency_ns: Arc<AtomicU64>) {
+        let mut backoff = INITIAL_BACKOFF;
+
+        loop {
+            let connect_start = now_nanos();
+            match connect_async(url).await {
+                Ok((mut socket, _)) => {
+                    backoff = INITIAL_BACKOFF;
+                    latency_ns.store(now_nanos().saturating_sub(connect_start), Ordering::Relaxed);
+
+                    let sub_msg = serde_json::json!({
+                        "op": "subscribe",
+                        "args": [format!("orderbook.1.{}", symbol.as_str())],
+                    });
+                    if socket.send(Message::Text(sub_msg.to_string())).await.is_err() {
+                        tracing::warn!("Bybit: failed to send subscription for {}", symbol);
+                    }
+
+                    while let Some(msg) = socket.next().await {
+                        let text = match msg {
+                            Ok(Message::Text(t)) => t,
+                            Ok(Message::Close(_)) | Err(_) => break,
+                            Ok(_) => continue,
+                        };
+
+                        match Self::parse_frame(&text) {
+                            Some(MarketDataEvent::Quote(q)) => {
+                                if let Err(e) = book.update(q.exchange, q.bid, q.bid_qty, q.ask, q.ask_qty) {
+                                    tracing::warn!("Bybit: rejected quote for {}: {}", symbol, e);
+                                }
+                            }
+                            Some(MarketDataEvent::SubscriptionAck { success: false }) => {
+                                tracing::warn!("Bybit rejected subscription for {}", symbol);
+                            }
+                            Some(MarketDataEvent::SystemStatus { status }) => {
+                                tracing::warn!("Bybit system status: {}", status);
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    tracing::warn!("Bybit market data socket closed for {}, reconnecting", symbol);
+                }
+                Err(e) => {
+                    tracing::warn!("Bybit market data connect failed: {}, retrying in {:?}", e, backoff);
+                }
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
+
+#[async_trait]
+impl ExchangeClient for BybitClient {
+    fn id(&self) -> ExchangeId {
+        ExchangeId::Bybit
+    }
+
+    fn name(&self) -> &str {
+        "Bybit"
+    }
+
+    async fn connect(&mut self) -> Result<(), ExchangeError> {
+        tracing::info!("Connecting to Bybit (testnet={})", self.testnet);
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), ExchangeError> {
+        if let Some(task) = self.stream_task.lock().await.take() {
+            task.abort();
+        }
+        self.connected.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    async fn subscribe(&mut self, symbol: &Symbol) -> Result<(), ExchangeError> {
+        if !self.is_connected() {
+            return Err(ExchangeError::NotConnected);
+        }
+
+        tracing::info!("Subscribing to {} on Bybit", symbol);
+
+        let symbol = symbol.clone();
+        let url = self.ws_url();
+        let book = self.book.clone();
+        let latency_ns = self.latency_ns.clone();
+        let task = tokio::spawn(Self::run_stream(symbol, url, book, latency_ns));
+
+        if let Some(previous) = self.stream_task.lock().await.replace(task) {
+            previous.abort();
+        }
+
+        Ok(())
+    }
+
+    async fn send_order(&self, order: &Order) -> Result<OrderId, ExchangeError> {
+        if !self.is_connected() {
+            return Err(ExchangeError::NotConnected);
+        }
+        Ok(order.id)
+    }
+
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), ExchangeError> {
+        if !self.is_connected() {
+            return Err(ExchangeError::NotConnected);
+        }
+        tracing::debug!("Canceling order {} on Bybit", order_id);
+        Ok(())
+    }
+
+    fn get_latency(&self) -> Timestamp {
+        self.latency_ns.load(Ordering::Relaxed)
+    }
+}
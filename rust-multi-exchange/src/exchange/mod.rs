@@ -8,6 +8,7 @@ use thiserror::Error;
 
 pub mod binance;
 pub mod bybit;
+pub mod simulated;
 
 pub use crate::core::types::ExchangeId;
 
@@ -21,6 +22,20 @@ pub enum ExchangeError {
     NotConnected,
 }
 
+/// A parsed message from an exchange's market-data WebSocket, distinguishing control
+/// frames (heartbeats, subscription acks, system status) from actual book updates
+#[derive(Debug, Clone)]
+pub enum MarketDataEvent {
+    /// Top-of-book quote update, ready to feed into a `ConsolidatedBook`
+    Quote(ExchangeQuote),
+    /// Venue keepalive ping/pong; carries no market data
+    Heartbeat,
+    /// Venue acknowledged (or rejected) a channel subscription
+    SubscriptionAck { success: bool },
+    /// Venue-wide system status change (e.g. entering maintenance)
+    SystemStatus { status: String },
+}
+
 /// Exchange client trait
 #[async_trait]
 pub trait ExchangeClient: Send + Sync {
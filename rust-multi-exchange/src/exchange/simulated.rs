@@ -0,0 +1,424 @@
+//! Simulated exchange client backed by a local matching engine, for deterministic backtesting
+
+use super::{ExchangeClient, ExchangeError};
+use crate::core::types::*;
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Maker/taker fee schedule applied to simulated fills, in bps of notional
+#[derive(Debug, Clone, Copy)]
+pub struct SimulatedFees {
+    /// Negative means a rebate
+    pub maker_bps: f64,
+    pub taker_bps: f64,
+}
+
+impl Default for SimulatedFees {
+    fn default() -> Self {
+        SimulatedFees {
+            maker_bps: -1.0,
+            taker_bps: 5.0,
+        }
+    }
+}
+
+/// A fill produced by the simulated matching engine
+#[derive(Debug, Clone, Copy)]
+pub struct SimulatedFill {
+    pub order_id: OrderId,
+    pub exchange: ExchangeId,
+    pub side: Side,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub fee: f64,
+    pub is_maker: bool,
+    pub timestamp: Timestamp,
+}
+
+pub type FillCallback = Box<dyn Fn(SimulatedFill) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy)]
+struct RestingOrder {
+    id: OrderId,
+    price: Price,
+    remaining: Quantity,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingStop {
+    id: OrderId,
+    side: Side,
+    stop_price: Price,
+    quantity: Quantity,
+}
+
+/// Simulated exchange client matching orders against an in-memory limit order book,
+/// seeded from replayed market data instead of a live venue
+pub struct SimulatedExchange {
+    exchange_id: ExchangeId,
+    connected: AtomicBool,
+    latency_ns: Timestamp,
+    fees: SimulatedFees,
+    next_order_id: AtomicU64,
+    bids: Mutex<BTreeMap<Reverse<Price>, Vec<RestingOrder>>>,
+    asks: Mutex<BTreeMap<Price, Vec<RestingOrder>>>,
+    stops: Mutex<Vec<PendingStop>>,
+    last_trade_price: AtomicU64,
+    on_fill: RwLock<Option<FillCallback>>,
+}
+
+impl SimulatedExchange {
+    /// Maximum resting limit orders, and separately stop orders, kept per side
+    pub const MAX_RESTING_PER_SIDE: usize = 50;
+
+    pub fn new(exchange_id: ExchangeId, fees: SimulatedFees, latency_ns: Timestamp) -> Self {
+        SimulatedExchange {
+            exchange_id,
+            connected: AtomicBool::new(false),
+            latency_ns,
+            fees,
+            next_order_id: AtomicU64::new(1),
+            bids: Mutex::new(BTreeMap::new()),
+            asks: Mutex::new(BTreeMap::new()),
+            stops: Mutex::new(Vec::new()),
+            last_trade_price: AtomicU64::new(0),
+            on_fill: RwLock::new(None),
+        }
+    }
+
+    /// Register a callback invoked for every fill this client produces. A backtest
+    /// driver typically uses this to forward fills into `CrossExchangeMM::on_fill`
+    /// (which takes `&mut self`, so needs e.g. a channel) and `RiskManager::on_fill`
+    /// (which takes `&self` and can be called directly through a shared `Arc`)
+    /// so strategy and risk state advance deterministically in step with the
+    /// replayed market data rather than on wall-clock timers.
+    pub fn set_fill_callback(&self, callback: FillCallback) {
+        *self.on_fill.write() = Some(callback);
+    }
+
+    /// Seed resting liquidity replayed from historical market data
+    pub fn seed_liquidity(&self, side: Side, price: Price, qty: Quantity) -> Result<(), ExchangeError> {
+        let order = RestingOrder {
+            id: self.next_order_id.fetch_add(1, Ordering::Relaxed),
+            price,
+            remaining: qty,
+        };
+
+        match side {
+            Side::Buy => Self::rest(&mut self.bids.lock(), Reverse(price), order),
+            Side::Sell => Self::rest(&mut self.asks.lock(), price, order),
+        }
+    }
+
+    /// Feed a replayed market trade; fills any of our resting orders it crosses
+    pub fn on_market_trade(&self, trade_price: Price, trade_qty: Quantity) {
+        self.last_trade_price.store(trade_price as u64, Ordering::Relaxed);
+
+        // A trade at `trade_price` sells into resting bids at or above it, and buys
+        // from resting asks at or below it.
+        let mut remaining = trade_qty;
+        {
+            let mut bids = self.bids.lock();
+            let mut drained = Vec::new();
+            for (Reverse(level), orders) in bids.iter_mut() {
+                if remaining <= 0 || *level < trade_price {
+                    break;
+                }
+                remaining = Self::fill_resting_at(orders, remaining, |id, qty| {
+                    self.emit_maker_fill(Side::Sell, id, *level, qty)
+                });
+                if orders.is_empty() {
+                    drained.push(Reverse(*level));
+                }
+            }
+            for key in drained {
+                bids.remove(&key);
+            }
+        }
+
+        {
+            let mut asks = self.asks.lock();
+            let mut drained = Vec::new();
+            for (level, orders) in asks.iter_mut() {
+                if remaining <= 0 || *level > trade_price {
+                    break;
+                }
+                remaining = Self::fill_resting_at(orders, remaining, |id, qty| {
+                    self.emit_maker_fill(Side::Buy, id, *level, qty)
+                });
+                if orders.is_empty() {
+                    drained.push(*level);
+                }
+            }
+            for key in drained {
+                asks.remove(&key);
+            }
+        }
+
+        self.trigger_stops(trade_price);
+    }
+
+    /// Drain fills from a resting price level up to `available` quantity, invoking
+    /// `on_fill(order_id, qty)` for each partial or full match
+    fn fill_resting_at(orders: &mut Vec<RestingOrder>, mut available: Quantity, on_fill: impl Fn(OrderId, Quantity)) -> Quantity {
+        orders.retain_mut(|order| {
+            if available <= 0 {
+                return true;
+            }
+            let fill_qty = order.remaining.min(available);
+            order.remaining -= fill_qty;
+            available -= fill_qty;
+            on_fill(order.id, fill_qty);
+            order.remaining > 0
+        });
+        available
+    }
+
+    fn emit_maker_fill(&self, side: Side, order_id: OrderId, price: Price, qty: Quantity) {
+        let notional = from_qty(qty) * from_price(price);
+        let fee = notional * self.fees.maker_bps / 10_000.0;
+        self.emit_fill(SimulatedFill {
+            order_id,
+            exchange: self.exchange_id,
+            side,
+            price,
+            quantity: qty,
+            fee,
+            is_maker: true,
+            timestamp: now_nanos(),
+        });
+    }
+
+    fn trigger_stops(&self, last_price: Price) {
+        let mut stops = self.stops.lock();
+        let (triggered, remaining): (Vec<_>, Vec<_>) = stops.drain(..).partition(|s| match s.side {
+            Side::Buy => last_price >= s.stop_price,
+            Side::Sell => last_price <= s.stop_price,
+        });
+        *stops = remaining;
+        drop(stops);
+
+        for stop in triggered {
+            let _ = self.match_taker(stop.id, stop.side, None, stop.quantity);
+        }
+    }
+
+    fn rest<K: Ord>(book: &mut BTreeMap<K, Vec<RestingOrder>>, key: K, order: RestingOrder) -> Result<(), ExchangeError> {
+        let total: usize = book.values().map(Vec::len).sum();
+        if total >= SimulatedExchange::MAX_RESTING_PER_SIDE {
+            return Err(ExchangeError::RequestFailed("resting order book is full for this side".into()));
+        }
+        book.entry(key).or_default().push(order);
+        Ok(())
+    }
+
+    /// Match an incoming taker order against resting opposite-side liquidity.
+    /// `limit_price` is `None` for a market order (matches at any price).
+    fn match_taker(&self, order_id: OrderId, side: Side, limit_price: Option<Price>, qty: Quantity) -> Quantity {
+        let mut remaining = qty;
+
+        match side {
+            Side::Buy => {
+                let mut asks = self.asks.lock();
+                let mut drained = Vec::new();
+                for (price, orders) in asks.iter_mut() {
+                    if remaining <= 0 {
+                        break;
+                    }
+                    if let Some(limit) = limit_price {
+                        if *price > limit {
+                            break;
+                        }
+                    }
+                    orders.retain_mut(|resting| {
+                        if remaining <= 0 {
+                            return true;
+                        }
+                        let fill_qty = resting.remaining.min(remaining);
+                        resting.remaining -= fill_qty;
+                        remaining -= fill_qty;
+                        self.emit_taker_fill(order_id, Side::Buy, *price, fill_qty);
+                        self.emit_maker_fill(Side::Sell, resting.id, *price, fill_qty);
+                        resting.remaining > 0
+                    });
+                    if orders.is_empty() {
+                        drained.push(*price);
+                    }
+                }
+                for p in drained {
+                    asks.remove(&p);
+                }
+            }
+            Side::Sell => {
+                let mut bids = self.bids.lock();
+                let mut drained = Vec::new();
+                for (Reverse(price), orders) in bids.iter_mut() {
+                    if remaining <= 0 {
+                        break;
+                    }
+                    if let Some(limit) = limit_price {
+                        if *price < limit {
+                            break;
+                        }
+                    }
+                    orders.retain_mut(|resting| {
+                        if remaining <= 0 {
+                            return true;
+                        }
+                        let fill_qty = resting.remaining.min(remaining);
+                        resting.remaining -= fill_qty;
+                        remaining -= fill_qty;
+                        self.emit_taker_fill(order_id, Side::Sell, *price, fill_qty);
+                        self.emit_maker_fill(Side::Buy, resting.id, *price, fill_qty);
+                        resting.remaining > 0
+                    });
+                    if orders.is_empty() {
+                        drained.push(Reverse(*price));
+                    }
+                }
+                for p in drained {
+                    bids.remove(&p);
+                }
+            }
+        }
+
+        remaining
+    }
+
+    fn emit_taker_fill(&self, order_id: OrderId, side: Side, price: Price, qty: Quantity) {
+        let notional = from_qty(qty) * from_price(price);
+        let fee = notional * self.fees.taker_bps / 10_000.0;
+        self.emit_fill(SimulatedFill {
+            order_id,
+            exchange: self.exchange_id,
+            side,
+            price,
+            quantity: qty,
+            fee,
+            is_maker: false,
+            timestamp: now_nanos(),
+        });
+    }
+
+    fn emit_fill(&self, fill: SimulatedFill) {
+        if let Some(callback) = self.on_fill.read().as_ref() {
+            callback(fill);
+        }
+    }
+}
+
+#[async_trait]
+impl ExchangeClient for SimulatedExchange {
+    fn id(&self) -> ExchangeId {
+        self.exchange_id
+    }
+
+    fn name(&self) -> &str {
+        "Simulated"
+    }
+
+    async fn connect(&mut self) -> Result<(), ExchangeError> {
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), ExchangeError> {
+        self.connected.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    async fn subscribe(&mut self, _symbol: &Symbol) -> Result<(), ExchangeError> {
+        Ok(())
+    }
+
+    async fn send_order(&self, order: &Order) -> Result<OrderId, ExchangeError> {
+        if !self.is_connected() {
+            return Err(ExchangeError::NotConnected);
+        }
+        if order.quantity <= 0 {
+            return Err(ExchangeError::RequestFailed("order quantity must be positive".into()));
+        }
+
+        match order.order_type {
+            OrderType::Market => {
+                self.match_taker(order.id, order.side, None, order.quantity);
+            }
+            OrderType::Limit => {
+                let unfilled = self.match_taker(order.id, order.side, Some(order.price), order.quantity);
+                if unfilled > 0 {
+                    let resting = RestingOrder {
+                        id: order.id,
+                        price: order.price,
+                        remaining: unfilled,
+                    };
+                    match order.side {
+                        Side::Buy => Self::rest(&mut self.bids.lock(), Reverse(order.price), resting)?,
+                        Side::Sell => Self::rest(&mut self.asks.lock(), order.price, resting)?,
+                    }
+                }
+            }
+            OrderType::Stop => {
+                let stop_price = order
+                    .stop_price
+                    .ok_or_else(|| ExchangeError::RequestFailed("stop order requires a stop_price".into()))?;
+
+                let mut stops = self.stops.lock();
+                if stops.len() >= Self::MAX_RESTING_PER_SIDE {
+                    return Err(ExchangeError::RequestFailed("stop order book is full".into()));
+                }
+                stops.push(PendingStop {
+                    id: order.id,
+                    side: order.side,
+                    stop_price,
+                    quantity: order.quantity,
+                });
+            }
+        }
+
+        Ok(order.id)
+    }
+
+    async fn cancel_order(&self, order_id: OrderId) -> Result<(), ExchangeError> {
+        let mut removed = false;
+
+        let mut bids = self.bids.lock();
+        for orders in bids.values_mut() {
+            let before = orders.len();
+            orders.retain(|o| o.id != order_id);
+            removed |= orders.len() != before;
+        }
+        bids.retain(|_, orders| !orders.is_empty());
+        drop(bids);
+
+        let mut asks = self.asks.lock();
+        for orders in asks.values_mut() {
+            let before = orders.len();
+            orders.retain(|o| o.id != order_id);
+            removed |= orders.len() != before;
+        }
+        asks.retain(|_, orders| !orders.is_empty());
+        drop(asks);
+
+        let mut stops = self.stops.lock();
+        let before = stops.len();
+        stops.retain(|s| s.id != order_id);
+        removed |= stops.len() != before;
+
+        if removed {
+            Ok(())
+        } else {
+            Err(ExchangeError::RequestFailed(format!("order {} not found", order_id)))
+        }
+    }
+
+    fn get_latency(&self) -> Timestamp {
+        self.latency_ns
+    }
+}
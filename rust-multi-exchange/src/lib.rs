@@ -9,12 +9,16 @@ pub mod orderbook;
 pub mod strategy;
 pub mod risk;
 pub mod arbitrage;
+pub mod routing;
+pub mod arb;
 
 pub mod prelude {
     pub use crate::core::types::*;
     pub use crate::exchange::{ExchangeId, ExchangeManager};
-    pub use crate::orderbook::{ConsolidatedBook, NBBO};
-    pub use crate::strategy::CrossExchangeMM;
+    pub use crate::orderbook::{BookError, ConsolidatedBook, LadderLevel, MarketSpec, NBBO};
+    pub use crate::strategy::{CrossExchangeMM, PassiveHedgedQuoter};
     pub use crate::arbitrage::{ArbitrageDetector, ArbitrageOpportunity};
     pub use crate::risk::RiskManager;
+    pub use crate::routing::{HybridRouter, RoutePlan};
+    pub use crate::arb::{Detector as CrossVenueDetector, TakerFeeTable};
 }
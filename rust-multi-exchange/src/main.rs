@@ -31,22 +31,26 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Starting Multi-Exchange HFT Bot");
 
+    // Initialize consolidated order book
+    let symbol = Symbol::new("BTCUSDT");
+    let consolidated_book = Arc::new(ConsolidatedBook::new(symbol.clone()));
+
     // Initialize exchange manager
     let exchange_manager = Arc::new(ExchangeManager::new());
 
     // Add exchange clients
     let binance = Arc::new(BinanceClient::new(true)) as Arc<dyn ExchangeClient>;
-    let bybit = Arc::new(BybitClient::new(true)) as Arc<dyn ExchangeClient>;
+
+    let mut bybit = BybitClient::new(true, consolidated_book.clone());
+    bybit.connect().await?;
+    bybit.subscribe(&symbol).await?;
+    let bybit = Arc::new(bybit) as Arc<dyn ExchangeClient>;
 
     exchange_manager.add_client(binance);
     exchange_manager.add_client(bybit);
 
     info!("Configured {} exchanges", 2);
 
-    // Initialize consolidated order book
-    let symbol = Symbol::new("BTCUSDT");
-    let consolidated_book = Arc::new(ConsolidatedBook::new(symbol.clone()));
-
     // Initialize cross-exchange market maker
     let mm_params = CrossExchangeMMParams {
         target_spread_bps: 15.0,
@@ -72,7 +76,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Initialize risk manager
     let risk_limits = CrossExchangeRiskLimits::default();
-    let mut risk_manager = RiskManager::new(risk_limits);
+    let risk_manager = RiskManager::new(risk_limits);
 
     info!("All components initialized");
     info!("Monitoring {} across exchanges", symbol);
@@ -80,37 +84,8 @@ async fn main() -> anyhow::Result<()> {
     // Enable market maker
     market_maker.enable();
 
-    // Simulate market data updates (in production, would come from WebSocket)
-    let book = consolidated_book.clone();
-    tokio::spawn(async move {
-        let mut counter = 0u64;
-        loop {
-            // Simulate price updates
-            let base_price = to_price(50000.0);
-            let noise = ((counter % 100) as i64 - 50) * to_price(0.1);
-
-            // Binance quotes
-            book.update(
-                ExchangeId::Binance,
-                base_price + noise - to_price(0.5),
-                to_qty(1.0),
-                base_price + noise + to_price(0.5),
-                to_qty(1.0),
-            );
-
-            // Bybit quotes (slightly different)
-            book.update(
-                ExchangeId::Bybit,
-                base_price + noise - to_price(0.6),
-                to_qty(0.8),
-                base_price + noise + to_price(0.4),
-                to_qty(0.9),
-            );
-
-            counter += 1;
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-        }
-    });
+    // Market data now streams in from each exchange's own WebSocket subscription
+    // (see BybitClient::subscribe) directly into `consolidated_book`.
 
     // Main loop
     let mut position = CrossExchangePosition::default();
@@ -139,9 +114,15 @@ async fn main() -> anyhow::Result<()> {
                     );
 
                     // Execute arbitrage
-                    if let Ok(profit) = arb_executor.execute(&opp).await {
-                        info!("Arbitrage executed, profit: ${:.4}", profit);
-                        arb_detector.record_execution(true);
+                    match arb_executor.execute(&opp, &exchange_manager).await {
+                        Ok(result) => {
+                            info!("Arbitrage executed, profit: ${:.4}", result.realized_profit);
+                            arb_detector.record_execution(true);
+                        }
+                        Err(e) => {
+                            warn!("Arbitrage execution failed: {}", e);
+                            arb_detector.record_execution(false);
+                        }
                     }
                 }
 
@@ -150,9 +131,9 @@ async fn main() -> anyhow::Result<()> {
                     let quotes = market_maker.compute_quotes(&consolidated_book, &position);
 
                     if quotes.should_quote {
-                        for (exchange, bid, ask, bid_sz, ask_sz) in &quotes.quotes {
+                        for (exchange, _price, size, side) in &quotes.quotes {
                             // Check risk before sending
-                            if risk_manager.check_order(*exchange, Side::Buy, *bid_sz) {
+                            if risk_manager.check_order(*exchange, *side, *size) {
                                 // Would send order here
                             }
                         }
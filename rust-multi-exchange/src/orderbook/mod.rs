@@ -0,0 +1,560 @@
+//! Consolidated order book across multiple exchanges
+
+use crate::core::types::*;
+use hashbrown::HashMap;
+use parking_lot::RwLock;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, VecDeque};
+use thiserror::Error;
+
+/// Per-symbol price/size increments a [`ConsolidatedBook`] enforces on every
+/// per-exchange update, so a feed quoting in the wrong tick/lot for a venue can't
+/// silently corrupt the aggregated NBBO.
+#[derive(Debug, Clone, Copy)]
+pub struct MarketSpec {
+    pub tick_size: Price,
+    pub lot_size: Quantity,
+    pub min_size: Quantity,
+}
+
+impl MarketSpec {
+    pub fn new(tick_size: Price, lot_size: Quantity, min_size: Quantity) -> Self {
+        MarketSpec { tick_size, lot_size, min_size }
+    }
+
+    pub fn validate_price(&self, price: Price) -> Result<(), BookError> {
+        if self.tick_size > 0 && price % self.tick_size != 0 {
+            return Err(BookError::InvalidTick { price, tick_size: self.tick_size });
+        }
+        Ok(())
+    }
+
+    pub fn validate_quantity(&self, quantity: Quantity) -> Result<(), BookError> {
+        if quantity < self.min_size {
+            return Err(BookError::BelowMinimumSize { quantity, min_size: self.min_size });
+        }
+        if self.lot_size > 0 && quantity % self.lot_size != 0 {
+            return Err(BookError::InvalidLotSize { quantity, lot_size: self.lot_size });
+        }
+        Ok(())
+    }
+
+    /// Round `price` down to the nearest multiple of `tick_size`
+    pub fn round_to_tick(&self, price: Price) -> Price {
+        if self.tick_size <= 0 {
+            return price;
+        }
+        (price / self.tick_size) * self.tick_size
+    }
+
+    /// Round `quantity` down to the nearest multiple of `lot_size`
+    pub fn round_to_lot(&self, quantity: Quantity) -> Quantity {
+        if self.lot_size <= 0 {
+            return quantity;
+        }
+        (quantity / self.lot_size) * self.lot_size
+    }
+}
+
+impl Default for MarketSpec {
+    /// Permissive spec (tick/lot of 1 native unit, no minimum) for callers that
+    /// don't need validation
+    fn default() -> Self {
+        MarketSpec { tick_size: 1, lot_size: 1, min_size: 0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Error)]
+pub enum BookError {
+    #[error("price {price} is not a multiple of tick size {tick_size}")]
+    InvalidTick { price: Price, tick_size: Price },
+    #[error("quantity {quantity} is not a multiple of lot size {lot_size}")]
+    InvalidLotSize { quantity: Quantity, lot_size: Quantity },
+    #[error("quantity {quantity} is below minimum size {min_size}")]
+    BelowMinimumSize { quantity: Quantity, min_size: Quantity },
+}
+
+/// National Best Bid and Offer across all exchanges
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NBBO {
+    pub best_bid: Price,
+    pub best_ask: Price,
+    pub best_bid_qty: Quantity,
+    pub best_ask_qty: Quantity,
+    pub best_bid_exchange: ExchangeId,
+    pub best_ask_exchange: ExchangeId,
+    pub timestamp: Timestamp,
+}
+
+impl NBBO {
+    pub fn spread(&self) -> Price {
+        self.best_ask - self.best_bid
+    }
+
+    pub fn spread_bps(&self) -> f64 {
+        let mid = (self.best_bid + self.best_ask) / 2;
+        if mid == 0 { 0.0 } else { 10000.0 * self.spread() as f64 / mid as f64 }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.best_bid > 0 && self.best_ask > 0 && self.best_bid < self.best_ask
+    }
+
+    /// Check if there's a cross-exchange arbitrage opportunity
+    pub fn has_arbitrage(&self) -> bool {
+        self.best_bid_exchange != self.best_ask_exchange && self.best_bid >= self.best_ask
+    }
+}
+
+/// Per-exchange order book
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeBook {
+    pub exchange: ExchangeId,
+    pub bids: BTreeMap<Reverse<Price>, Quantity>,
+    pub asks: BTreeMap<Price, Quantity>,
+    pub last_update: Timestamp,
+}
+
+impl ExchangeBook {
+    pub fn new(exchange: ExchangeId) -> Self {
+        ExchangeBook {
+            exchange,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            last_update: 0,
+        }
+    }
+
+    pub fn best_bid(&self) -> Option<(Price, Quantity)> {
+        self.bids.first_key_value().map(|(Reverse(p), q)| (*p, *q))
+    }
+
+    pub fn best_ask(&self) -> Option<(Price, Quantity)> {
+        self.asks.first_key_value().map(|(p, q)| (*p, *q))
+    }
+
+    pub fn update_bid(&mut self, price: Price, qty: Quantity) {
+        if qty == 0 {
+            self.bids.remove(&Reverse(price));
+        } else {
+            self.bids.insert(Reverse(price), qty);
+        }
+        self.last_update = now_nanos();
+    }
+
+    pub fn update_ask(&mut self, price: Price, qty: Quantity) {
+        if qty == 0 {
+            self.asks.remove(&price);
+        } else {
+            self.asks.insert(price, qty);
+        }
+        self.last_update = now_nanos();
+    }
+
+    /// Replace this exchange's entire depth ladder in one shot, as from a full L2
+    /// snapshot, rather than updating a single bid/ask level at a time.
+    pub fn apply_depth(&mut self, bids: Vec<(Price, Quantity)>, asks: Vec<(Price, Quantity)>) {
+        self.bids = bids.into_iter().map(|(price, qty)| (Reverse(price), qty)).collect();
+        self.asks = asks.into_iter().collect();
+        self.last_update = now_nanos();
+    }
+}
+
+/// One price level of the merged, cross-exchange ladder produced by
+/// [`ConsolidatedBook::consolidated_bids`]/[`ConsolidatedBook::consolidated_asks`],
+/// tagged with the venue it came from so a sweep can be routed back to it.
+#[derive(Debug, Clone, Copy)]
+pub struct LadderLevel {
+    pub exchange: ExchangeId,
+    pub price: Price,
+    pub quantity: Quantity,
+}
+
+/// A single per-exchange price level changing in a [`ConsolidatedBook`], with
+/// `new_qty` of `0` meaning the level was deleted. `seq` is a monotonic counter
+/// local to this book, incremented once per emitted update, queued for
+/// consumers to drain via [`ConsolidatedBook::pop_level_update`]. Mirrors
+/// `OrderBook::LevelUpdate` in the single-exchange book, tagged additionally
+/// with the venue the level belongs to.
+#[derive(Debug, Clone, Copy)]
+pub struct ConsolidatedLevelUpdate {
+    pub seq: SequenceNum,
+    pub exchange: ExchangeId,
+    pub side: Side,
+    pub price: Price,
+    pub new_qty: Quantity,
+}
+
+/// Full merged L2 snapshot of a [`ConsolidatedBook`], with the level-update
+/// sequence number current as of the snapshot. A subscriber syncs by fetching
+/// this via [`ConsolidatedBook::consolidated_checkpoint`], then applying
+/// [`ConsolidatedBook::pop_level_update`]s whose `seq` exceeds it, discarding
+/// any at or below it.
+#[derive(Debug, Clone)]
+pub struct ConsolidatedCheckpoint {
+    pub seq: SequenceNum,
+    pub bids: Vec<LadderLevel>,
+    pub asks: Vec<LadderLevel>,
+}
+
+/// Consolidated order book aggregating multiple exchanges
+pub struct ConsolidatedBook {
+    symbol: Symbol,
+    books: RwLock<HashMap<ExchangeId, ExchangeBook>>,
+    nbbo: RwLock<NBBO>,
+    spec: MarketSpec,
+    // Monotonic counter for `ConsolidatedLevelUpdate::seq`, local to this book
+    level_seq: RwLock<SequenceNum>,
+    // Bounded queue of per-exchange level-quantity changes, for the
+    // checkpoint/delta streaming protocol (`consolidated_checkpoint`/`pop_level_update`)
+    level_updates: RwLock<VecDeque<ConsolidatedLevelUpdate>>,
+}
+
+impl ConsolidatedBook {
+    /// Oldest level updates are dropped once the queue reaches this size
+    pub const LEVEL_UPDATE_QUEUE_CAPACITY: usize = 4096;
+
+    /// Build a book with a permissive [`MarketSpec`] (tick/lot of 1 native unit, no
+    /// minimum) — use [`Self::with_spec`] to enforce a symbol's real increments.
+    pub fn new(symbol: Symbol) -> Self {
+        Self::with_spec(symbol, MarketSpec::default())
+    }
+
+    pub fn with_spec(symbol: Symbol, spec: MarketSpec) -> Self {
+        ConsolidatedBook {
+            symbol,
+            books: RwLock::new(HashMap::new()),
+            nbbo: RwLock::new(NBBO::default()),
+            spec,
+            level_seq: RwLock::new(0),
+            level_updates: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    /// Bump `level_seq` and queue a [`ConsolidatedLevelUpdate`], dropping the
+    /// oldest queued update if `level_updates` is already at capacity.
+    fn push_level_update(&self, exchange: ExchangeId, side: Side, price: Price, new_qty: Quantity) {
+        let mut level_seq = self.level_seq.write();
+        *level_seq += 1;
+        let mut level_updates = self.level_updates.write();
+        if level_updates.len() >= Self::LEVEL_UPDATE_QUEUE_CAPACITY {
+            level_updates.pop_front();
+        }
+        level_updates.push_back(ConsolidatedLevelUpdate { seq: *level_seq, exchange, side, price, new_qty });
+    }
+
+    /// Pop the oldest queued [`ConsolidatedLevelUpdate`], if any.
+    pub fn pop_level_update(&self) -> Option<ConsolidatedLevelUpdate> {
+        self.level_updates.write().pop_front()
+    }
+
+    /// Number of level updates currently queued
+    pub fn level_update_count(&self) -> usize {
+        self.level_updates.read().len()
+    }
+
+    /// Full merged L2 snapshot of the book, with the level-update sequence
+    /// number current as of this call. A subscriber syncs by fetching this,
+    /// then applying [`Self::pop_level_update`]s whose `seq` exceeds it,
+    /// discarding any at or below it.
+    pub fn consolidated_checkpoint(&self) -> ConsolidatedCheckpoint {
+        ConsolidatedCheckpoint {
+            seq: *self.level_seq.read(),
+            bids: self.consolidated_bids(),
+            asks: self.consolidated_asks(),
+        }
+    }
+
+    pub fn spec(&self) -> MarketSpec {
+        self.spec
+    }
+
+    pub fn update(
+        &self,
+        exchange: ExchangeId,
+        bid: Price,
+        bid_qty: Quantity,
+        ask: Price,
+        ask_qty: Quantity,
+    ) -> Result<(), BookError> {
+        self.spec.validate_price(bid)?;
+        self.spec.validate_price(ask)?;
+        if bid_qty > 0 {
+            self.spec.validate_quantity(bid_qty)?;
+        }
+        if ask_qty > 0 {
+            self.spec.validate_quantity(ask_qty)?;
+        }
+
+        {
+            let mut books = self.books.write();
+            let book = books.entry(exchange).or_insert_with(|| ExchangeBook::new(exchange));
+            book.update_bid(bid, bid_qty);
+            book.update_ask(ask, ask_qty);
+        }
+        self.push_level_update(exchange, Side::Buy, bid, bid_qty);
+        self.push_level_update(exchange, Side::Sell, ask, ask_qty);
+        self.recalculate_nbbo();
+        Ok(())
+    }
+
+    /// Replace `exchange`'s full depth ladder with `bids`/`asks`, validating every
+    /// level against this book's `MarketSpec` up front (all-or-nothing, mirroring
+    /// `OrderBook::apply_snapshot` in the single-exchange book). Use this instead of
+    /// [`Self::update`] when a venue streams real L2 depth rather than a single
+    /// top-of-book quote, so [`Self::consolidated_bids`]/[`Self::consolidated_asks`]
+    /// have more than one level per exchange to merge.
+    pub fn update_depth(
+        &self,
+        exchange: ExchangeId,
+        bids: Vec<(Price, Quantity)>,
+        asks: Vec<(Price, Quantity)>,
+    ) -> Result<(), BookError> {
+        for &(price, qty) in bids.iter().chain(asks.iter()) {
+            self.spec.validate_price(price)?;
+            self.spec.validate_quantity(qty)?;
+        }
+
+        {
+            let mut books = self.books.write();
+            let book = books.entry(exchange).or_insert_with(|| ExchangeBook::new(exchange));
+            for (&Reverse(price), _) in book.bids.iter() {
+                self.push_level_update(exchange, Side::Buy, price, 0);
+            }
+            for (&price, _) in book.asks.iter() {
+                self.push_level_update(exchange, Side::Sell, price, 0);
+            }
+            for &(price, qty) in bids.iter() {
+                self.push_level_update(exchange, Side::Buy, price, qty);
+            }
+            for &(price, qty) in asks.iter() {
+                self.push_level_update(exchange, Side::Sell, price, qty);
+            }
+            book.apply_depth(bids, asks);
+        }
+        self.recalculate_nbbo();
+        Ok(())
+    }
+
+    /// Every resting bid across all exchanges, merged into a single ladder sorted
+    /// best price (highest) first.
+    pub fn consolidated_bids(&self) -> Vec<LadderLevel> {
+        let books = self.books.read();
+        let mut levels: Vec<LadderLevel> = books
+            .values()
+            .flat_map(|book| {
+                book.bids
+                    .iter()
+                    .map(move |(&Reverse(price), &quantity)| LadderLevel { exchange: book.exchange, price, quantity })
+            })
+            .collect();
+        levels.sort_by(|a, b| b.price.cmp(&a.price));
+        levels
+    }
+
+    /// Every resting ask across all exchanges, merged into a single ladder sorted
+    /// best price (lowest) first.
+    pub fn consolidated_asks(&self) -> Vec<LadderLevel> {
+        let books = self.books.read();
+        let mut levels: Vec<LadderLevel> = books
+            .values()
+            .flat_map(|book| {
+                book.asks
+                    .iter()
+                    .map(move |(&price, &quantity)| LadderLevel { exchange: book.exchange, price, quantity })
+            })
+            .collect();
+        levels.sort_by(|a, b| a.price.cmp(&b.price));
+        levels
+    }
+
+    /// Walk the merged ladder for `side` (asks to buy, bids to sell) best-price-first,
+    /// filling greedily until `target_qty` is reached or the ladder is exhausted.
+    /// Returns the blended VWAP and the per-exchange fill plan a smart order router
+    /// would need to actually execute the sweep, or `None` if no liquidity exists.
+    pub fn sweep_vwap(&self, side: Side, target_qty: Quantity) -> Option<(Price, Vec<(ExchangeId, Price, Quantity)>)> {
+        let levels = match side {
+            Side::Buy => self.consolidated_asks(),
+            Side::Sell => self.consolidated_bids(),
+        };
+
+        let mut remaining = target_qty;
+        let mut total_value: i64 = 0;
+        let mut total_qty: Quantity = 0;
+        let mut plan = Vec::new();
+
+        for level in levels {
+            if remaining <= 0 {
+                break;
+            }
+            let fill_qty = remaining.min(level.quantity);
+            total_value += level.price * fill_qty;
+            total_qty += fill_qty;
+            remaining -= fill_qty;
+            plan.push((level.exchange, level.price, fill_qty));
+        }
+
+        if total_qty == 0 {
+            return None;
+        }
+
+        Some((total_value / total_qty, plan))
+    }
+
+    fn recalculate_nbbo(&self) {
+        let books = self.books.read();
+        let mut nbbo = NBBO::default();
+        nbbo.timestamp = now_nanos();
+
+        for (exchange, book) in books.iter() {
+            if let Some((bid, qty)) = book.best_bid() {
+                if bid > nbbo.best_bid {
+                    nbbo.best_bid = bid;
+                    nbbo.best_bid_qty = qty;
+                    nbbo.best_bid_exchange = *exchange;
+                }
+            }
+            if let Some((ask, qty)) = book.best_ask() {
+                if nbbo.best_ask == 0 || ask < nbbo.best_ask {
+                    nbbo.best_ask = ask;
+                    nbbo.best_ask_qty = qty;
+                    nbbo.best_ask_exchange = *exchange;
+                }
+            }
+        }
+
+        *self.nbbo.write() = nbbo;
+    }
+
+    pub fn get_nbbo(&self) -> NBBO {
+        *self.nbbo.read()
+    }
+
+    pub fn get_exchange_quote(&self, exchange: ExchangeId) -> Option<ExchangeQuote> {
+        let books = self.books.read();
+        books.get(&exchange).and_then(|book| {
+            let (bid, bid_qty) = book.best_bid()?;
+            let (ask, ask_qty) = book.best_ask()?;
+            Some(ExchangeQuote {
+                exchange,
+                bid,
+                ask,
+                bid_qty,
+                ask_qty,
+                timestamp: book.last_update,
+                latency_ns: 0,
+            })
+        })
+    }
+
+    /// Top-of-book quote for every exchange currently contributing to this book
+    pub fn all_quotes(&self) -> Vec<ExchangeQuote> {
+        let exchanges: Vec<ExchangeId> = self.books.read().keys().copied().collect();
+        exchanges
+            .into_iter()
+            .filter_map(|exchange| self.get_exchange_quote(exchange))
+            .collect()
+    }
+
+    pub fn find_arbitrage(&self) -> Option<ArbitrageOpportunity> {
+        let nbbo = self.get_nbbo();
+
+        // Check if best bid > best ask on different exchanges
+        if nbbo.best_bid_exchange != nbbo.best_ask_exchange && nbbo.best_bid > nbbo.best_ask {
+            let profit_bps = 10000.0 * (nbbo.best_bid - nbbo.best_ask) as f64 /
+                            ((nbbo.best_bid + nbbo.best_ask) / 2) as f64;
+            let qty = nbbo.best_bid_qty.min(nbbo.best_ask_qty);
+
+            return Some(ArbitrageOpportunity {
+                symbol: self.symbol.clone(),
+                buy_exchange: nbbo.best_ask_exchange,
+                sell_exchange: nbbo.best_bid_exchange,
+                buy_price: nbbo.best_ask,
+                sell_price: nbbo.best_bid,
+                quantity: qty,
+                profit_bps,
+                timestamp: now_nanos(),
+            });
+        }
+
+        None
+    }
+
+    /// Like [`Self::find_arbitrage`], but walks the full merged ladder instead of
+    /// just the NBBO, so arbitrage that only appears once the top levels are
+    /// exhausted (or that the top-of-book alone under-sizes) is still found.
+    ///
+    /// Matches the cheapest remaining ask against the richest remaining bid,
+    /// level by level, for as long as the bid still beats the ask by at least
+    /// `min_profit_bps`; same-exchange pairs are skipped (but still consume
+    /// quantity from both legs, since that liquidity isn't available to a
+    /// cross-exchange arb either way). Matched quantity and profit are accumulated
+    /// per distinct `(buy_exchange, sell_exchange)` pair and each pair is returned
+    /// as a single size-weighted `ArbitrageOpportunity`.
+    pub fn find_arbitrage_depth(&self, min_profit_bps: f64) -> Vec<ArbitrageOpportunity> {
+        let asks = self.consolidated_asks();
+        let bids = self.consolidated_bids();
+
+        let mut ask_remaining: Vec<Quantity> = asks.iter().map(|l| l.quantity).collect();
+        let mut bid_remaining: Vec<Quantity> = bids.iter().map(|l| l.quantity).collect();
+
+        // (buy_exchange, sell_exchange) -> (total_qty, Σ buy_price*qty, Σ sell_price*qty, Σ profit_bps*qty)
+        let mut pairs: HashMap<(ExchangeId, ExchangeId), (Quantity, i64, i64, f64)> = HashMap::new();
+
+        let mut ai = 0;
+        let mut bi = 0;
+
+        while ai < asks.len() && bi < bids.len() {
+            let ask = asks[ai];
+            let bid = bids[bi];
+
+            if bid.price <= ask.price {
+                break; // cheapest remaining ask vs. richest remaining bid no longer crosses
+            }
+
+            let mid = (ask.price + bid.price) / 2;
+            let edge_bps = if mid == 0 { 0.0 } else { 10000.0 * (bid.price - ask.price) as f64 / mid as f64 };
+            if edge_bps < min_profit_bps {
+                break; // the best remaining legs' edge has turned negative (below the bar)
+            }
+
+            let qty = ask_remaining[ai].min(bid_remaining[bi]);
+            if qty > 0 && ask.exchange != bid.exchange {
+                let entry = pairs.entry((ask.exchange, bid.exchange)).or_insert((0, 0, 0, 0.0));
+                entry.0 += qty;
+                entry.1 += ask.price * qty;
+                entry.2 += bid.price * qty;
+                entry.3 += edge_bps * from_qty(qty);
+            }
+
+            ask_remaining[ai] -= qty;
+            bid_remaining[bi] -= qty;
+            if ask_remaining[ai] == 0 {
+                ai += 1;
+            }
+            if bid_remaining[bi] == 0 {
+                bi += 1;
+            }
+        }
+
+        pairs
+            .into_iter()
+            .map(|((buy_exchange, sell_exchange), (qty, buy_value, sell_value, profit_value))| {
+                ArbitrageOpportunity {
+                    symbol: self.symbol.clone(),
+                    buy_exchange,
+                    sell_exchange,
+                    buy_price: buy_value / qty,
+                    sell_price: sell_value / qty,
+                    quantity: qty,
+                    profit_bps: profit_value / from_qty(qty),
+                    timestamp: now_nanos(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn symbol(&self) -> &Symbol {
+        &self.symbol
+    }
+}
@@ -0,0 +1,273 @@
+//! Cross-exchange risk management
+
+use crate::core::types::*;
+use hashbrown::HashMap;
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Cross-exchange risk limits
+#[derive(Debug, Clone)]
+pub struct CrossExchangeRiskLimits {
+    pub max_position_per_exchange: Quantity,
+    pub max_total_position: Quantity,
+    pub max_daily_loss: Money,
+    pub max_drawdown: Money,
+    pub kill_switch_enabled: bool,
+}
+
+impl Default for CrossExchangeRiskLimits {
+    fn default() -> Self {
+        CrossExchangeRiskLimits {
+            max_position_per_exchange: to_qty(0.1),
+            max_total_position: to_qty(0.2),
+            max_daily_loss: to_money(500.0),
+            max_drawdown: to_money(1000.0),
+            kill_switch_enabled: true,
+        }
+    }
+}
+
+/// Per-exchange collateral weighting and oracle price feeding the
+/// cross-margin health computation, modeled on Mango's account-health
+/// design. Two weight pairs are kept: "initial" (stricter) gates new orders
+/// in `check_order`, while "maintenance" (looser) governs liquidation in
+/// `on_fill`. Defaults to a price of zero, which contributes no value to
+/// either health figure until configured via [`RiskManager::set_asset_config`].
+#[derive(Debug, Clone, Copy)]
+pub struct AssetConfig {
+    pub oracle_price: Price,
+    /// Multiplier on a positive (collateral) position's value for initial health; < 1.0 discounts it
+    pub initial_asset_weight: f64,
+    /// Multiplier on a negative (borrowed/short) position's value for initial health; > 1.0 penalizes it
+    pub initial_liab_weight: f64,
+    /// Looser asset weight used for maintenance (liquidation) health
+    pub maintenance_asset_weight: f64,
+    /// Looser liability weight used for maintenance (liquidation) health
+    pub maintenance_liab_weight: f64,
+}
+
+impl Default for AssetConfig {
+    fn default() -> Self {
+        AssetConfig {
+            oracle_price: 0,
+            initial_asset_weight: 1.0,
+            initial_liab_weight: 1.0,
+            maintenance_asset_weight: 1.0,
+            maintenance_liab_weight: 1.0,
+        }
+    }
+}
+
+/// Cross-exchange risk manager. Every mutating method takes `&self` so a
+/// single `Arc<RiskManager>` can be shared by the quoting loop and the
+/// fill-handling path concurrently: `positions`, `asset_configs`, and
+/// `pnl_by_exchange`/`daily_pnl` are each their own `parking_lot::RwLock`,
+/// while `kill_switch` stays a plain `AtomicBool` since it's only ever set
+/// or read, never read-modify-written.
+///
+/// Lock ordering: if a method ever needs more than one of these locks at
+/// once, acquire them in the order declared on the struct — `positions`,
+/// then `asset_configs`, then `pnl_by_exchange`, then `daily_pnl` — to avoid
+/// a cycle with a thread acquiring the reverse order. In practice no method
+/// here holds more than one lock at a time: each lock is acquired, used, and
+/// dropped before the next is taken, e.g. `on_fill` releases its `positions`
+/// write guard before `maintenance_health` takes its own `positions`/
+/// `asset_configs` read locks.
+pub struct RiskManager {
+    limits: CrossExchangeRiskLimits,
+    positions: RwLock<HashMap<ExchangeId, Quantity>>,
+    asset_configs: RwLock<HashMap<ExchangeId, AssetConfig>>,
+    pnl_by_exchange: RwLock<HashMap<ExchangeId, Money>>,
+    daily_pnl: RwLock<Money>,
+    kill_switch: AtomicBool,
+}
+
+impl RiskManager {
+    pub fn new(limits: CrossExchangeRiskLimits) -> Self {
+        RiskManager {
+            limits,
+            positions: RwLock::new(HashMap::new()),
+            asset_configs: RwLock::new(HashMap::new()),
+            pnl_by_exchange: RwLock::new(HashMap::new()),
+            daily_pnl: RwLock::new(Money::ZERO),
+            kill_switch: AtomicBool::new(false),
+        }
+    }
+
+    /// Configure the oracle price and asset/liability weights used to value
+    /// `exchange`'s position in the health computation
+    pub fn set_asset_config(&self, exchange: ExchangeId, config: AssetConfig) {
+        self.asset_configs.write().insert(exchange, config);
+    }
+
+    /// Weighted value of a single position: positive values (collateral) get
+    /// the asset weight, negative values (borrowed/short) get the liability
+    /// weight, per the requested weight set
+    fn weighted_value(config: AssetConfig, qty: Quantity, maintenance: bool) -> f64 {
+        let value = from_qty(qty) * from_price(config.oracle_price);
+        if value >= 0.0 {
+            let weight = if maintenance { config.maintenance_asset_weight } else { config.initial_asset_weight };
+            value * weight
+        } else {
+            let weight = if maintenance { config.maintenance_liab_weight } else { config.initial_liab_weight };
+            value * weight
+        }
+    }
+
+    /// Sum of weighted position values across exchanges: Σ(weighted assets)
+    /// − Σ(weighted liabilities). Takes its own `asset_configs` read lock;
+    /// callers must not already be holding it.
+    fn compute_health(&self, positions: &HashMap<ExchangeId, Quantity>, maintenance: bool) -> f64 {
+        let asset_configs = self.asset_configs.read();
+        positions
+            .iter()
+            .map(|(exchange, &qty)| {
+                let config = asset_configs.get(exchange).copied().unwrap_or_default();
+                Self::weighted_value(config, qty, maintenance)
+            })
+            .sum()
+    }
+
+    /// Current account health under initial (stricter) weights; the same
+    /// computation `check_order` simulates a candidate fill against
+    pub fn initial_health(&self) -> f64 {
+        let positions = self.positions.read().clone();
+        self.compute_health(&positions, false)
+    }
+
+    /// Current account health under maintenance (looser) weights; dropping
+    /// below zero is what `on_fill` treats as a liquidation trigger
+    pub fn maintenance_health(&self) -> f64 {
+        let positions = self.positions.read().clone();
+        self.compute_health(&positions, true)
+    }
+
+    /// Check if order is allowed
+    pub fn check_order(&self, exchange: ExchangeId, side: Side, qty: Quantity) -> bool {
+        if self.kill_switch.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        // Snapshot positions under one read lock, then drop it before the
+        // simulated health check re-derives its own lock (see struct-level
+        // lock-ordering note)
+        let (current_pos, total, mut simulated) = {
+            let positions = self.positions.read();
+            let current_pos = *positions.get(&exchange).unwrap_or(&0);
+            let total: Quantity = positions.values().sum();
+            (current_pos, total, positions.clone())
+        };
+
+        let new_pos = match side {
+            Side::Buy => current_pos + qty,
+            Side::Sell => current_pos - qty,
+        };
+
+        // Check per-exchange limit
+        if new_pos.abs() > self.limits.max_position_per_exchange {
+            return false;
+        }
+
+        // Check total position limit
+        let new_total = match side {
+            Side::Buy => total + qty,
+            Side::Sell => total - qty,
+        };
+
+        if new_total.abs() > self.limits.max_total_position {
+            return false;
+        }
+
+        // Simulate the candidate fill and reject if it would push initial
+        // health negative, i.e. weighted liabilities would exceed weighted
+        // collateral across exchanges
+        simulated.insert(exchange, new_pos);
+        if self.compute_health(&simulated, false) < 0.0 {
+            return false;
+        }
+
+        true
+    }
+
+    /// Update position after fill. `pnl` is fixed-point so repeated
+    /// accumulation across many fills doesn't accrue `f64` rounding drift in
+    /// a ledger that gates the kill switch; overflow is treated as a kill
+    /// switch trigger rather than silently wrapping.
+    pub fn on_fill(&self, exchange: ExchangeId, side: Side, qty: Quantity, pnl: Money) {
+        {
+            let mut positions = self.positions.write();
+            let pos = positions.entry(exchange).or_insert(0);
+            *pos = match side {
+                Side::Buy => *pos + qty,
+                Side::Sell => *pos - qty,
+            };
+        }
+
+        {
+            let mut pnl_by_exchange = self.pnl_by_exchange.write();
+            let exchange_pnl = pnl_by_exchange.entry(exchange).or_insert(Money::ZERO);
+            match exchange_pnl.checked_add(pnl) {
+                Some(total) => *exchange_pnl = total,
+                None => tracing::error!("PnL overflow accumulating for exchange {}", exchange),
+            }
+        }
+
+        let current_total = {
+            let mut daily_pnl = self.daily_pnl.write();
+            match daily_pnl.checked_add(pnl) {
+                Some(total) => {
+                    *daily_pnl = total;
+                    total
+                }
+                None => {
+                    drop(daily_pnl);
+                    self.activate_kill_switch("Daily PnL overflow");
+                    *self.daily_pnl.read()
+                }
+            }
+        };
+
+        // Check loss limits
+        if self.limits.kill_switch_enabled && current_total < -self.limits.max_daily_loss {
+            self.activate_kill_switch("Daily loss limit exceeded");
+        }
+
+        // Check maintenance health: this is the liquidation trigger, looser
+        // than the initial-health gate `check_order` applies to new orders
+        if self.limits.kill_switch_enabled && self.maintenance_health() < 0.0 {
+            self.activate_kill_switch("Maintenance health below zero");
+        }
+    }
+
+    pub fn activate_kill_switch(&self, reason: &str) {
+        tracing::error!("KILL SWITCH ACTIVATED: {}", reason);
+        self.kill_switch.store(true, Ordering::Relaxed);
+    }
+
+    pub fn deactivate_kill_switch(&self) {
+        self.kill_switch.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_kill_switch_active(&self) -> bool {
+        self.kill_switch.load(Ordering::Relaxed)
+    }
+
+    pub fn get_position(&self, exchange: ExchangeId) -> Quantity {
+        *self.positions.read().get(&exchange).unwrap_or(&0)
+    }
+
+    pub fn get_total_position(&self) -> Quantity {
+        self.positions.read().values().sum()
+    }
+
+    /// Daily PnL converted to `f64` for display; the ledger itself stays
+    /// fixed-point
+    pub fn get_daily_pnl(&self) -> f64 {
+        from_money(*self.daily_pnl.read())
+    }
+
+    pub fn reset_daily(&self) {
+        *self.daily_pnl.write() = Money::ZERO;
+        self.pnl_by_exchange.write().clear();
+    }
+}
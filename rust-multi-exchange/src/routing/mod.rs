@@ -0,0 +1,179 @@
+//! Liquidity-aware order routing across exchanges and synthetic AMM sources
+
+use crate::core::types::*;
+use crate::exchange::ExchangeManager;
+use crate::orderbook::ConsolidatedBook;
+
+/// A constant-product (x*y=k) pool used as a fallback liquidity source once
+/// order-book depth across connected venues is exhausted
+#[derive(Debug, Clone, Copy)]
+pub struct AmmPool {
+    pub reserve_base: f64,
+    pub reserve_quote: f64,
+}
+
+impl AmmPool {
+    /// Average execution price for trading `qty` base units against this pool
+    pub fn average_price(&self, side: Side, qty: f64) -> Option<f64> {
+        if qty <= 0.0 {
+            return None;
+        }
+        let k = self.reserve_base * self.reserve_quote;
+
+        match side {
+            // Buying base: we pay quote in, removing qty base from the pool
+            Side::Buy => {
+                let new_base = self.reserve_base - qty;
+                if new_base <= 0.0 {
+                    return None;
+                }
+                let new_quote = k / new_base;
+                Some((new_quote - self.reserve_quote) / qty)
+            }
+            // Selling base: we add qty base, removing quote from the pool
+            Side::Sell => {
+                let new_base = self.reserve_base + qty;
+                let new_quote = k / new_base;
+                Some((self.reserve_quote - new_quote) / qty)
+            }
+        }
+    }
+}
+
+/// Configuration for the hybrid router
+#[derive(Debug, Clone)]
+pub struct RouterConfig {
+    /// Reject a plan whose expected slippage vs. the current mid exceeds this
+    pub max_slippage_bps: f64,
+    /// Fallback AMM liquidity used once venue order books are exhausted
+    pub amm_pool: Option<AmmPool>,
+}
+
+impl Default for RouterConfig {
+    fn default() -> Self {
+        RouterConfig {
+            max_slippage_bps: 25.0,
+            amm_pool: None,
+        }
+    }
+}
+
+/// A routing decision: the child orders ("legs") a parent order should be split into,
+/// and the expected execution quality of the plan as a whole
+#[derive(Debug, Clone, Default)]
+pub struct RoutePlan {
+    pub legs: Vec<(ExchangeId, Price, Quantity)>,
+    pub expected_avg_price: f64,
+    pub expected_slippage_bps: f64,
+}
+
+impl RoutePlan {
+    pub fn total_quantity(&self) -> Quantity {
+        self.legs.iter().map(|(_, _, qty)| *qty).sum()
+    }
+}
+
+/// Splits a parent order across connected venues by walking top-of-book liquidity,
+/// then an AMM-style synthetic quote for whatever quantity the venues can't fill
+pub struct HybridRouter {
+    config: RouterConfig,
+}
+
+impl HybridRouter {
+    pub fn new(config: RouterConfig) -> Self {
+        HybridRouter { config }
+    }
+
+    /// Build a `RoutePlan` for `side`/`qty`, walking connected venues' top-of-book
+    /// liquidity in price-priority order and falling back to the configured AMM pool
+    /// for any quantity depth can't cover. Rejects the plan if expected slippage vs.
+    /// the current NBBO mid exceeds `max_slippage_bps`.
+    pub fn route(
+        &self,
+        book: &ConsolidatedBook,
+        exchanges: &ExchangeManager,
+        side: Side,
+        qty: Quantity,
+    ) -> Result<RoutePlan, String> {
+        if qty <= 0 {
+            return Err("route quantity must be positive".to_string());
+        }
+
+        let nbbo = book.get_nbbo();
+        if !nbbo.is_valid() {
+            return Err("no valid NBBO to route against".to_string());
+        }
+        let mid = (nbbo.best_bid + nbbo.best_ask) / 2;
+
+        let connected = exchanges.connected_exchanges();
+        let mut levels: Vec<(ExchangeId, Price, Quantity)> = book
+            .all_quotes()
+            .into_iter()
+            .filter(|q| connected.contains(&q.exchange))
+            .filter_map(|q| match side {
+                Side::Buy if q.ask > 0 => Some((q.exchange, q.ask, q.ask_qty)),
+                Side::Sell if q.bid > 0 => Some((q.exchange, q.bid, q.bid_qty)),
+                _ => None,
+            })
+            .collect();
+
+        // Walk cheapest-first for a buy, richest-first for a sell
+        match side {
+            Side::Buy => levels.sort_by_key(|(_, price, _)| *price),
+            Side::Sell => levels.sort_by_key(|(_, price, _)| std::cmp::Reverse(*price)),
+        }
+
+        let mut remaining = qty;
+        let mut legs = Vec::new();
+        let mut notional = 0.0f64;
+
+        for (exchange, price, available) in levels {
+            if remaining <= 0 {
+                break;
+            }
+            let fill_qty = available.min(remaining);
+            if fill_qty <= 0 {
+                continue;
+            }
+            legs.push((exchange, price, fill_qty));
+            notional += from_qty(fill_qty) * from_price(price);
+            remaining -= fill_qty;
+        }
+
+        if remaining > 0 {
+            if let Some(pool) = self.config.amm_pool {
+                let amm_qty = from_qty(remaining);
+                if let Some(amm_price) = pool.average_price(side, amm_qty) {
+                    legs.push((ExchangeId::Amm, to_price(amm_price), remaining));
+                    notional += amm_qty * amm_price;
+                    remaining = 0;
+                }
+            }
+        }
+
+        if remaining > 0 {
+            return Err(format!(
+                "insufficient liquidity: {} of {} unfilled after venues and AMM fallback",
+                from_qty(remaining),
+                from_qty(qty)
+            ));
+        }
+
+        let filled_qty = from_qty(qty);
+        let expected_avg_price = notional / filled_qty;
+        let expected_slippage_bps = 10000.0 * (expected_avg_price - from_price(mid)).abs() / from_price(mid);
+
+        if expected_slippage_bps > self.config.max_slippage_bps {
+            return Err(format!(
+                "expected slippage {:.2} bps exceeds limit {:.2} bps",
+                expected_slippage_bps, self.config.max_slippage_bps
+            ));
+        }
+
+        Ok(RoutePlan {
+            legs,
+            expected_avg_price,
+            expected_slippage_bps,
+        })
+    }
+}
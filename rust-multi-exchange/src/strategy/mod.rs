@@ -1,8 +1,10 @@
 //! Cross-exchange market making strategy
 
+use crate::arb::TakerFeeTable;
 use crate::core::types::*;
 use crate::orderbook::{ConsolidatedBook, NBBO};
 use hashbrown::HashMap;
+use std::collections::VecDeque;
 
 /// Cross-exchange position tracking
 #[derive(Debug, Clone, Default)]
@@ -22,13 +24,22 @@ impl CrossExchangePosition {
     }
 }
 
-/// Quote decision for multiple exchanges
+/// Quote decision for multiple exchanges: a flat ladder of per-side, per-level orders
 #[derive(Debug, Clone, Default)]
 pub struct MultiExchangeQuotes {
-    pub quotes: Vec<(ExchangeId, Price, Price, Quantity, Quantity)>, // (exchange, bid, ask, bid_size, ask_size)
+    pub quotes: Vec<(ExchangeId, Price, Quantity, Side)>, // (exchange, price, size, side)
     pub should_quote: bool,
 }
 
+/// Shape of the liquidity ladder posted on each side of the book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteShape {
+    /// Evenly sized levels, geometrically spaced across the price range
+    Linear,
+    /// Level sizes replicate a constant-product (xyk) curve across the price range
+    ConstantProduct,
+}
+
 /// Cross-exchange market making parameters
 #[derive(Debug, Clone)]
 pub struct CrossExchangeMMParams {
@@ -39,6 +50,24 @@ pub struct CrossExchangeMMParams {
     pub hedge_immediately: bool,
     pub quote_exchanges: Vec<ExchangeId>,
     pub hedge_exchanges: Vec<ExchangeId>,
+    /// Widen the static spread using a rolling Bollinger bandwidth of recent mids
+    pub volatility_adaptive_spread: bool,
+    pub min_spread_bps: f64,
+    pub max_spread_bps: f64,
+    /// Multiplier applied to the normalized bandwidth when widening the spread
+    pub band_factor: f64,
+    /// Bandwidth considered "normal"; bw_normalized = bw / baseline_bandwidth
+    pub baseline_bandwidth: f64,
+    /// Minimum elapsed time between batched hedges
+    pub hedge_interval_us: u64,
+    /// Outstanding un-hedged exposure that forces an immediate hedge regardless of interval
+    pub max_uncovered: Quantity,
+    /// Shape of the per-side liquidity ladder
+    pub quote_shape: QuoteShape,
+    /// Number of price levels posted per side
+    pub levels: usize,
+    /// Extra distance in bps the ladder extends beyond `half_spread` for the outermost level
+    pub price_range_bps: f64,
 }
 
 impl Default for CrossExchangeMMParams {
@@ -51,25 +80,162 @@ impl Default for CrossExchangeMMParams {
             hedge_immediately: true,
             quote_exchanges: vec![ExchangeId::Binance, ExchangeId::Bybit],
             hedge_exchanges: vec![ExchangeId::Binance, ExchangeId::Bybit],
+            volatility_adaptive_spread: false,
+            min_spread_bps: 5.0,
+            max_spread_bps: 50.0,
+            band_factor: 1.0,
+            baseline_bandwidth: 0.002,
+            hedge_interval_us: 500_000,
+            max_uncovered: to_qty(0.05),
+            quote_shape: QuoteShape::Linear,
+            levels: 1,
+            price_range_bps: 0.0,
         }
     }
 }
 
+/// Tracks filled exposure versus what has already been hedged, so fills can be
+/// netted and covered in batches instead of one hedge order per fill
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CoveredPosition {
+    /// Signed net quantity filled so far (positive = net long)
+    pub net_filled: Quantity,
+    /// Signed quantity already covered by acknowledged hedge orders
+    pub covered: Quantity,
+}
+
+impl CoveredPosition {
+    /// Signed exposure that still needs to be hedged
+    pub fn outstanding(&self) -> Quantity {
+        self.net_filled - self.covered
+    }
+}
+
 /// Cross-exchange market maker
 pub struct CrossExchangeMM {
     params: CrossExchangeMMParams,
     enabled: bool,
     quotes_sent: u64,
     fills: u64,
+    mid_window: VecDeque<f64>,
+    covered: CoveredPosition,
+    last_hedge_at: Timestamp,
 }
 
 impl CrossExchangeMM {
+    /// Number of recent mids kept for the Bollinger bandwidth estimate
+    const MID_WINDOW: usize = 20;
+    /// Bollinger band width in standard deviations (k in `bw = 2*k*sigma/sma`)
+    const BOLLINGER_K: f64 = 2.0;
+
     pub fn new(params: CrossExchangeMMParams) -> Self {
         CrossExchangeMM {
             params,
             enabled: false,
             quotes_sent: 0,
             fills: 0,
+            mid_window: VecDeque::with_capacity(Self::MID_WINDOW),
+            covered: CoveredPosition::default(),
+            last_hedge_at: 0,
+        }
+    }
+
+    /// Push a new mid price into the rolling window, evicting the oldest once full
+    fn push_mid(&mut self, mid: f64) {
+        if self.mid_window.len() == Self::MID_WINDOW {
+            self.mid_window.pop_front();
+        }
+        self.mid_window.push_back(mid);
+    }
+
+    /// Bollinger bandwidth `bw = 2*k*sigma/sma` over the current window, if full
+    fn bollinger_bandwidth(&self) -> Option<f64> {
+        if self.mid_window.len() < Self::MID_WINDOW {
+            return None;
+        }
+
+        let n = self.mid_window.len() as f64;
+        let sma = self.mid_window.iter().sum::<f64>() / n;
+        if sma == 0.0 {
+            return None;
+        }
+
+        let variance = self.mid_window.iter().map(|m| (m - sma).powi(2)).sum::<f64>() / n;
+        let sigma = variance.sqrt();
+
+        Some(2.0 * Self::BOLLINGER_K * sigma / sma)
+    }
+
+    /// Effective half-spread in bps, widened by realized volatility when enabled
+    fn effective_spread_bps(&mut self, mid: f64) -> f64 {
+        if !self.params.volatility_adaptive_spread {
+            return self.params.target_spread_bps;
+        }
+
+        self.push_mid(mid);
+
+        let bw = match self.bollinger_bandwidth() {
+            Some(bw) => bw,
+            None => return self.params.target_spread_bps,
+        };
+
+        let bw_normalized = bw / self.params.baseline_bandwidth;
+        let spread = self.params.target_spread_bps * (1.0 + self.params.band_factor * bw_normalized);
+
+        spread.clamp(self.params.min_spread_bps, self.params.max_spread_bps)
+    }
+
+    /// Geometrically-spaced price offsets (from the mid) for each ladder level, ranging
+    /// from `half_spread` out to `half_spread + price_range_bps`
+    fn ladder_offsets(&self, half_spread: Price, fair_value: Price) -> Vec<Price> {
+        let levels = self.params.levels.max(1);
+        if levels == 1 || half_spread <= 0 {
+            return vec![half_spread.max(0)];
+        }
+
+        let range = (fair_value as f64 * self.params.price_range_bps / 20_000.0) as Price;
+        let far = half_spread + range;
+        if range <= 0 {
+            return vec![half_spread; levels];
+        }
+
+        let ratio = (far as f64 / half_spread as f64).powf(1.0 / (levels - 1) as f64);
+        (0..levels)
+            .map(|i| (half_spread as f64 * ratio.powi(i as i32)) as Price)
+            .collect()
+    }
+
+    /// Per-level order sizes replicating `quote_shape` across the given offsets, summing
+    /// to `default_order_size`
+    fn ladder_sizes(&self, offsets: &[Price], fair_value: Price) -> Vec<Quantity> {
+        let n = offsets.len();
+        if n <= 1 {
+            return vec![self.params.default_order_size; n];
+        }
+
+        match self.params.quote_shape {
+            QuoteShape::Linear => {
+                vec![self.params.default_order_size / n as Quantity; n]
+            }
+            QuoteShape::ConstantProduct => {
+                let mut prices: Vec<f64> = offsets.iter().map(|o| (fair_value + o) as f64).collect();
+                let last_gap = offsets[n - 1] - offsets[n - 2];
+                prices.push((fair_value + offsets[n - 1] + last_gap) as f64);
+
+                let weights: Vec<f64> = (0..n)
+                    .map(|i| (1.0 / prices[i] - 1.0 / prices[i + 1]).abs())
+                    .collect();
+                let total_weight: f64 = weights.iter().sum();
+
+                if total_weight <= 0.0 {
+                    return vec![self.params.default_order_size / n as Quantity; n];
+                }
+
+                weights
+                    .iter()
+                    .map(|w| (self.params.default_order_size as f64 * w / total_weight) as Quantity)
+                    .collect()
+            }
         }
     }
 
@@ -86,9 +252,13 @@ impl CrossExchangeMM {
         }
 
         let fair_value = (nbbo.best_bid + nbbo.best_ask) / 2;
-        let half_spread = (fair_value as f64 * self.params.target_spread_bps / 20000.0) as Price;
+        let spread_bps = self.effective_spread_bps(fair_value as f64);
+        let half_spread = (fair_value as f64 * spread_bps / 20000.0) as Price;
+
+        let offsets = self.ladder_offsets(half_spread, fair_value);
+        let sizes = self.ladder_sizes(&offsets, fair_value);
 
-        // Compute quotes for each exchange
+        // Compute a quote ladder for each exchange
         for exchange in &self.params.quote_exchanges {
             let pos = position.get(*exchange);
 
@@ -105,13 +275,13 @@ impl CrossExchangeMM {
             };
             let skew_adj = (fair_value as f64 * skew * 0.5 / 10000.0) as Price;
 
-            let bid_price = fair_value - half_spread - skew_adj;
-            let ask_price = fair_value + half_spread - skew_adj;
+            for (offset, size) in offsets.iter().zip(sizes.iter()) {
+                let bid_price = fair_value - offset - skew_adj;
+                let ask_price = fair_value + offset - skew_adj;
 
-            let bid_size = self.params.default_order_size;
-            let ask_size = self.params.default_order_size;
-
-            result.quotes.push((*exchange, bid_price, ask_price, bid_size, ask_size));
+                result.quotes.push((*exchange, bid_price, *size, Side::Buy));
+                result.quotes.push((*exchange, ask_price, *size, Side::Sell));
+            }
         }
 
         if !result.quotes.is_empty() {
@@ -167,7 +337,9 @@ impl CrossExchangeMM {
             exchange: hedge_exchange,
             symbol: book.symbol().clone(),
             side: hedge_side,
+            order_type: OrderType::Limit,
             price: hedge_price,
+            stop_price: None,
             quantity: fill_qty,
             filled_qty: 0,
             status: OrderStatus::New,
@@ -177,13 +349,150 @@ impl CrossExchangeMM {
         Some((hedge_exchange, order))
     }
 
+    /// Emit a single netted hedge order covering the outstanding exposure accumulated
+    /// across fills since the last hedge, once `hedge_interval_us` has elapsed or
+    /// `max_uncovered` is breached. The returned quantity is only subtracted from the
+    /// outstanding exposure once the caller confirms the order via [`Self::on_hedge_ack`].
+    pub fn poll_hedge(&mut self, now: Timestamp, book: &ConsolidatedBook) -> Option<(ExchangeId, Order)> {
+        if !self.params.hedge_immediately {
+            return None;
+        }
+
+        let outstanding = self.covered.outstanding();
+        if outstanding == 0 {
+            return None;
+        }
+
+        let elapsed_us = now.saturating_sub(self.last_hedge_at) / 1_000;
+        let interval_elapsed = elapsed_us >= self.params.hedge_interval_us;
+        let over_threshold = outstanding.abs() >= self.params.max_uncovered;
+
+        if !interval_elapsed && !over_threshold {
+            return None;
+        }
+
+        let hedge_side = if outstanding > 0 { Side::Sell } else { Side::Buy };
+        let hedge_exchange = *self.params.hedge_exchanges.first()?;
+
+        let nbbo = book.get_nbbo();
+        let hedge_price = match hedge_side {
+            Side::Buy => nbbo.best_ask,
+            Side::Sell => nbbo.best_bid,
+        };
+
+        let order = Order {
+            id: 0,
+            exchange: hedge_exchange,
+            symbol: book.symbol().clone(),
+            side: hedge_side,
+            order_type: OrderType::Limit,
+            price: hedge_price,
+            stop_price: None,
+            quantity: outstanding.abs(),
+            filled_qty: 0,
+            status: OrderStatus::New,
+            timestamp: now_nanos(),
+        };
+
+        self.last_hedge_at = now;
+        Some((hedge_exchange, order))
+    }
+
+    /// Advance the covered exposure once a hedge order from [`Self::poll_hedge`] is
+    /// acknowledged by the exchange. `qty` is the signed outstanding amount it covers.
+    pub fn on_hedge_ack(&mut self, qty: Quantity) {
+        self.covered.covered += qty;
+    }
+
     pub fn enable(&mut self) { self.enabled = true; }
     pub fn disable(&mut self) { self.enabled = false; }
     pub fn is_enabled(&self) -> bool { self.enabled }
     pub fn quotes_sent(&self) -> u64 { self.quotes_sent }
     pub fn fills(&self) -> u64 { self.fills }
+    pub fn covered_position(&self) -> CoveredPosition { self.covered }
 
-    pub fn on_fill(&mut self) {
+    pub fn on_fill(&mut self, side: Side, qty: Quantity) {
         self.fills += 1;
+        self.covered.net_filled += match side {
+            Side::Buy => qty,
+            Side::Sell => -qty,
+        };
+    }
+}
+
+/// Quotes passively on a single venue while treating the best price available on the
+/// *other* exchanges as a hedge reference, skewing the quote toward that cross-venue
+/// mid instead of the local one so it isn't adversely selected by takers who can
+/// already trade at the better price elsewhere. A quote is only emitted when the
+/// resulting edge clears the quoting venue's taker fee plus the fee and latency cost
+/// of hedging on whichever venue supplied the reference price (a slower hedge venue
+/// needs a wider edge, since the exposure sits un-hedged for longer).
+pub struct PassiveHedgedQuoter {
+    quote_exchange: ExchangeId,
+    fees: TakerFeeTable,
+    order_size: Quantity,
+    /// Required edge, in bps, charged per microsecond of hedge-venue latency
+    latency_penalty_bps_per_us: f64,
+}
+
+impl PassiveHedgedQuoter {
+    pub fn new(
+        quote_exchange: ExchangeId,
+        fees: TakerFeeTable,
+        order_size: Quantity,
+        latency_penalty_bps_per_us: f64,
+    ) -> Self {
+        PassiveHedgedQuoter {
+            quote_exchange,
+            fees,
+            order_size,
+            latency_penalty_bps_per_us,
+        }
+    }
+
+    /// Compute a passive bid/ask to post on `quote_exchange`, or `None` if no other
+    /// venue in `quotes` offers enough edge to clear fees and hedge latency
+    pub fn compute_quote(&self, quotes: &HashMap<ExchangeId, ExchangeQuote>) -> Option<(Price, Price, Quantity)> {
+        let mut hedge_bid: Option<&ExchangeQuote> = None;
+        let mut hedge_ask: Option<&ExchangeQuote> = None;
+
+        for (exchange, quote) in quotes {
+            if *exchange == self.quote_exchange {
+                continue;
+            }
+            if quote.bid > 0 && hedge_bid.map_or(true, |best| quote.bid > best.bid) {
+                hedge_bid = Some(quote);
+            }
+            if quote.ask > 0 && hedge_ask.map_or(true, |best| quote.ask < best.ask) {
+                hedge_ask = Some(quote);
+            }
+        }
+
+        let hedge_bid = hedge_bid?;
+        let hedge_ask = hedge_ask?;
+
+        let hedge_mid = (hedge_bid.bid + hedge_ask.ask) / 2;
+        if hedge_mid <= 0 {
+            return None;
+        }
+
+        let hedge_fee_bps = self.fees.fee_bps(hedge_bid.exchange).max(self.fees.fee_bps(hedge_ask.exchange));
+        let hedge_latency_us = hedge_bid.latency_ns.max(hedge_ask.latency_ns) as f64 / 1000.0;
+        let required_bps = self.fees.fee_bps(self.quote_exchange)
+            + hedge_fee_bps
+            + self.latency_penalty_bps_per_us * hedge_latency_us;
+
+        let half_edge = (hedge_mid as f64 * required_bps / 20_000.0) as Price;
+        if half_edge <= 0 {
+            return None;
+        }
+
+        let bid_price = hedge_mid - half_edge;
+        let ask_price = hedge_mid + half_edge;
+        if bid_price <= 0 || bid_price >= ask_price {
+            return None;
+        }
+
+        Some((bid_price, ask_price, self.order_size))
     }
 }
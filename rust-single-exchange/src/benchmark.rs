@@ -27,7 +27,7 @@ fn bench_orderbook_updates() {
     for i in 0..iterations {
         let price = to_price(50000.0 + (i % 100) as f64);
         let qty = to_qty(1.0 + (i % 10) as f64);
-        book.update_bid(price, qty);
+        book.update_bid(price, qty).unwrap();
     }
     let elapsed = start.elapsed();
     let ops_per_sec = iterations as f64 / elapsed.as_secs_f64();
@@ -43,7 +43,7 @@ fn bench_orderbook_updates() {
     for i in 0..iterations {
         let price = to_price(50001.0 + (i % 100) as f64);
         let qty = to_qty(1.0 + (i % 10) as f64);
-        book.update_ask(price, qty);
+        book.update_ask(price, qty).unwrap();
     }
     let elapsed = start.elapsed();
     let ops_per_sec = iterations as f64 / elapsed.as_secs_f64();
@@ -65,8 +65,8 @@ fn bench_orderbook_queries() {
 
     // Populate book
     for i in 0..100 {
-        book.update_bid(to_price(50000.0 - i as f64), to_qty(1.0));
-        book.update_ask(to_price(50001.0 + i as f64), to_qty(1.0));
+        book.update_bid(to_price(50000.0 - i as f64), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(50001.0 + i as f64), to_qty(1.0)).unwrap();
     }
 
     let iterations = 10_000_000;
@@ -133,8 +133,8 @@ fn bench_strategy_compute() {
     strategy.set_enabled(true);
 
     let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
-    book.update_bid(to_price(50000.0), to_qty(10.0));
-    book.update_ask(to_price(50001.0), to_qty(10.0));
+    book.update_bid(to_price(50000.0), to_qty(10.0)).unwrap();
+    book.update_ask(to_price(50001.0), to_qty(10.0)).unwrap();
 
     let signal = Signal::default();
     let iterations = 1_000_000;
@@ -0,0 +1,255 @@
+//! Multi-subscriber event bus with per-subscriber QoS.
+//!
+//! `TradingEngine` used to fan `EngineEvent`s out over a single bounded
+//! crossbeam channel with exactly one consumer, so nothing else (a risk
+//! dashboard, a logger, a second execution path) could observe the stream,
+//! and a slow consumer would stall the engine's own processing. Here, every
+//! subscriber gets its own [`SpscQueue`] fed independently by
+//! [`EventBus::publish`]'s fan-out, so one slow or absent subscriber can
+//! never block another.
+
+use crate::core::engine::EngineEvent;
+use crate::core::queue::SpscQueue;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Which slice of `EngineEvent`s a subscriber wants to see
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Ticks,
+    Orders,
+    Trades,
+    Control,
+}
+
+impl Topic {
+    fn matches(&self, event: &EngineEvent) -> bool {
+        matches!(
+            (self, event),
+            (Topic::Ticks, EngineEvent::Tick(_))
+                | (Topic::Orders, EngineEvent::OrderUpdate(_))
+                | (Topic::Trades, EngineEvent::Trade(_))
+                | (Topic::Control, EngineEvent::Shutdown)
+        )
+    }
+}
+
+/// Delivery guarantee for a subscriber's queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Qos {
+    /// Drop the oldest queued event to make room for the newest on overflow —
+    /// lossy, but a slow dashboard can never apply backpressure to the engine
+    Realtime,
+    /// Block the publisher until the subscriber drains — for consumers (e.g.
+    /// the execution path) that must not miss an event
+    Reliable,
+}
+
+struct Subscription {
+    topic: Topic,
+    qos: Qos,
+    queue: Arc<SpscQueue<EngineEvent>>,
+}
+
+/// A single subscriber's receiving end, returned by [`EventBus::subscribe`]
+pub struct Receiver {
+    queue: Arc<SpscQueue<EngineEvent>>,
+}
+
+impl Receiver {
+    /// Pop the oldest queued event for this subscriber, if any
+    pub fn try_recv(&self) -> Option<EngineEvent> {
+        self.queue.try_pop()
+    }
+}
+
+/// Fan-out publisher with per-subscriber topic filtering and QoS, backed by
+/// one [`SpscQueue`] per subscriber
+pub struct EventBus {
+    subscribers: RwLock<Vec<Subscription>>,
+    queue_capacity: usize,
+}
+
+impl EventBus {
+    pub fn new(queue_capacity: usize) -> Self {
+        EventBus {
+            subscribers: RwLock::new(Vec::new()),
+            queue_capacity: queue_capacity.next_power_of_two(),
+        }
+    }
+
+    /// Register a new subscriber for `topic` with the given `qos`, returning
+    /// its dedicated [`Receiver`].
+    pub fn subscribe(&self, topic: Topic, qos: Qos) -> Receiver {
+        let queue = Arc::new(SpscQueue::new(self.queue_capacity));
+        self.subscribers.write().push(Subscription { topic, qos, queue: queue.clone() });
+        Receiver { queue }
+    }
+
+    /// Fan `event` out to every subscriber whose topic matches, applying each
+    /// subscriber's QoS independently. A `Realtime` subscriber drops its
+    /// oldest queued event to make room for this one; a `Reliable` one spins
+    /// until there's room, backpressuring this call rather than dropping.
+    pub fn publish(&self, event: EngineEvent) {
+        for sub in self.subscribers.read().iter() {
+            if !sub.topic.matches(&event) {
+                continue;
+            }
+            match sub.qos {
+                Qos::Realtime => {
+                    if let Err(rejected) = sub.queue.try_push(event.clone()) {
+                        sub.queue.try_pop();
+                        let _ = sub.queue.try_push(rejected);
+                    }
+                }
+                Qos::Reliable => {
+                    let mut pending = event.clone();
+                    while let Err(rejected) = sub.queue.try_push(pending) {
+                        pending = rejected;
+                        std::thread::yield_now();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of currently registered subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.read().len()
+    }
+}
+
+#[cfg(unix)]
+mod unix_socket {
+    use super::*;
+    use std::path::Path;
+    use tokio::io::AsyncWriteExt;
+    use tracing::warn;
+
+    impl EventBus {
+        /// Accept connections on a Unix-domain socket at `path`, subscribing
+        /// each one to `topic` at [`Qos::Realtime`] and streaming its events
+        /// out as newline-delimited JSON, so an external process (risk
+        /// dashboard, logger) can observe the bus without linking this crate.
+        pub fn bind_unix_socket(self: &Arc<Self>, path: impl AsRef<Path>, topic: Topic) -> std::io::Result<()> {
+            let _ = std::fs::remove_file(path.as_ref());
+            let std_listener = std::os::unix::net::UnixListener::bind(path.as_ref())?;
+            std_listener.set_nonblocking(true)?;
+            let listener = tokio::net::UnixListener::from_std(std_listener)?;
+            let bus = self.clone();
+
+            tokio::spawn(async move {
+                loop {
+                    let Ok((stream, _)) = listener.accept().await else {
+                        break;
+                    };
+                    let receiver = bus.subscribe(topic, Qos::Realtime);
+                    tokio::spawn(stream_to_socket(receiver, stream));
+                }
+            });
+            Ok(())
+        }
+    }
+
+    async fn stream_to_socket(receiver: Receiver, mut stream: tokio::net::UnixStream) {
+        loop {
+            match receiver.try_recv() {
+                Some(event) => {
+                    let line = to_json_line(&event);
+                    if let Err(e) = stream.write_all(line.as_bytes()).await {
+                        warn!("Event-bus socket subscriber disconnected: {}", e);
+                        break;
+                    }
+                }
+                None => {
+                    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                }
+            }
+        }
+    }
+
+    fn to_json_line(event: &EngineEvent) -> String {
+        let value = match event {
+            EngineEvent::Tick(tick) => serde_json::json!({
+                "type": "tick",
+                "bid": tick.bid,
+                "ask": tick.ask,
+                "bid_qty": tick.bid_qty,
+                "ask_qty": tick.ask_qty,
+                "last_price": tick.last_price,
+                "last_qty": tick.last_qty,
+                "exchange_ts": tick.exchange_ts,
+                "local_ts": tick.local_ts,
+                "sequence": tick.sequence,
+            }),
+            EngineEvent::OrderUpdate(order) => serde_json::json!({ "type": "order_update", "order": order }),
+            EngineEvent::Trade(trade) => serde_json::json!({ "type": "trade", "trade": trade }),
+            EngineEvent::Shutdown => serde_json::json!({ "type": "shutdown" }),
+        };
+        format!("{}\n", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::*;
+
+    fn tick_event() -> EngineEvent {
+        EngineEvent::Tick(Tick {
+            bid: to_price(100.0),
+            ask: to_price(101.0),
+            bid_qty: to_qty(1.0),
+            ask_qty: to_qty(1.0),
+            last_price: to_price(100.5),
+            last_qty: to_qty(1.0),
+            exchange_ts: 0,
+            local_ts: 0,
+            sequence: 0,
+        })
+    }
+
+    #[test]
+    fn test_subscribers_only_see_their_own_topic() {
+        let bus = EventBus::new(8);
+        let ticks = bus.subscribe(Topic::Ticks, Qos::Reliable);
+        let trades = bus.subscribe(Topic::Trades, Qos::Reliable);
+
+        bus.publish(tick_event());
+
+        assert!(ticks.try_recv().is_some());
+        assert!(trades.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_realtime_qos_drops_oldest_on_overflow() {
+        let bus = EventBus::new(2);
+        let sub = bus.subscribe(Topic::Ticks, Qos::Realtime);
+
+        for _ in 0..4 {
+            bus.publish(tick_event());
+        }
+
+        // Capacity 2 (one slot always reserved) never blocks, always holds
+        // the newest event regardless of how many were published.
+        assert!(sub.try_recv().is_some());
+    }
+
+    #[test]
+    fn test_slow_subscriber_does_not_block_another_subscriber() {
+        let bus = EventBus::new(8);
+        let slow = bus.subscribe(Topic::Ticks, Qos::Realtime);
+        let fast = bus.subscribe(Topic::Ticks, Qos::Reliable);
+
+        for _ in 0..4 {
+            bus.publish(tick_event());
+        }
+
+        // `fast` drains its full backlog even though `slow` never read a thing
+        let mut fast_count = 0;
+        while fast.try_recv().is_some() {
+            fast_count += 1;
+        }
+        assert_eq!(fast_count, 4);
+    }
+}
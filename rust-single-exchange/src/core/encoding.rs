@@ -0,0 +1,305 @@
+//! Compact binary record encoding for ticks, trades, and orders
+//!
+//! JSON is too slow and too large to log at tick rate, so high-throughput
+//! logging/replay (e.g. the file appender in `utils::logger`) uses this format
+//! instead: every record is little-endian, self-describing via a 1-byte type tag,
+//! and length-prefixed where it carries a variable-length field (the symbol), so
+//! a stream of records can be parsed back incrementally without an external schema.
+
+use crate::core::types::{Order, OrderStatus, OrderType, Side, Symbol, TimeInForce, Tick, Trade};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum EncodingError {
+    #[error("invalid code")]
+    InvalidCode,
+    #[error("truncated record: need at least {needed} bytes, got {got}")]
+    Truncated { needed: usize, got: usize },
+    #[error("invalid symbol encoding: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+    #[error("unknown record type tag {0}")]
+    UnknownTag(u8),
+}
+
+pub type Result<T> = std::result::Result<T, EncodingError>;
+
+const TAG_TICK: u8 = 1;
+const TAG_TRADE: u8 = 2;
+const TAG_ORDER: u8 = 3;
+
+/// Wire encoding for `#[repr(u8)]` enums: code 0 is reserved as "no variant", so the
+/// wire code is always the enum discriminant plus one
+trait ByteCode: Sized {
+    fn to_byte(self) -> u8;
+    fn from_byte(b: u8) -> Result<Self>;
+}
+
+macro_rules! byte_code {
+    ($ty:ty { $($variant:ident = $disc:literal),+ $(,)? }) => {
+        impl ByteCode for $ty {
+            fn to_byte(self) -> u8 {
+                match self {
+                    $(<$ty>::$variant => $disc + 1,)+
+                }
+            }
+
+            fn from_byte(b: u8) -> Result<Self> {
+                match b.checked_sub(1) {
+                    $(Some($disc) => Ok(<$ty>::$variant),)+
+                    _ => Err(EncodingError::InvalidCode),
+                }
+            }
+        }
+    };
+}
+
+byte_code!(Side { Buy = 0, Sell = 1 });
+byte_code!(OrderType { Limit = 0, Market = 1, LimitMaker = 2, Ioc = 3, Fok = 4 });
+byte_code!(OrderStatus { New = 0, PartiallyFilled = 1, Filled = 2, Canceled = 3, Rejected = 4, Expired = 5 });
+byte_code!(TimeInForce { Gtc = 0, Ioc = 1, Fok = 2, Gtx = 3, Gtd = 4 });
+
+fn push_i64(buf: &mut Vec<u8>, v: i64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn push_u64(buf: &mut Vec<u8>, v: u64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn push_symbol(buf: &mut Vec<u8>, symbol: &Symbol) {
+    let bytes = symbol.as_str().as_bytes();
+    buf.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn take<'a>(buf: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8]> {
+    if buf.len() < *pos + len {
+        return Err(EncodingError::Truncated { needed: *pos + len, got: buf.len() });
+    }
+    let slice = &buf[*pos..*pos + len];
+    *pos += len;
+    Ok(slice)
+}
+
+fn read_u8(buf: &[u8], pos: &mut usize) -> Result<u8> {
+    Ok(take(buf, pos, 1)?[0])
+}
+
+fn read_i64(buf: &[u8], pos: &mut usize) -> Result<i64> {
+    Ok(i64::from_le_bytes(take(buf, pos, 8)?.try_into().unwrap()))
+}
+
+fn read_u64(buf: &[u8], pos: &mut usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(take(buf, pos, 8)?.try_into().unwrap()))
+}
+
+fn read_symbol(buf: &[u8], pos: &mut usize) -> Result<Symbol> {
+    let len = u16::from_le_bytes(take(buf, pos, 2)?.try_into().unwrap()) as usize;
+    let bytes = take(buf, pos, len)?;
+    Ok(Symbol::new(std::str::from_utf8(bytes)?))
+}
+
+impl Tick {
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(TAG_TICK);
+        push_i64(buf, self.bid);
+        push_i64(buf, self.ask);
+        push_i64(buf, self.bid_qty);
+        push_i64(buf, self.ask_qty);
+        push_i64(buf, self.last_price);
+        push_i64(buf, self.last_qty);
+        push_u64(buf, self.exchange_ts);
+        push_u64(buf, self.local_ts);
+        push_u64(buf, self.sequence);
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<(Self, usize)> {
+        let mut pos = 0;
+        let tag = read_u8(buf, &mut pos)?;
+        if tag != TAG_TICK {
+            return Err(EncodingError::UnknownTag(tag));
+        }
+
+        let tick = Tick {
+            bid: read_i64(buf, &mut pos)?,
+            ask: read_i64(buf, &mut pos)?,
+            bid_qty: read_i64(buf, &mut pos)?,
+            ask_qty: read_i64(buf, &mut pos)?,
+            last_price: read_i64(buf, &mut pos)?,
+            last_qty: read_i64(buf, &mut pos)?,
+            exchange_ts: read_u64(buf, &mut pos)?,
+            local_ts: read_u64(buf, &mut pos)?,
+            sequence: read_u64(buf, &mut pos)?,
+        };
+        Ok((tick, pos))
+    }
+}
+
+impl Trade {
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(TAG_TRADE);
+        push_u64(buf, self.order_id);
+        push_u64(buf, self.trade_id);
+        push_symbol(buf, &self.symbol);
+        buf.push(self.side.to_byte());
+        push_i64(buf, self.price);
+        push_i64(buf, self.quantity);
+        push_u64(buf, self.timestamp);
+        buf.push(self.is_maker as u8);
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<(Self, usize)> {
+        let mut pos = 0;
+        let tag = read_u8(buf, &mut pos)?;
+        if tag != TAG_TRADE {
+            return Err(EncodingError::UnknownTag(tag));
+        }
+
+        let trade = Trade {
+            order_id: read_u64(buf, &mut pos)?,
+            trade_id: read_u64(buf, &mut pos)?,
+            symbol: read_symbol(buf, &mut pos)?,
+            side: Side::from_byte(read_u8(buf, &mut pos)?)?,
+            price: read_i64(buf, &mut pos)?,
+            quantity: read_i64(buf, &mut pos)?,
+            timestamp: read_u64(buf, &mut pos)?,
+            is_maker: read_u8(buf, &mut pos)? != 0,
+        };
+        Ok((trade, pos))
+    }
+}
+
+impl Order {
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(TAG_ORDER);
+        push_u64(buf, self.id);
+        push_u64(buf, self.client_id);
+        push_symbol(buf, &self.symbol);
+        buf.push(self.side.to_byte());
+        buf.push(self.order_type.to_byte());
+        buf.push(self.time_in_force.to_byte());
+        push_i64(buf, self.price);
+        push_i64(buf, self.quantity);
+        push_i64(buf, self.filled_qty);
+        buf.push(self.status.to_byte());
+        push_u64(buf, self.timestamp);
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<(Self, usize)> {
+        let mut pos = 0;
+        let tag = read_u8(buf, &mut pos)?;
+        if tag != TAG_ORDER {
+            return Err(EncodingError::UnknownTag(tag));
+        }
+
+        let order = Order {
+            id: read_u64(buf, &mut pos)?,
+            client_id: read_u64(buf, &mut pos)?,
+            symbol: read_symbol(buf, &mut pos)?,
+            side: Side::from_byte(read_u8(buf, &mut pos)?)?,
+            order_type: OrderType::from_byte(read_u8(buf, &mut pos)?)?,
+            time_in_force: TimeInForce::from_byte(read_u8(buf, &mut pos)?)?,
+            price: read_i64(buf, &mut pos)?,
+            quantity: read_i64(buf, &mut pos)?,
+            filled_qty: read_i64(buf, &mut pos)?,
+            status: OrderStatus::from_byte(read_u8(buf, &mut pos)?)?,
+            timestamp: read_u64(buf, &mut pos)?,
+            // Not part of the wire format; GTD expiry doesn't survive a log round-trip
+            expires_at: None,
+        };
+        Ok((order, pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{to_price, to_qty};
+
+    #[test]
+    fn test_tick_round_trip() {
+        let tick = Tick {
+            bid: to_price(50000.0),
+            ask: to_price(50000.5),
+            bid_qty: to_qty(1.0),
+            ask_qty: to_qty(2.0),
+            last_price: to_price(50000.2),
+            last_qty: to_qty(0.5),
+            exchange_ts: 123,
+            local_ts: 456,
+            sequence: 789,
+        };
+
+        let mut buf = Vec::new();
+        tick.encode(&mut buf);
+        let (decoded, consumed) = Tick::decode(&buf).unwrap();
+        assert_eq!(consumed, buf.len());
+        assert_eq!(decoded.bid, tick.bid);
+        assert_eq!(decoded.sequence, tick.sequence);
+    }
+
+    #[test]
+    fn test_trade_round_trip() {
+        let trade = Trade {
+            order_id: 1,
+            trade_id: 2,
+            symbol: Symbol::new("BTCUSDT"),
+            side: Side::Sell,
+            price: to_price(50000.0),
+            quantity: to_qty(1.5),
+            timestamp: 999,
+            is_maker: true,
+        };
+
+        let mut buf = Vec::new();
+        trade.encode(&mut buf);
+        let (decoded, consumed) = Trade::decode(&buf).unwrap();
+        assert_eq!(consumed, buf.len());
+        assert_eq!(decoded.symbol, trade.symbol);
+        assert_eq!(decoded.side, trade.side);
+        assert!(decoded.is_maker);
+    }
+
+    #[test]
+    fn test_order_round_trip_with_trailing_bytes() {
+        let order = Order::new(
+            Symbol::new("ETHUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(3000.0),
+            to_qty(2.0),
+        );
+
+        let mut buf = Vec::new();
+        order.encode(&mut buf);
+        buf.extend_from_slice(&[0xAA, 0xBB]); // trailing bytes from the next record
+
+        let (decoded, consumed) = Order::decode(&buf).unwrap();
+        assert_eq!(consumed, buf.len() - 2);
+        assert_eq!(decoded.symbol, order.symbol);
+        assert_eq!(decoded.order_type, order.order_type);
+    }
+
+    #[test]
+    fn test_invalid_code_rejected_not_panicking() {
+        // side byte 0xFF is out of range for the Side wire code
+        let mut buf = vec![TAG_TRADE];
+        push_u64(&mut buf, 1);
+        push_u64(&mut buf, 2);
+        push_symbol(&mut buf, &Symbol::new("BTCUSDT"));
+        buf.push(0xFF);
+        push_i64(&mut buf, to_price(1.0));
+        push_i64(&mut buf, to_qty(1.0));
+        push_u64(&mut buf, 0);
+        buf.push(0);
+
+        let err = Trade::decode(&buf).unwrap_err();
+        assert!(matches!(err, EncodingError::InvalidCode));
+    }
+
+    #[test]
+    fn test_truncated_record_rejected() {
+        let err = Tick::decode(&[TAG_TICK, 1, 2, 3]).unwrap_err();
+        assert!(matches!(err, EncodingError::Truncated { .. }));
+    }
+}
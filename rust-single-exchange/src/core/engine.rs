@@ -1,18 +1,40 @@
 //! Trading Engine - Core system orchestrator
 
+use crate::core::bus::{EventBus, Qos, Receiver as BusReceiver, Topic};
 use crate::core::types::*;
-use crate::exchange::ExchangeClient;
+use crate::exchange::{ExchangeClient, OrderRequest};
 use crate::orderbook::OrderBook;
-use crate::risk::{RiskManager, RiskLimits};
+use crate::risk::{FeeModel, RiskManager, RiskLimits};
 use crate::strategy::{MarketMaker, MarketMakerParams, QuoteDecision, Signal};
 
 use crossbeam_channel::{bounded, Receiver, Sender};
 use parking_lot::RwLock;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+/// A single quote leg dispatched to an exchange but not yet confirmed filled.
+/// Inserted into `TradingEngine`'s pending-matches map the moment it's sent —
+/// optimistically reserving its quantity via `RiskManager::on_order_sent` and
+/// counting it in `orders_sent` — then either promoted to a fill or rolled
+/// back once `ExchangeClient::send_order` resolves (see
+/// `TradingEngine::dispatch_match`). A match that never resolves (the
+/// exchange hangs, the task is lost) is swept by `TradingEngine::rollback_expired`.
+#[derive(Debug, Clone)]
+pub struct ExecutableMatch {
+    pub match_id: u64,
+    pub order_id: OrderId,
+    pub exchange: String,
+    pub symbol: Symbol,
+    pub side: Side,
+    pub price: Price,
+    pub qty: Quantity,
+    pub created_at: Timestamp,
+}
+
 // ============================================================================
 // Engine Configuration
 // ============================================================================
@@ -23,11 +45,19 @@ pub struct EngineConfig {
     pub exchange: String,
     pub strategy: MarketMakerParams,
     pub risk: RiskLimits,
+    /// Fee schedule fed into the `RiskManager` so fill PnL and loss-limit
+    /// gating reflect real maker/taker costs; see `AppConfig::to_fee_model`
+    pub fee_model: FeeModel,
 
     // System settings
     pub tick_buffer_size: usize,
     pub order_buffer_size: usize,
     pub enable_trading: bool,
+    /// Route every order through `ExchangeClient::test_order` instead of
+    /// `send_order`: quotes are validated against the venue's matching-engine
+    /// rules but never actually rest or fill, so a strategy can be checked
+    /// for exchange-acceptable output before it ever trades for real.
+    pub validate_orders: bool,
 }
 
 impl Default for EngineConfig {
@@ -37,9 +67,11 @@ impl Default for EngineConfig {
             exchange: "binance".to_string(),
             strategy: MarketMakerParams::default(),
             risk: RiskLimits::default(),
+            fee_model: FeeModel::default(),
             tick_buffer_size: 65536,
             order_buffer_size: 8192,
             enable_trading: false,
+            validate_orders: false,
         }
     }
 }
@@ -53,9 +85,143 @@ pub enum EngineEvent {
     Tick(Tick),
     OrderUpdate(Order),
     Trade(Trade),
+    /// A mark-price/funding-rate update from a futures venue's stream; see
+    /// [`TradingEngine::on_funding_update`]
+    FundingUpdate(FundingUpdate),
+    Shutdown,
+}
+
+/// Runtime steering command issued via an external control channel (e.g. the
+/// control socket spawned in `main`) instead of only being configurable once
+/// via `EngineBuilder` at startup. Processed by the same loop that drives
+/// `EngineEvent`s; see `TradingEngine::command_sender`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EngineCommand {
+    EnableTrading,
+    DisableTrading,
+    /// Set `MarketMakerParams::target_spread_bps` to the given value
+    UpdateSpread(f64),
+    /// Equivalent to `TradingEngine::enter_maintenance_mode`: stop quoting
+    /// and wind the current position down toward flat
+    FlattenPosition,
+    /// Re-point the engine at a different traded symbol. The local order
+    /// book is keyed to a single symbol, so this resets it empty rather than
+    /// attempting to carry old depth over.
+    SetSymbol(Symbol),
     Shutdown,
 }
 
+impl EngineCommand {
+    /// Parse one line of the control socket's line protocol, e.g.
+    /// `ENABLE_TRADING`, `UPDATE_SPREAD 12.5`, `SET_SYMBOL ETHUSDT`.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut parts = line.trim().split_whitespace();
+        let verb = parts.next().ok_or("empty command")?;
+        match verb.to_ascii_uppercase().as_str() {
+            "ENABLE_TRADING" => Ok(EngineCommand::EnableTrading),
+            "DISABLE_TRADING" => Ok(EngineCommand::DisableTrading),
+            "UPDATE_SPREAD" => {
+                let bps: f64 = parts
+                    .next()
+                    .ok_or("UPDATE_SPREAD requires a bps value")?
+                    .parse()
+                    .map_err(|e| format!("invalid bps: {}", e))?;
+                Ok(EngineCommand::UpdateSpread(bps))
+            }
+            "FLATTEN_POSITION" => Ok(EngineCommand::FlattenPosition),
+            "SET_SYMBOL" => {
+                let symbol = parts.next().ok_or("SET_SYMBOL requires a symbol")?;
+                Ok(EngineCommand::SetSymbol(Symbol::new(symbol)))
+            }
+            "SHUTDOWN" => Ok(EngineCommand::Shutdown),
+            other => Err(format!("unknown command: {}", other)),
+        }
+    }
+}
+
+/// Fill state of an order, derived from the cumulative quantity of all
+/// `Trade`s carrying its `order_id` compared against its original quantity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillStatus {
+    Open,
+    PartiallyFilled { cumulative: Quantity, remaining: Quantity },
+    Filled,
+}
+
+/// Sums the quantities of all `Trade`s carrying a given `order_id` against
+/// that order's original quantity, so an exchange filling a resting quote in
+/// pieces can be told apart from a single full fill. Orders are registered
+/// with their original quantity when dispatched (see
+/// `TradingEngine::dispatch_match`) and dropped from the tracker once fully
+/// filled.
+#[derive(Debug, Default)]
+pub struct OrderFillTracker {
+    orders: HashMap<OrderId, (Quantity, Quantity)>,
+}
+
+impl OrderFillTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an order's original quantity so subsequent trades can be
+    /// measured against it.
+    pub fn register(&mut self, order_id: OrderId, quantity: Quantity) {
+        self.orders.insert(order_id, (quantity, 0));
+    }
+
+    /// Record a trade against `order_id`, returning its updated fill status.
+    /// Fully filled orders are dropped from the tracker.
+    pub fn record_trade(&mut self, order_id: OrderId, quantity: Quantity) -> FillStatus {
+        let entry = self.orders.entry(order_id).or_insert((quantity, 0));
+        entry.1 = entry.1.saturating_add(quantity);
+        let status = Self::status_of(entry.0, entry.1);
+        if status == FillStatus::Filled {
+            self.orders.remove(&order_id);
+        }
+        status
+    }
+
+    /// Remaining (unfilled) quantity for `order_id`; `0` if it was never
+    /// registered or has already been filled.
+    pub fn remaining(&self, order_id: OrderId) -> Quantity {
+        self.orders
+            .get(&order_id)
+            .map(|&(original, filled)| original.saturating_sub(filled))
+            .unwrap_or(0)
+    }
+
+    fn status_of(original: Quantity, filled: Quantity) -> FillStatus {
+        if filled >= original {
+            FillStatus::Filled
+        } else if filled > 0 {
+            FillStatus::PartiallyFilled { cumulative: filled, remaining: original - filled }
+        } else {
+            FillStatus::Open
+        }
+    }
+
+    /// Average filled/original ratio across all currently outstanding
+    /// orders; `1.0` if none are outstanding.
+    pub fn average_fill_ratio(&self) -> f64 {
+        if self.orders.is_empty() {
+            return 1.0;
+        }
+        let total: f64 = self
+            .orders
+            .values()
+            .map(|&(original, filled)| {
+                if original == 0 {
+                    1.0
+                } else {
+                    from_qty(filled) / from_qty(original)
+                }
+            })
+            .sum();
+        total / self.orders.len() as f64
+    }
+}
+
 // ============================================================================
 // Trading Engine
 // ============================================================================
@@ -67,11 +233,32 @@ pub struct TradingEngine {
     orderbook: Arc<RwLock<OrderBook>>,
     strategy: Arc<RwLock<Box<dyn MarketMaker + Send + Sync>>>,
     risk_manager: Arc<RwLock<RiskManager>>,
+    /// Where quotes actually get sent; `None` runs the engine quote-only
+    /// (matches the pre-existing "would send orders here" behavior)
+    exchange_client: Option<Arc<dyn ExchangeClient + Send + Sync>>,
 
     // State
     running: Arc<AtomicBool>,
     trading_enabled: Arc<AtomicBool>,
+    /// Distinct from `trading_enabled`: blocks new quote generation and
+    /// fresh `ExecutableMatch`es while still processing fills and
+    /// flattening any outstanding position toward zero; see
+    /// [`Self::enter_maintenance_mode`]
+    maintenance_mode: Arc<AtomicBool>,
     order_id_counter: Arc<AtomicU64>,
+    match_id_counter: Arc<AtomicU64>,
+    /// Quote legs sent to the exchange but not yet confirmed filled or rolled
+    /// back; see [`ExecutableMatch`]
+    pending_matches: Arc<RwLock<HashMap<u64, ExecutableMatch>>>,
+    /// Cumulative fill quantity per outstanding order, so a quote resting
+    /// that's only been partially filled isn't treated as either fully open
+    /// or fully done; see [`OrderFillTracker`]
+    fill_tracker: Arc<RwLock<OrderFillTracker>>,
+    /// Most recently received funding rate, fed into each tick's [`Signal`]
+    /// for `MarketMaker::compute_quotes` to skew against; see
+    /// [`Self::on_funding_update`]. Stays `0.0` (no bias) on spot, where
+    /// nothing ever feeds it.
+    latest_funding_rate: Arc<RwLock<f64>>,
 
     // Statistics
     ticks_processed: Arc<AtomicU64>,
@@ -81,14 +268,30 @@ pub struct TradingEngine {
     // Channels for internal communication
     event_tx: Sender<EngineEvent>,
     event_rx: Receiver<EngineEvent>,
+    /// Fan-out to external subscribers (dashboards, loggers, a second
+    /// execution path) by topic and QoS; every event the engine's own loop
+    /// processes is also published here, see [`Self::subscribe`]
+    event_bus: Arc<EventBus>,
+    /// Runtime steering commands (see [`EngineCommand`]), consumed by the
+    /// same loop that processes `EngineEvent`s; see [`Self::command_sender`]
+    command_tx: Sender<EngineCommand>,
+    command_rx: Receiver<EngineCommand>,
+    /// Symbol currently being traded; mutable at runtime via
+    /// `EngineCommand::SetSymbol`, unlike `config.symbol`
+    active_symbol: Arc<RwLock<Symbol>>,
 }
 
 impl TradingEngine {
     pub fn new(config: EngineConfig) -> Self {
         let (event_tx, event_rx) = bounded(config.tick_buffer_size);
+        let event_bus = Arc::new(EventBus::new(config.tick_buffer_size));
+        let (command_tx, command_rx) = bounded(256);
+        let active_symbol = Arc::new(RwLock::new(config.symbol.clone()));
 
         let orderbook = Arc::new(RwLock::new(OrderBook::new(config.symbol.clone())));
-        let risk_manager = Arc::new(RwLock::new(RiskManager::new(config.risk.clone())));
+        let mut risk_manager_inner = RiskManager::new(config.risk.clone());
+        risk_manager_inner.set_fee_model(config.fee_model);
+        let risk_manager = Arc::new(RwLock::new(risk_manager_inner));
 
         // Create default strategy
         let strategy: Box<dyn MarketMaker + Send + Sync> =
@@ -100,14 +303,24 @@ impl TradingEngine {
             orderbook,
             strategy,
             risk_manager,
+            exchange_client: None,
             running: Arc::new(AtomicBool::new(false)),
             trading_enabled: Arc::new(AtomicBool::new(false)),
+            maintenance_mode: Arc::new(AtomicBool::new(false)),
             order_id_counter: Arc::new(AtomicU64::new(1)),
+            match_id_counter: Arc::new(AtomicU64::new(1)),
+            pending_matches: Arc::new(RwLock::new(HashMap::new())),
+            fill_tracker: Arc::new(RwLock::new(OrderFillTracker::new())),
+            latest_funding_rate: Arc::new(RwLock::new(0.0)),
             ticks_processed: Arc::new(AtomicU64::new(0)),
             orders_sent: Arc::new(AtomicU64::new(0)),
             trades_executed: Arc::new(AtomicU64::new(0)),
             event_tx,
             event_rx,
+            event_bus,
+            command_tx,
+            command_rx,
+            active_symbol,
         }
     }
 
@@ -116,8 +329,55 @@ impl TradingEngine {
         self
     }
 
-    /// Start the trading engine
-    pub async fn start(&self) -> anyhow::Result<()> {
+    /// Wire up the exchange quotes actually get sent to. Without this, the
+    /// engine still computes `QuoteDecision`s on every tick but never
+    /// dispatches them.
+    pub fn with_exchange_client(mut self, client: Arc<dyn ExchangeClient + Send + Sync>) -> Self {
+        self.exchange_client = Some(client);
+        self
+    }
+
+    /// Subscribe to `topic` with the given `qos`; see [`EventBus`]. Every
+    /// event the engine's own processing loop sees is also published here,
+    /// so any number of subscribers can observe the stream independently of
+    /// each other and of the engine's own consumption.
+    pub fn subscribe(&self, topic: Topic, qos: Qos) -> BusReceiver {
+        self.event_bus.subscribe(topic, qos)
+    }
+
+    /// Bind the engine's event bus to a Unix-domain socket so an external
+    /// process (risk dashboard, logger) can attach without linking this
+    /// crate; see [`EventBus::bind_unix_socket`].
+    #[cfg(unix)]
+    pub fn bind_unix_socket(&self, path: impl AsRef<std::path::Path>, topic: Topic) -> std::io::Result<()> {
+        self.event_bus.bind_unix_socket(path, topic)
+    }
+
+    /// Clone of the command channel's sender, so an external control
+    /// channel (e.g. a line-protocol socket spawned in `main`) can forward
+    /// parsed [`EngineCommand`]s into the engine's processing loop without
+    /// holding a reference to the engine itself.
+    pub fn command_sender(&self) -> Sender<EngineCommand> {
+        self.command_tx.clone()
+    }
+
+    /// Issue a command directly, bypassing any external channel
+    pub fn send_command(&self, command: EngineCommand) {
+        let _ = self.command_tx.try_send(command);
+    }
+
+    /// Symbol currently being traded; see [`EngineCommand::SetSymbol`]
+    pub fn active_symbol(&self) -> Symbol {
+        self.active_symbol.read().clone()
+    }
+
+    /// Start the trading engine. When `resume_only` is set (an operator
+    /// restarting the process after a crash), the engine comes up straight
+    /// into [`Self::enter_maintenance_mode`] instead of honoring
+    /// `config.enable_trading` — it reconciles and winds down whatever
+    /// state survived (resting orders, an open position) without opening
+    /// any new exposure.
+    pub async fn start(&self, resume_only: bool) -> anyhow::Result<()> {
         if self.running.load(Ordering::Relaxed) {
             warn!("Engine already running");
             return Ok(());
@@ -126,41 +386,67 @@ impl TradingEngine {
         info!("Starting trading engine for {}", self.config.symbol);
         self.running.store(true, Ordering::Relaxed);
 
+        if resume_only {
+            info!("Starting in resume-only maintenance mode");
+            self.enter_maintenance_mode();
+        }
+
         // Spawn event processing task
         let event_rx = self.event_rx.clone();
+        let event_tx = self.event_tx.clone();
+        let event_bus = self.event_bus.clone();
+        let command_rx = self.command_rx.clone();
         let orderbook = self.orderbook.clone();
         let strategy = self.strategy.clone();
         let risk_manager = self.risk_manager.clone();
+        let exchange_client = self.exchange_client.clone();
+        let active_symbol = self.active_symbol.clone();
+        let exchange_name = self.config.exchange.clone();
+        let validate_orders = self.config.validate_orders;
+        let order_id_counter = self.order_id_counter.clone();
+        let match_id_counter = self.match_id_counter.clone();
+        let pending_matches = self.pending_matches.clone();
+        let fill_tracker = self.fill_tracker.clone();
+        let latest_funding_rate = self.latest_funding_rate.clone();
         let running = self.running.clone();
+        let maintenance_mode = self.maintenance_mode.clone();
         let trading_enabled = self.trading_enabled.clone();
         let ticks_processed = self.ticks_processed.clone();
+        let orders_sent = self.orders_sent.clone();
         let trades_executed = self.trades_executed.clone();
 
         tokio::spawn(async move {
             while running.load(Ordering::Relaxed) {
                 match event_rx.try_recv() {
                     Ok(event) => {
+                        event_bus.publish(event.clone());
                         match event {
                             EngineEvent::Tick(tick) => {
                                 // Update orderbook
                                 {
                                     let mut book = orderbook.write();
-                                    book.update_bid(tick.bid, tick.bid_qty);
-                                    book.update_ask(tick.ask, tick.ask_qty);
+                                    if let Err(e) = book.update_bid(tick.bid, tick.bid_qty) {
+                                        warn!("Rejected bid update: {}", e);
+                                    }
+                                    if let Err(e) = book.update_ask(tick.ask, tick.ask_qty) {
+                                        warn!("Rejected ask update: {}", e);
+                                    }
                                 }
                                 ticks_processed.fetch_add(1, Ordering::Relaxed);
 
                                 // Update risk manager mark price
                                 {
                                     let book = orderbook.read();
-                                    let mut rm = risk_manager.write();
+                                    let rm = risk_manager.write();
                                     if let Some(mid) = book.mid_price() {
-                                        // rm.update_mark_price(mid);
+                                        rm.update_reference_price(mid);
                                     }
                                 }
 
-                                // Compute quotes if trading enabled
-                                if trading_enabled.load(Ordering::Relaxed) {
+                                // Compute quotes if trading enabled and not winding down
+                                if trading_enabled.load(Ordering::Relaxed)
+                                    && !maintenance_mode.load(Ordering::Relaxed)
+                                {
                                     let book = orderbook.read();
                                     let rm = risk_manager.read();
                                     let position = rm.get_position();
@@ -170,6 +456,7 @@ impl TradingEngine {
                                         volatility: 0.0,
                                         momentum: 0.0,
                                         inventory_pressure: 0.0,
+                                        funding_rate: *latest_funding_rate.read(),
                                         timestamp: now_nanos(),
                                     };
 
@@ -182,7 +469,106 @@ impl TradingEngine {
                                             from_price(decision.bid_price),
                                             from_price(decision.ask_price)
                                         );
-                                        // Would send orders here
+
+                                        if let Some(ref client) = exchange_client {
+                                            // Only quote the shortfall: a resting order that's
+                                            // already working (fully or partially unfilled)
+                                            // shouldn't be topped back up to the full decision
+                                            // size on every tick.
+                                            let bid_working = TradingEngine::working_quantity(
+                                                &pending_matches,
+                                                &fill_tracker,
+                                                Side::Buy,
+                                            );
+                                            let ask_working = TradingEngine::working_quantity(
+                                                &pending_matches,
+                                                &fill_tracker,
+                                                Side::Sell,
+                                            );
+                                            let bid_remaining = decision.bid_size.saturating_sub(bid_working);
+                                            let ask_remaining = decision.ask_size.saturating_sub(ask_working);
+
+                                            if bid_remaining > 0 {
+                                                TradingEngine::dispatch_match(
+                                                    client.clone(),
+                                                    active_symbol.read().clone(),
+                                                    exchange_name.clone(),
+                                                    Side::Buy,
+                                                    decision.bid_price,
+                                                    bid_remaining,
+                                                    &order_id_counter,
+                                                    &match_id_counter,
+                                                    &pending_matches,
+                                                    &fill_tracker,
+                                                    &risk_manager,
+                                                    &orders_sent,
+                                                    &event_tx,
+                                                    validate_orders,
+                                                );
+                                            }
+                                            if ask_remaining > 0 {
+                                                TradingEngine::dispatch_match(
+                                                    client.clone(),
+                                                    active_symbol.read().clone(),
+                                                    exchange_name.clone(),
+                                                    Side::Sell,
+                                                    decision.ask_price,
+                                                    ask_remaining,
+                                                    &order_id_counter,
+                                                    &match_id_counter,
+                                                    &pending_matches,
+                                                    &fill_tracker,
+                                                    &risk_manager,
+                                                    &orders_sent,
+                                                    &event_tx,
+                                                    validate_orders,
+                                                );
+                                            }
+                                        }
+                                    }
+                                } else if maintenance_mode.load(Ordering::Relaxed) {
+                                    // Winding down: no new quotes, but flatten any
+                                    // outstanding position toward zero one shortfall
+                                    // at a time, same as the normal quoting path above.
+                                    if let Some(ref client) = exchange_client {
+                                        let book = orderbook.read();
+                                        let position = risk_manager.read().get_position();
+                                        if position != 0 {
+                                            let flatten_side =
+                                                if position > 0 { Side::Sell } else { Side::Buy };
+                                            let flatten_qty = position.abs();
+                                            let working = TradingEngine::working_quantity(
+                                                &pending_matches,
+                                                &fill_tracker,
+                                                flatten_side,
+                                            );
+                                            let remaining = flatten_qty.saturating_sub(working);
+                                            let flatten_price = match flatten_side {
+                                                Side::Buy => book.best_ask(),
+                                                Side::Sell => book.best_bid(),
+                                            };
+
+                                            if remaining > 0 {
+                                                if let Some(price) = flatten_price {
+                                                    TradingEngine::dispatch_match(
+                                                        client.clone(),
+                                                        active_symbol.read().clone(),
+                                                        exchange_name.clone(),
+                                                        flatten_side,
+                                                        price,
+                                                        remaining,
+                                                        &order_id_counter,
+                                                        &match_id_counter,
+                                                        &pending_matches,
+                                                        &fill_tracker,
+                                                        &risk_manager,
+                                                        &orders_sent,
+                                                        &event_tx,
+                                                        validate_orders,
+                                                    );
+                                                }
+                                            }
+                                        }
                                     }
                                 }
                             }
@@ -191,12 +577,27 @@ impl TradingEngine {
                             }
                             EngineEvent::Trade(trade) => {
                                 trades_executed.fetch_add(1, Ordering::Relaxed);
+                                let status =
+                                    fill_tracker.write().record_trade(trade.order_id, trade.quantity);
                                 info!(
-                                    "Trade executed: {} {} @ {} qty={}",
+                                    "Trade executed: {} {} @ {} qty={} order={} status={:?}",
                                     trade.symbol,
                                     trade.side,
                                     from_price(trade.price),
-                                    from_qty(trade.quantity)
+                                    from_qty(trade.quantity),
+                                    trade.order_id,
+                                    status
+                                );
+                            }
+                            EngineEvent::FundingUpdate(update) => {
+                                *latest_funding_rate.write() = update.funding_rate;
+                                risk_manager
+                                    .write()
+                                    .apply_funding_payment(update.funding_rate, update.mark_price);
+                                info!(
+                                    "Funding update: rate={} mark={}",
+                                    update.funding_rate,
+                                    from_price(update.mark_price)
                                 );
                             }
                             EngineEvent::Shutdown => {
@@ -213,10 +614,43 @@ impl TradingEngine {
                         break;
                     }
                 }
+
+                match command_rx.try_recv() {
+                    Ok(command) => {
+                        info!("Engine command: {:?}", command);
+                        match command {
+                            EngineCommand::EnableTrading => {
+                                trading_enabled.store(true, Ordering::Relaxed);
+                            }
+                            EngineCommand::DisableTrading => {
+                                trading_enabled.store(false, Ordering::Relaxed);
+                            }
+                            EngineCommand::UpdateSpread(bps) => {
+                                let mut strat = strategy.write();
+                                let mut params = strat.params().clone();
+                                params.target_spread_bps = bps;
+                                strat.update_params(params);
+                            }
+                            EngineCommand::FlattenPosition => {
+                                maintenance_mode.store(true, Ordering::Relaxed);
+                                risk_manager.write().set_reduce_only(true);
+                            }
+                            EngineCommand::SetSymbol(new_symbol) => {
+                                *orderbook.write() = OrderBook::new(new_symbol.clone());
+                                *active_symbol.write() = new_symbol;
+                            }
+                            EngineCommand::Shutdown => {
+                                let _ = event_tx.try_send(EngineEvent::Shutdown);
+                            }
+                        }
+                    }
+                    Err(crossbeam_channel::TryRecvError::Empty) => {}
+                    Err(crossbeam_channel::TryRecvError::Disconnected) => {}
+                }
             }
         });
 
-        if self.config.enable_trading {
+        if self.config.enable_trading && !resume_only {
             self.trading_enabled.store(true, Ordering::Relaxed);
         }
 
@@ -263,6 +697,12 @@ impl TradingEngine {
         let _ = self.event_tx.try_send(EngineEvent::Trade(trade));
     }
 
+    /// Process a mark-price/funding-rate update from a futures venue's
+    /// stream (see `ExchangeCallbacks::on_funding_rate`)
+    pub fn on_funding_update(&self, update: FundingUpdate) {
+        let _ = self.event_tx.try_send(EngineEvent::FundingUpdate(update));
+    }
+
     /// Enable trading
     pub fn enable_trading(&self) {
         info!("Trading enabled");
@@ -285,6 +725,33 @@ impl TradingEngine {
         self.trading_enabled.load(Ordering::Relaxed)
     }
 
+    /// Enter maintenance mode: reject new quote generation and fresh
+    /// `ExecutableMatch`es, but keep processing fills and flattening any
+    /// outstanding position toward zero. Unlike [`Self::disable_trading`],
+    /// which halts quoting outright and leaves existing exposure unmanaged,
+    /// this keeps winding the engine down toward flat. Also puts
+    /// `RiskManager` into reduce-only mode, so anything that would grow
+    /// absolute exposure is rejected at the risk layer too.
+    pub fn enter_maintenance_mode(&self) {
+        info!("Entering maintenance mode");
+        self.maintenance_mode.store(true, Ordering::Relaxed);
+        self.risk_manager.write().set_reduce_only(true);
+    }
+
+    /// Leave maintenance mode, resuming normal quote generation (subject to
+    /// [`Self::is_trading_enabled`])
+    pub fn exit_maintenance_mode(&self) {
+        info!("Exiting maintenance mode");
+        self.maintenance_mode.store(false, Ordering::Relaxed);
+        self.risk_manager.write().set_reduce_only(false);
+    }
+
+    /// Check if the engine is winding down toward flat rather than quoting
+    /// normally; see [`Self::enter_maintenance_mode`]
+    pub fn is_maintenance_mode(&self) -> bool {
+        self.maintenance_mode.load(Ordering::Relaxed)
+    }
+
     /// Get next order ID
     pub fn next_order_id(&self) -> OrderId {
         self.order_id_counter.fetch_add(1, Ordering::Relaxed)
@@ -300,12 +767,192 @@ impl TradingEngine {
         &self.risk_manager
     }
 
+    /// Quote legs sent to the exchange but not yet confirmed filled or rolled
+    /// back
+    pub fn pending_matches(&self) -> Vec<ExecutableMatch> {
+        self.pending_matches.read().values().cloned().collect()
+    }
+
+    /// Roll back any match still pending after `timeout`, restoring the
+    /// reserved inventory/risk exposure it optimistically took (via
+    /// `RiskManager::on_order_canceled`) and removing it from
+    /// `pending_matches`. A match can get stuck like this if `send_order`'s
+    /// future is lost (e.g. the task driving it panics or the process is
+    /// killed mid-request) and never reaches `dispatch_match`'s own
+    /// success/failure handling.
+    pub fn rollback_expired(&self, timeout: Duration) {
+        let timeout_ns = timeout.as_nanos() as i64;
+        let now = now_nanos() as i64;
+
+        let expired: Vec<ExecutableMatch> = self
+            .pending_matches
+            .read()
+            .values()
+            .filter(|m| now - m.created_at as i64 >= timeout_ns)
+            .cloned()
+            .collect();
+
+        for m in expired {
+            warn!("Rolling back expired match {} (order {})", m.match_id, m.order_id);
+            self.risk_manager.write().on_order_canceled(m.order_id);
+            self.pending_matches.write().remove(&m.match_id);
+        }
+    }
+
+    /// Unfilled quantity already resting on `side` across `pending_matches`,
+    /// per `fill_tracker`. Used to quote only the shortfall rather than
+    /// assuming a prior quote on the same side was either untouched or fully
+    /// gone.
+    fn working_quantity(
+        pending_matches: &Arc<RwLock<HashMap<u64, ExecutableMatch>>>,
+        fill_tracker: &Arc<RwLock<OrderFillTracker>>,
+        side: Side,
+    ) -> Quantity {
+        let pending = pending_matches.read();
+        let tracker = fill_tracker.read();
+        pending
+            .values()
+            .filter(|m| m.side == side)
+            .map(|m| tracker.remaining(m.order_id))
+            .sum()
+    }
+
+    /// Materialize `side`/`price`/`qty` as an [`ExecutableMatch`], optimistically
+    /// register it (reserving its quantity against the risk manager's
+    /// open-order exposure via [`RiskManager::on_order_sent`] and counting it
+    /// in `orders_sent`), then dispatch `ExchangeClient::send_order` in its
+    /// own task: on a successful, accepted response it's promoted to a fill
+    /// (`RiskManager::on_fill` plus an `EngineEvent::Trade`); on a rejected
+    /// response, an `ExchangeError`, or — via [`Self::rollback_expired`] — a
+    /// timeout, it's rolled back via `RiskManager::on_order_canceled` and
+    /// removed from `pending_matches`.
+    ///
+    /// If `validate_orders` is set, none of that bookkeeping happens at all —
+    /// the computed quote is routed to `ExchangeClient::test_order` instead,
+    /// which never rests or fills, so a `--validate-orders` run only ever
+    /// checks whether the strategy's output would be exchange-acceptable.
+    #[allow(clippy::too_many_arguments)]
+    fn dispatch_match(
+        client: Arc<dyn ExchangeClient + Send + Sync>,
+        symbol: Symbol,
+        exchange_name: String,
+        side: Side,
+        price: Price,
+        qty: Quantity,
+        order_id_counter: &Arc<AtomicU64>,
+        match_id_counter: &Arc<AtomicU64>,
+        pending_matches: &Arc<RwLock<HashMap<u64, ExecutableMatch>>>,
+        fill_tracker: &Arc<RwLock<OrderFillTracker>>,
+        risk_manager: &Arc<RwLock<RiskManager>>,
+        orders_sent: &Arc<AtomicU64>,
+        event_tx: &Sender<EngineEvent>,
+        validate_orders: bool,
+    ) {
+        if validate_orders {
+            let request = match side {
+                Side::Buy => OrderRequest::limit_buy(symbol, price, qty),
+                Side::Sell => OrderRequest::limit_sell(symbol, price, qty),
+            };
+            tokio::spawn(async move {
+                if let Err(e) = client.test_order(request).await {
+                    warn!("Order validation failed: {}", e);
+                }
+            });
+            return;
+        }
+
+        let order_id = order_id_counter.fetch_add(1, Ordering::Relaxed);
+        let match_id = match_id_counter.fetch_add(1, Ordering::Relaxed);
+
+        let order = Order {
+            id: order_id,
+            client_id: 0,
+            symbol: symbol.clone(),
+            side,
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::Gtc,
+            price,
+            quantity: qty,
+            filled_qty: 0,
+            status: OrderStatus::New,
+            timestamp: now_nanos(),
+            expires_at: None,
+        };
+
+        risk_manager.write().on_order_sent(order.clone());
+        pending_matches.write().insert(
+            match_id,
+            ExecutableMatch {
+                match_id,
+                order_id,
+                exchange: exchange_name,
+                symbol: symbol.clone(),
+                side,
+                price,
+                qty,
+                created_at: now_nanos(),
+            },
+        );
+        orders_sent.fetch_add(1, Ordering::Relaxed);
+        fill_tracker.write().register(order_id, qty);
+
+        let request = match side {
+            Side::Buy => OrderRequest::limit_buy(symbol, price, qty),
+            Side::Sell => OrderRequest::limit_sell(symbol, price, qty),
+        };
+        let pending_matches = pending_matches.clone();
+        let risk_manager = risk_manager.clone();
+        let event_tx = event_tx.clone();
+
+        tokio::spawn(async move {
+            let result = client.send_order(request).await;
+
+            let response = match &result {
+                Ok(response) if response.success => response,
+                _ => {
+                    if let Err(e) = &result {
+                        warn!("Order dispatch failed for match {}: {}", match_id, e);
+                    }
+                    risk_manager.write().on_order_canceled(order_id);
+                    pending_matches.write().remove(&match_id);
+                    return;
+                }
+            };
+
+            // A `New`/`PartiallyFilled` acceptance just confirms the order is
+            // resting; it's left in `pending_matches` (with its reservation
+            // still held) until the exchange's actual fill reports — carried
+            // by subsequent `Trade`s routed through `on_trade` — bring its
+            // `OrderFillTracker` status to `Filled`.
+            if response.status != OrderStatus::Filled {
+                return;
+            }
+
+            risk_manager.write().on_fill(&order, qty, price, true);
+            pending_matches.write().remove(&match_id);
+
+            let trade = Trade {
+                order_id,
+                trade_id: match_id,
+                symbol: order.symbol,
+                side,
+                price,
+                quantity: qty,
+                timestamp: now_nanos(),
+                is_maker: true,
+            };
+            let _ = event_tx.try_send(EngineEvent::Trade(trade));
+        });
+    }
+
     /// Get statistics
     pub fn stats(&self) -> EngineStats {
         EngineStats {
             ticks_processed: self.ticks_processed.load(Ordering::Relaxed),
             orders_sent: self.orders_sent.load(Ordering::Relaxed),
             trades_executed: self.trades_executed.load(Ordering::Relaxed),
+            avg_fill_ratio: self.fill_tracker.read().average_fill_ratio(),
+            maintenance_mode: self.maintenance_mode.load(Ordering::Relaxed),
         }
     }
 }
@@ -315,6 +962,15 @@ pub struct EngineStats {
     pub ticks_processed: u64,
     pub orders_sent: u64,
     pub trades_executed: u64,
+    /// Average filled/original quantity ratio across currently outstanding
+    /// orders, per [`OrderFillTracker::average_fill_ratio`]; `1.0` when
+    /// nothing is outstanding.
+    pub avg_fill_ratio: f64,
+    /// Whether the engine is winding down toward flat rather than quoting
+    /// normally, so monitoring can tell "halted with open orders" (`true`)
+    /// apart from "fully flat" (`false`); see
+    /// [`TradingEngine::enter_maintenance_mode`]
+    pub maintenance_mode: bool,
 }
 
 // ============================================================================
@@ -352,11 +1008,21 @@ impl EngineBuilder {
         self
     }
 
+    pub fn fee_model(mut self, fee_model: FeeModel) -> Self {
+        self.config.fee_model = fee_model;
+        self
+    }
+
     pub fn enable_trading(mut self, enabled: bool) -> Self {
         self.config.enable_trading = enabled;
         self
     }
 
+    pub fn validate_orders(mut self, enabled: bool) -> Self {
+        self.config.validate_orders = enabled;
+        self
+    }
+
     pub fn build(self) -> TradingEngine {
         TradingEngine::new(self.config)
     }
@@ -1,21 +1,50 @@
 //! Memory pool for zero-allocation hot paths
 
 use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::ptr::NonNull;
 use std::marker::PhantomData;
 
+/// Sentinel `next`/index value meaning "no block", since a bare `*mut T` has
+/// no equivalent of null once indices are packed into a tagged word
+const NULL_INDEX: usize = u32::MAX as usize;
+/// Bits of the packed free-list word given to the block index; the remaining
+/// high bits are a generation tag
+const INDEX_BITS: u32 = 32;
+const INDEX_MASK: usize = (1usize << INDEX_BITS) - 1;
+
+/// Pack a free-list `(index, tag)` pair into one word so the whole thing can
+/// be compare-and-swapped atomically
+#[inline]
+fn pack(index: usize, tag: usize) -> usize {
+    (tag << INDEX_BITS) | (index & INDEX_MASK)
+}
+
+#[inline]
+fn unpack(word: usize) -> (usize, usize) {
+    (word & INDEX_MASK, word >> INDEX_BITS)
+}
+
 /// A fixed-size memory pool for fast allocation
+///
+/// The free list's head is a single tagged `AtomicUsize` packing a block
+/// index and a generation counter, rather than a bare `AtomicPtr`. A plain
+/// pointer CAS is vulnerable to the ABA problem: if a block is popped,
+/// reused, and pushed back onto the free list between a thread's `load` and
+/// its `compare_exchange_weak`, the head pointer can read as unchanged while
+/// the list underneath it was rebuilt, and the CAS would succeed against a
+/// corrupted list. Every push/pop bumps the tag, so a racing thread's stale
+/// head word fails the exchange and retries instead of succeeding spuriously.
 pub struct MemoryPool<T> {
     blocks: Box<[UnsafeCell<Block<T>>]>,
-    free_list: AtomicPtr<Block<T>>,
+    free_head: AtomicUsize,
     allocated: AtomicUsize,
     capacity: usize,
 }
 
 struct Block<T> {
     data: Option<T>,
-    next: *mut Block<T>,
+    next: usize,
 }
 
 // Safety: MemoryPool can be safely shared between threads
@@ -25,29 +54,27 @@ unsafe impl<T: Send> Sync for MemoryPool<T> {}
 impl<T> MemoryPool<T> {
     /// Create a new memory pool with the given capacity
     pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity < NULL_INDEX,
+            "MemoryPool capacity {} too large to pack into a tagged index",
+            capacity
+        );
+
         let mut blocks: Vec<UnsafeCell<Block<T>>> = Vec::with_capacity(capacity);
 
-        for _ in 0..capacity {
-            blocks.push(UnsafeCell::new(Block {
-                data: None,
-                next: std::ptr::null_mut(),
-            }));
+        for i in 0..capacity {
+            let next = if i + 1 < capacity { i + 1 } else { NULL_INDEX };
+            blocks.push(UnsafeCell::new(Block { data: None, next }));
         }
 
         let blocks = blocks.into_boxed_slice();
 
-        // Build free list
-        for i in 0..capacity - 1 {
-            unsafe {
-                (*blocks[i].get()).next = blocks[i + 1].get();
-            }
-        }
-
-        let free_list = AtomicPtr::new(blocks[0].get());
+        let head_index = if capacity > 0 { 0 } else { NULL_INDEX };
+        let free_head = AtomicUsize::new(pack(head_index, 0));
 
         MemoryPool {
             blocks,
-            free_list,
+            free_head,
             allocated: AtomicUsize::new(0),
             capacity,
         }
@@ -56,24 +83,28 @@ impl<T> MemoryPool<T> {
     /// Allocate an item from the pool
     pub fn allocate(&self, value: T) -> Option<PoolHandle<T>> {
         loop {
-            let head = self.free_list.load(Ordering::Acquire);
-            if head.is_null() {
+            let head_word = self.free_head.load(Ordering::Acquire);
+            let (index, tag) = unpack(head_word);
+            if index == NULL_INDEX {
                 return None; // Pool exhausted
             }
 
+            let block = self.blocks[index].get();
             unsafe {
-                let next = (*head).next;
-                if self.free_list.compare_exchange_weak(
-                    head,
-                    next,
+                let next = (*block).next;
+                let new_word = pack(next, tag.wrapping_add(1));
+                if self.free_head.compare_exchange_weak(
+                    head_word,
+                    new_word,
                     Ordering::Release,
                     Ordering::Relaxed,
                 ).is_ok() {
-                    (*head).data = Some(value);
-                    (*head).next = std::ptr::null_mut();
+                    (*block).data = Some(value);
+                    (*block).next = NULL_INDEX;
                     self.allocated.fetch_add(1, Ordering::Relaxed);
                     return Some(PoolHandle {
-                        ptr: NonNull::new_unchecked(head),
+                        ptr: NonNull::new_unchecked(block),
+                        index,
                         _marker: PhantomData,
                     });
                 }
@@ -87,17 +118,20 @@ impl<T> MemoryPool<T> {
     /// The handle must have been allocated from this pool
     pub unsafe fn deallocate(&self, handle: PoolHandle<T>) {
         let block = handle.ptr.as_ptr();
+        let index = handle.index;
 
         // Clear the data
         (*block).data = None;
 
         // Add back to free list
         loop {
-            let head = self.free_list.load(Ordering::Relaxed);
-            (*block).next = head;
-            if self.free_list.compare_exchange_weak(
-                head,
-                block,
+            let head_word = self.free_head.load(Ordering::Relaxed);
+            let (head_index, tag) = unpack(head_word);
+            (*block).next = head_index;
+            let new_word = pack(index, tag.wrapping_add(1));
+            if self.free_head.compare_exchange_weak(
+                head_word,
+                new_word,
                 Ordering::Release,
                 Ordering::Relaxed,
             ).is_ok() {
@@ -125,9 +159,25 @@ impl<T> MemoryPool<T> {
     }
 }
 
+impl<T> Drop for MemoryPool<T> {
+    /// Drop any `T` still held by blocks whose `PoolHandle` was leaked or
+    /// forgotten without going through `deallocate` (a handle carries no
+    /// destructor of its own, since its `T` is owned by the pool's block,
+    /// not the handle). Walking `blocks` here guarantees every live item
+    /// is dropped once, whether or not its handle was ever returned.
+    fn drop(&mut self) {
+        for cell in self.blocks.iter() {
+            unsafe {
+                (*cell.get()).data = None;
+            }
+        }
+    }
+}
+
 /// Handle to a pool-allocated item
 pub struct PoolHandle<T> {
     ptr: NonNull<Block<T>>,
+    index: usize,
     _marker: PhantomData<T>,
 }
 
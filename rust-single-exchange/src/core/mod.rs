@@ -0,0 +1,9 @@
+//! Core engine, data types, and low-level primitives
+
+pub mod bus;
+pub mod encoding;
+pub mod engine;
+pub mod memory;
+pub mod queue;
+pub mod shmem;
+pub mod types;
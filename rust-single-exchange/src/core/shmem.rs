@@ -0,0 +1,172 @@
+//! Lock-free POD ring buffer for publishing market data with no allocation or copying
+//!
+//! A single feed-handler thread/process publishes fixed-size `Pod` slots (e.g. a
+//! `Tick`) into a power-of-two ring; any number of consumer threads/processes can
+//! read the latest slots without locks, using a per-slot sequence number (a
+//! seqlock) to detect and retry a read that raced a concurrent write. This is the
+//! layout that would back an mmap'd shared-memory segment shared between a feed
+//! handler process and strategy processes, though it works identically in-process.
+//!
+//! Invariant: slot types must not contain a `String`/`Symbol` or any other
+//! heap-allocated field, since there is no allocator backing shared memory —
+//! reference a symbol by a `u32` id against a side-channel symbol table instead.
+
+use bytemuck::Pod;
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const MAX_READ_RETRIES: u32 = 64;
+
+/// Why a `read` could not return a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadError {
+    /// No slot has been published at this index yet
+    NotYetPublished,
+    /// The producer has wrapped the ring and overwritten this slot before it
+    /// could be read; the consumer must skip ahead
+    Lagged,
+}
+
+struct Slot<T> {
+    /// Even once the write at this slot is stable; odd while a write is in
+    /// progress. Readers retry if this changes between their two loads.
+    sequence: AtomicU64,
+    value: UnsafeCell<T>,
+}
+
+/// Single-producer, multi-consumer ring of fixed-size POD slots
+pub struct ShmemRing<T: Pod> {
+    mask: u64,
+    slots: Box<[Slot<T>]>,
+    write_seq: AtomicU64,
+}
+
+// `Slot::value` is only ever mutated by the single producer and is always read
+// through the seqlock protocol below, so sharing `&ShmemRing` across threads is sound.
+unsafe impl<T: Pod + Send> Sync for ShmemRing<T> {}
+
+impl<T: Pod> ShmemRing<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity.is_power_of_two(), "ShmemRing capacity must be a power of two");
+
+        let slots = (0..capacity)
+            .map(|_| Slot {
+                sequence: AtomicU64::new(0),
+                value: UnsafeCell::new(T::zeroed()),
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        ShmemRing {
+            mask: capacity as u64 - 1,
+            slots,
+            write_seq: AtomicU64::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Absolute index the next `publish` will write to
+    pub fn write_index(&self) -> u64 {
+        self.write_seq.load(Ordering::Acquire)
+    }
+
+    /// Publish a value, returning the absolute index it was written at. Callers
+    /// must serialize calls themselves (single producer).
+    pub fn publish(&self, value: T) -> u64 {
+        let idx = self.write_seq.fetch_add(1, Ordering::AcqRel);
+        let slot = &self.slots[(idx & self.mask) as usize];
+
+        let base = slot.sequence.load(Ordering::Relaxed);
+        slot.sequence.store(base.wrapping_add(1), Ordering::Release);
+        unsafe { *slot.value.get() = value };
+        slot.sequence.store(base.wrapping_add(2), Ordering::Release);
+
+        idx
+    }
+
+    /// Tear-free read of the slot published at absolute index `idx`
+    pub fn read(&self, idx: u64) -> Result<T, ReadError> {
+        if idx >= self.write_seq.load(Ordering::Acquire) {
+            return Err(ReadError::NotYetPublished);
+        }
+
+        let slot = &self.slots[(idx & self.mask) as usize];
+
+        for _ in 0..MAX_READ_RETRIES {
+            let before = slot.sequence.load(Ordering::Acquire);
+            if before & 1 != 0 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let value = unsafe { *slot.value.get() };
+            let after = slot.sequence.load(Ordering::Acquire);
+
+            if before != after {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            if self.write_seq.load(Ordering::Acquire).saturating_sub(idx) > self.slots.len() as u64 {
+                return Err(ReadError::Lagged);
+            }
+
+            return Ok(value);
+        }
+
+        Err(ReadError::Lagged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{to_price, to_qty, Tick};
+
+    fn sample_tick(sequence: u64) -> Tick {
+        Tick {
+            bid: to_price(100.0),
+            ask: to_price(100.1),
+            bid_qty: to_qty(1.0),
+            ask_qty: to_qty(1.0),
+            last_price: to_price(100.05),
+            last_qty: to_qty(0.1),
+            exchange_ts: sequence,
+            local_ts: sequence,
+            sequence,
+        }
+    }
+
+    #[test]
+    fn test_publish_then_read() {
+        let ring: ShmemRing<Tick> = ShmemRing::new(8);
+        let idx = ring.publish(sample_tick(1));
+        let tick = ring.read(idx).unwrap();
+        assert_eq!(tick.sequence, 1);
+    }
+
+    #[test]
+    fn test_read_not_yet_published() {
+        let ring: ShmemRing<Tick> = ShmemRing::new(8);
+        assert_eq!(ring.read(0), Err(ReadError::NotYetPublished));
+    }
+
+    #[test]
+    fn test_read_lagged_after_wraparound() {
+        let ring: ShmemRing<Tick> = ShmemRing::new(4);
+        let first = ring.publish(sample_tick(1));
+        for i in 2..=10 {
+            ring.publish(sample_tick(i));
+        }
+        assert_eq!(ring.read(first), Err(ReadError::Lagged));
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn test_rejects_non_power_of_two_capacity() {
+        let _ring: ShmemRing<Tick> = ShmemRing::new(3);
+    }
+}
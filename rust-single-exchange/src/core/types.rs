@@ -83,6 +83,7 @@ pub enum TimeInForce {
     Ioc = 1,  // Immediate or cancel
     Fok = 2,  // Fill or kill
     Gtx = 3,  // Good till crossing (post-only)
+    Gtd = 4,  // Good till date: rests like Gtc until `Order::expires_at`
 }
 
 // ============================================================================
@@ -137,6 +138,9 @@ pub struct Order {
     pub filled_qty: Quantity,
     pub status: OrderStatus,
     pub timestamp: Timestamp,
+    /// Wall-clock time at which a resting order is considered stale and eligible
+    /// for lazy removal (GTD time-in-force); `None` never expires on its own
+    pub expires_at: Option<Timestamp>,
 }
 
 impl Order {
@@ -159,6 +163,7 @@ impl Order {
             filled_qty: 0,
             status: OrderStatus::New,
             timestamp: now_nanos(),
+            expires_at: None,
         }
     }
 
@@ -166,6 +171,12 @@ impl Order {
         self.quantity - self.filled_qty
     }
 
+    /// Whether this order is stale as of `now` and should be lazily dropped if
+    /// encountered resting in a book
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires_at.is_some_and(|t| now >= t)
+    }
+
     pub fn is_active(&self) -> bool {
         matches!(
             self.status,
@@ -178,7 +189,10 @@ impl Order {
 // Quote
 // ============================================================================
 
-#[derive(Debug, Clone, Copy, Default)]
+/// `repr(C)` + `Pod`: trivially copyable so it can be written directly into the
+/// `core::shmem` ring buffer with no serialization step
+#[derive(Debug, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
 pub struct Quote {
     pub bid_price: Price,
     pub ask_price: Price,
@@ -221,11 +235,43 @@ pub struct Trade {
     pub is_maker: bool,
 }
 
+// ============================================================================
+// Balance
+// ============================================================================
+
+/// A single asset's free/locked balance, as reported by an exchange's
+/// account/user-data feed (e.g. Binance's `outboundAccountPosition` event)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Balance {
+    pub asset: String,
+    pub free: f64,
+    pub locked: f64,
+}
+
+// ============================================================================
+// Funding
+// ============================================================================
+
+/// A mark-price/funding-rate update from a futures venue's dedicated stream
+/// (e.g. Binance's `markPriceUpdate`), surfaced separately from [`Tick`]
+/// since it carries none of `Tick`'s best-bid/ask shape.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FundingUpdate {
+    pub mark_price: Price,
+    /// Fraction of notional paid per funding interval; positive means longs
+    /// pay shorts, negative means shorts pay longs
+    pub funding_rate: f64,
+    pub next_funding_time: Timestamp,
+}
+
 // ============================================================================
 // Market Data Tick
 // ============================================================================
 
-#[derive(Debug, Clone, Copy, Default)]
+/// `repr(C)` + `Pod`: trivially copyable so it can be written directly into the
+/// `core::shmem` ring buffer with no serialization step
+#[derive(Debug, Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
 pub struct Tick {
     pub bid: Price,
     pub ask: Price,
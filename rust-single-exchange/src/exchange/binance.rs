@@ -0,0 +1,1379 @@
+//! Binance exchange client implementation
+
+use super::*;
+use crate::core::types::*;
+use crate::orderbook::OrderBook;
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How often to `PUT /api/v3/userDataStream` to keep a `listenKey` alive;
+/// Binance expires a key after 60 minutes without one
+const LISTEN_KEY_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+type WsStream = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+type WsSender = futures_util::stream::SplitSink<WsStream, Message>;
+type WsReceiver = futures_util::stream::SplitStream<WsStream>;
+
+/// Binance's documented snapshot+diff protocol for maintaining a consistent
+/// local depth book (see [`BinanceClient::subscribe_orderbook`]'s doc
+/// comment for the full procedure). `Buffering` accumulates `depthUpdate`
+/// events until a REST snapshot has been fetched and reconciled against
+/// them; `Synced` holds the resulting book plus the last applied event's
+/// final update id (`u`), used to detect sequence gaps on the next event.
+enum DepthSync {
+    Buffering(Vec<serde_json::Value>),
+    Synced { book: OrderBook, last_u: u64 },
+}
+
+/// Per-symbol depth-sync state: the snapshot `limit` requested at subscribe
+/// time (re-used on every resync) plus the current [`DepthSync`]
+struct DepthSyncState {
+    limit: u32,
+    sync: DepthSync,
+}
+
+/// Binance-specific configuration
+#[derive(Debug, Clone)]
+pub struct BinanceConfig {
+    pub base: ExchangeConfig,
+    pub use_futures: bool,
+    pub recv_window: u64,
+    /// Starting delay before the first reconnect attempt after a dropped
+    /// connection; doubles on each further failure up to `reconnect_max_backoff_ms`
+    pub reconnect_initial_backoff_ms: u64,
+    /// Cap on the exponential reconnect backoff
+    pub reconnect_max_backoff_ms: u64,
+    /// Force a reconnect if no WebSocket frame (including pings) has arrived
+    /// within this window, catching a half-open socket tungstenite's
+    /// automatic pong handling won't notice on its own
+    pub idle_timeout_ms: u64,
+}
+
+impl Default for BinanceConfig {
+    fn default() -> Self {
+        BinanceConfig {
+            base: ExchangeConfig::default(),
+            use_futures: false,
+            recv_window: 5000,
+            reconnect_initial_backoff_ms: 100,
+            reconnect_max_backoff_ms: 30_000,
+            idle_timeout_ms: 60_000,
+        }
+    }
+}
+
+impl BinanceConfig {
+    pub fn testnet() -> Self {
+        let mut config = Self::default();
+        config.base.testnet = true;
+        config.base.rest_url = "https://testnet.binance.vision".to_string();
+        config.base.ws_url = "wss://testnet.binance.vision/ws".to_string();
+        config
+    }
+
+    pub fn futures_testnet() -> Self {
+        let mut config = Self::testnet();
+        config.use_futures = true;
+        config.base.rest_url = "https://testnet.binancefuture.com".to_string();
+        config.base.ws_url = "wss://stream.binancefuture.com/ws".to_string();
+        config
+    }
+}
+
+/// Binance exchange client
+pub struct BinanceClient {
+    config: BinanceConfig,
+    http_client: Client,
+    callbacks: Arc<RwLock<ExchangeCallbacks>>,
+    connected: Arc<AtomicBool>,
+    ws_sender: Arc<RwLock<Option<WsSender>>>,
+    /// Managed depth-sync state per subscribed symbol, shared with the
+    /// message-handler task spawned in `connect()`
+    depth_sync: Arc<RwLock<HashMap<Symbol, DepthSyncState>>>,
+    /// Every SUBSCRIBE stream name registered via `subscribe_ticker`/
+    /// `subscribe_orderbook`/`subscribe_trades`, replayed on each reconnect
+    subscriptions: Arc<RwLock<Vec<String>>>,
+    /// Nanosecond timestamp of the last frame received on the current
+    /// connection; the reconnect supervisor forces a redial once this goes
+    /// stale for longer than `BinanceConfig::idle_timeout_ms`
+    last_message_ts: Arc<AtomicI64>,
+    /// Set by `disconnect()` so the reconnect supervisor stands down instead
+    /// of redialing after an intentional disconnect
+    shutting_down: Arc<AtomicBool>,
+    /// Send half of the dedicated user-data-stream connection opened by
+    /// `subscribe_user_stream`, kept separate from the market-data `ws_sender`
+    user_stream_sender: Arc<RwLock<Option<WsSender>>>,
+    /// Set by `disconnect()` so the user-data-stream supervisor stands down
+    /// instead of refreshing the `listenKey` and redialing
+    user_stream_shutting_down: Arc<AtomicBool>,
+    /// Bumped every time `subscribe_user_stream` (re)connects with a fresh
+    /// `listenKey`, so a keepalive task for a since-replaced key knows to
+    /// stop PUTting it rather than renewing a connection nothing is using
+    listen_key_generation: Arc<AtomicU64>,
+}
+
+impl BinanceClient {
+    pub fn new(config: BinanceConfig) -> Self {
+        let http_client = Client::builder()
+            .timeout(std::time::Duration::from_millis(config.base.connect_timeout_ms))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        BinanceClient {
+            config,
+            http_client,
+            callbacks: Arc::new(RwLock::new(ExchangeCallbacks::default())),
+            connected: Arc::new(AtomicBool::new(false)),
+            ws_sender: Arc::new(RwLock::new(None)),
+            depth_sync: Arc::new(RwLock::new(HashMap::new())),
+            subscriptions: Arc::new(RwLock::new(Vec::new())),
+            last_message_ts: Arc::new(AtomicI64::new(0)),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            user_stream_sender: Arc::new(RwLock::new(None)),
+            user_stream_shutting_down: Arc::new(AtomicBool::new(false)),
+            listen_key_generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn sign(&self, query: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(self.config.base.api_secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(query.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    async fn signed_request(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+        params: &[(&str, &str)],
+    ) -> Result<serde_json::Value, ExchangeError> {
+        let timestamp = now_millis().to_string();
+        let mut query_params: Vec<(&str, &str)> = params.to_vec();
+        query_params.push(("timestamp", &timestamp));
+        query_params.push(("recvWindow", &self.config.recv_window.to_string()));
+
+        let query_string: String = query_params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let signature = self.sign(&query_string);
+        let full_query = format!("{}&signature={}", query_string, signature);
+
+        let url = format!("{}{}?{}", self.config.base.rest_url, endpoint, full_query);
+
+        let response = self
+            .http_client
+            .request(method, &url)
+            .header("X-MBX-APIKEY", &self.config.base.api_key)
+            .send()
+            .await
+            .map_err(|e| ExchangeError::RequestFailed(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let wire = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|value| WireError::from_binance_json(&value));
+
+            return Err(match wire {
+                Some(wire) => ExchangeError::from_wire(wire, endpoint, None),
+                None => ExchangeError::RequestFailed(format!("HTTP {}: {}", status, body)),
+            });
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| ExchangeError::ParseError(e.to_string()))
+    }
+
+    async fn public_request(&self, endpoint: &str) -> Result<serde_json::Value, ExchangeError> {
+        let url = format!("{}{}", self.config.base.rest_url, endpoint);
+
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ExchangeError::RequestFailed(e.to_string()))?;
+
+        response
+            .json()
+            .await
+            .map_err(|e| ExchangeError::ParseError(e.to_string()))
+    }
+
+    fn parse_ticker(data: &serde_json::Value) -> Option<Tick> {
+        Some(Tick {
+            bid: to_price(data["b"].as_str()?.parse().ok()?),
+            ask: to_price(data["a"].as_str()?.parse().ok()?),
+            bid_qty: to_qty(data["B"].as_str()?.parse().ok()?),
+            ask_qty: to_qty(data["A"].as_str()?.parse().ok()?),
+            last_price: to_price(data["c"].as_str().unwrap_or("0").parse().unwrap_or(0.0)),
+            last_qty: 0,
+            exchange_ts: data["E"].as_u64().unwrap_or(0) * 1_000_000,
+            local_ts: now_nanos(),
+            sequence: 0,
+        })
+    }
+
+    fn parse_order_update(data: &serde_json::Value) -> Option<Order> {
+        let status_str = data["X"].as_str()?;
+        let status = match status_str {
+            "NEW" => OrderStatus::New,
+            "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+            "FILLED" => OrderStatus::Filled,
+            "CANCELED" => OrderStatus::Canceled,
+            "REJECTED" => OrderStatus::Rejected,
+            "EXPIRED" => OrderStatus::Expired,
+            _ => return None,
+        };
+
+        let side_str = data["S"].as_str()?;
+        let side = match side_str {
+            "BUY" => Side::Buy,
+            "SELL" => Side::Sell,
+            _ => return None,
+        };
+
+        Some(Order {
+            id: data["i"].as_u64()?,
+            client_id: data["c"].as_str()?.parse().unwrap_or(0),
+            symbol: Symbol::new(data["s"].as_str()?),
+            side,
+            order_type: OrderType::Limit,
+            time_in_force: TimeInForce::Gtc,
+            price: to_price(data["p"].as_str()?.parse().ok()?),
+            quantity: to_qty(data["q"].as_str()?.parse().ok()?),
+            filled_qty: to_qty(data["z"].as_str()?.parse().ok()?),
+            status,
+            timestamp: data["T"].as_u64()? * 1_000_000,
+            expires_at: None,
+        })
+    }
+
+    /// Parse an `outboundAccountPosition` event's `"B"` array into one
+    /// [`Balance`] per asset it reports
+    fn parse_balance_update(data: &serde_json::Value) -> Vec<Balance> {
+        data["B"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                Some(Balance {
+                    asset: entry["a"].as_str()?.to_string(),
+                    free: entry["f"].as_str()?.parse().ok()?,
+                    locked: entry["l"].as_str()?.parse().ok()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Parse a `markPriceUpdate` event (Binance Futures' `<symbol>@markPrice`
+    /// stream) into a [`FundingUpdate`]
+    fn parse_funding_update(data: &serde_json::Value) -> Option<FundingUpdate> {
+        Some(FundingUpdate {
+            mark_price: to_price(data["p"].as_str()?.parse().ok()?),
+            funding_rate: data["r"].as_str()?.parse().ok()?,
+            next_funding_time: data["T"].as_u64()? * 1_000_000,
+        })
+    }
+
+    /// Fetch a REST depth snapshot, returning its `lastUpdateId` alongside
+    /// the parsed bid/ask level arrays
+    async fn fetch_depth_snapshot(
+        http_client: &Client,
+        rest_url: &str,
+        symbol: &Symbol,
+        limit: u32,
+    ) -> Result<(u64, Vec<(Price, Quantity)>, Vec<(Price, Quantity)>), ExchangeError> {
+        let url = format!("{}/api/v3/depth?symbol={}&limit={}", rest_url, symbol.as_str(), limit);
+
+        let response = http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ExchangeError::RequestFailed(e.to_string()))?;
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ExchangeError::ParseError(e.to_string()))?;
+
+        let last_update_id = data["lastUpdateId"]
+            .as_u64()
+            .ok_or_else(|| ExchangeError::ParseError("Missing lastUpdateId in depth snapshot".to_string()))?;
+
+        let parse_levels = |key: &str| -> Result<Vec<(Price, Quantity)>, ExchangeError> {
+            data[key]
+                .as_array()
+                .ok_or_else(|| ExchangeError::ParseError(format!("Missing {} in depth snapshot", key)))?
+                .iter()
+                .map(|level| {
+                    let price = level[0].as_str().and_then(|s| s.parse::<f64>().ok());
+                    let qty = level[1].as_str().and_then(|s| s.parse::<f64>().ok());
+                    match (price, qty) {
+                        (Some(p), Some(q)) => Ok((to_price(p), to_qty(q))),
+                        _ => Err(ExchangeError::ParseError(format!("Invalid level in {}", key))),
+                    }
+                })
+                .collect()
+        };
+
+        let bids = parse_levels("bids")?;
+        let asks = parse_levels("asks")?;
+        Ok((last_update_id, bids, asks))
+    }
+
+    /// Apply a single `depthUpdate` event's bid/ask arrays to `book`; a
+    /// level with quantity 0 deletes that price, otherwise it replaces it
+    fn apply_depth_event(book: &mut OrderBook, event: &serde_json::Value) -> Result<(), ExchangeError> {
+        let apply_levels = |levels: &serde_json::Value, book: &mut OrderBook, is_bid: bool| -> Result<(), ExchangeError> {
+            for level in levels.as_array().into_iter().flatten() {
+                let price = level[0].as_str().and_then(|s| s.parse::<f64>().ok());
+                let qty = level[1].as_str().and_then(|s| s.parse::<f64>().ok());
+                let (price, qty) = match (price, qty) {
+                    (Some(p), Some(q)) => (to_price(p), to_qty(q)),
+                    _ => return Err(ExchangeError::ParseError("Invalid depth update level".to_string())),
+                };
+
+                let result = if is_bid { book.update_bid(price, qty) } else { book.update_ask(price, qty) };
+                result.map_err(|e| ExchangeError::ParseError(e.to_string()))?;
+            }
+            Ok(())
+        };
+
+        apply_levels(&event["b"], book, true)?;
+        apply_levels(&event["a"], book, false)?;
+        Ok(())
+    }
+
+    /// Reconcile a REST snapshot against diff events buffered while it was
+    /// in flight, per Binance's documented procedure: events with `u` before
+    /// the snapshot are stale and dropped; the first applied event must
+    /// satisfy `U <= lastUpdateId+1 <= u`; every event after that must chain
+    /// `U == previous u + 1`. Returns `None` if the snapshot itself is
+    /// invalid or the buffered events don't chain, signaling the caller to
+    /// fetch a fresh snapshot and try again.
+    fn reconcile_snapshot(
+        symbol: &Symbol,
+        last_update_id: u64,
+        bids: Vec<(Price, Quantity)>,
+        asks: Vec<(Price, Quantity)>,
+        buffered: Vec<serde_json::Value>,
+    ) -> Option<(OrderBook, u64)> {
+        let mut book = OrderBook::new(symbol.clone());
+        book.apply_snapshot(bids, asks).ok()?;
+
+        let mut last_u = last_update_id;
+        let mut validated_first = false;
+
+        for event in &buffered {
+            let u = event["u"].as_u64()?;
+            if u < last_update_id {
+                continue;
+            }
+
+            if !validated_first {
+                let first_u = event["U"].as_u64().unwrap_or(0);
+                if first_u > last_update_id + 1 {
+                    return None;
+                }
+                validated_first = true;
+            } else if event["U"].as_u64().unwrap_or(0) != last_u + 1 {
+                return None;
+            }
+
+            Self::apply_depth_event(&mut book, event).ok()?;
+            last_u = u;
+        }
+
+        Some((book, last_u))
+    }
+
+    /// (Re)synchronize `symbol`'s managed book: marks it `Buffering` (seeded
+    /// with `seed`, e.g. the diff event that revealed a sequence gap), fetches
+    /// a REST snapshot, and reconciles it against whatever diffs accumulated
+    /// in the meantime. Retries with a fresh snapshot if reconciliation fails.
+    /// Fires `on_orderbook` once synced.
+    async fn resync_depth(
+        http_client: &Client,
+        rest_url: &str,
+        symbol: &Symbol,
+        limit: u32,
+        depth_sync: &RwLock<HashMap<Symbol, DepthSyncState>>,
+        callbacks: &RwLock<ExchangeCallbacks>,
+        seed: Vec<serde_json::Value>,
+    ) {
+        depth_sync
+            .write()
+            .await
+            .insert(symbol.clone(), DepthSyncState { limit, sync: DepthSync::Buffering(seed) });
+
+        loop {
+            let (last_update_id, bids, asks) = match Self::fetch_depth_snapshot(http_client, rest_url, symbol, limit).await {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    error!("Failed to fetch depth snapshot for {}: {}", symbol, e);
+                    return;
+                }
+            };
+
+            let mut sync_map = depth_sync.write().await;
+            let buffered = match sync_map.remove(symbol) {
+                Some(DepthSyncState { sync: DepthSync::Buffering(buffered), .. }) => buffered,
+                // Another resync (or a steady-state update) already moved this
+                // symbol on; stand down rather than clobber it
+                Some(state) => {
+                    sync_map.insert(symbol.clone(), state);
+                    return;
+                }
+                None => return,
+            };
+
+            match Self::reconcile_snapshot(symbol, last_update_id, bids, asks, buffered) {
+                Some((book, last_u)) => {
+                    sync_map.insert(symbol.clone(), DepthSyncState { limit, sync: DepthSync::Synced { book, last_u } });
+                    if let Some(DepthSyncState { sync: DepthSync::Synced { book, .. }, .. }) = sync_map.get(symbol) {
+                        let cbs = callbacks.read().await;
+                        if let Some(ref cb) = cbs.on_orderbook {
+                            cb(book);
+                        }
+                    }
+                    info!("Orderbook sync established for {}", symbol);
+                    return;
+                }
+                None => {
+                    debug!("Depth snapshot/diff reconciliation failed for {}; retrying", symbol);
+                    sync_map.insert(symbol.clone(), DepthSyncState { limit, sync: DepthSync::Buffering(Vec::new()) });
+                }
+            }
+        }
+    }
+
+    /// Add up to 20% jitter to a reconnect backoff, derived from the current
+    /// timestamp so this doesn't need its own random-number dependency
+    fn jittered(backoff: Duration) -> Duration {
+        let backoff_ms = backoff.as_millis() as u64;
+        if backoff_ms == 0 {
+            return backoff;
+        }
+        let jitter_ms = now_nanos() % (backoff_ms / 5 + 1);
+        backoff + Duration::from_millis(jitter_ms)
+    }
+
+    /// Open a fresh WebSocket connection, store the send half in `ws_sender`,
+    /// and fire `on_connected`. Returns the receive half for the caller to
+    /// feed into `run_receive_loop`.
+    async fn dial(
+        ws_url: &str,
+        ws_sender: &RwLock<Option<WsSender>>,
+        callbacks: &RwLock<ExchangeCallbacks>,
+    ) -> Result<WsReceiver, ExchangeError> {
+        let (ws_stream, _) = connect_async(ws_url)
+            .await
+            .map_err(|e| ExchangeError::ConnectionFailed(e.to_string()))?;
+
+        let (sender, receiver) = ws_stream.split();
+        *ws_sender.write().await = Some(sender);
+
+        let cbs = callbacks.read().await;
+        if let Some(ref cb) = cbs.on_connected {
+            cb();
+        }
+
+        Ok(receiver)
+    }
+
+    /// Re-send every SUBSCRIBE stream registered before a reconnect, so the
+    /// feed resumes exactly the subscriptions it had before the drop
+    async fn resubscribe(ws_sender: &RwLock<Option<WsSender>>, subscriptions: &[String]) {
+        if subscriptions.is_empty() {
+            return;
+        }
+
+        if let Some(ref mut sender) = *ws_sender.write().await {
+            for (i, stream) in subscriptions.iter().enumerate() {
+                let msg = serde_json::json!({
+                    "method": "SUBSCRIBE",
+                    "params": [stream],
+                    "id": i as u64 + 1,
+                });
+                if let Err(e) = sender.send(Message::Text(msg.to_string())).await {
+                    error!("Failed to resubscribe to {}: {}", stream, e);
+                }
+            }
+        }
+    }
+
+    /// Shared implementation behind [`ExchangeClient::connect`] and
+    /// [`Self::subscribe_many`]'s combined-stream dial: opens `ws_url` and hands
+    /// the connection to a supervisor task that keeps it alive (redial with
+    /// jittered exponential backoff, replaying `subscriptions` on each
+    /// reconnect — see [`Self::run_receive_loop`]/[`Self::resubscribe`]). Only
+    /// the first dial is synchronous; this returns once that succeeds.
+    async fn connect_to(&mut self, ws_url: String) -> Result<(), ExchangeError> {
+        info!("Connecting to Binance WebSocket: {}", ws_url);
+
+        self.shutting_down.store(false, Ordering::Relaxed);
+
+        let callbacks = self.callbacks.clone();
+        let depth_sync = self.depth_sync.clone();
+        let http_client = self.http_client.clone();
+        let rest_url = self.config.base.rest_url.clone();
+        let ws_sender = self.ws_sender.clone();
+        let subscriptions = self.subscriptions.clone();
+        let last_message_ts = self.last_message_ts.clone();
+        let connected = self.connected.clone();
+        let shutting_down = self.shutting_down.clone();
+        let initial_backoff = Duration::from_millis(self.config.reconnect_initial_backoff_ms);
+        let max_backoff = Duration::from_millis(self.config.reconnect_max_backoff_ms);
+        let idle_timeout = Duration::from_millis(self.config.idle_timeout_ms);
+
+        let receiver = Self::dial(&ws_url, &ws_sender, &callbacks).await?;
+        connected.store(true, Ordering::Relaxed);
+
+        tokio::spawn(async move {
+            let mut receiver = receiver;
+            let mut backoff = initial_backoff;
+
+            loop {
+                Self::run_receive_loop(
+                    receiver,
+                    &callbacks,
+                    &depth_sync,
+                    &http_client,
+                    &rest_url,
+                    &last_message_ts,
+                    idle_timeout,
+                )
+                .await;
+
+                connected.store(false, Ordering::Relaxed);
+                {
+                    let cbs = callbacks.read().await;
+                    if let Some(ref cb) = cbs.on_disconnected {
+                        cb();
+                    }
+                }
+
+                if shutting_down.load(Ordering::Relaxed) {
+                    info!("Binance connection closed intentionally; reconnect supervisor exiting");
+                    return;
+                }
+
+                receiver = loop {
+                    tokio::time::sleep(Self::jittered(backoff)).await;
+
+                    if shutting_down.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    match Self::dial(&ws_url, &ws_sender, &callbacks).await {
+                        Ok(r) => {
+                            connected.store(true, Ordering::Relaxed);
+                            Self::resubscribe(&ws_sender, &subscriptions.read().await).await;
+                            backoff = initial_backoff;
+                            break r;
+                        }
+                        Err(e) => {
+                            error!("Reconnect to Binance failed: {}", e);
+                            backoff = (backoff * 2).min(max_backoff);
+                        }
+                    }
+                };
+            }
+        });
+
+        info!("Connected to Binance");
+        Ok(())
+    }
+
+    /// Build the URL for Binance's combined-stream endpoint, which multiplexes
+    /// every listed stream over a single socket and wraps each message as
+    /// `{"stream": "<name>", "data": <payload>}` (see
+    /// [`Self::unwrap_combined_envelope`]), instead of the plain `/ws`
+    /// endpoint's one-stream-per-SUBSCRIBE model.
+    fn combined_stream_url(&self, streams: &[String]) -> String {
+        let base = self.config.base.ws_url.trim_end_matches("/ws").trim_end_matches('/');
+        format!("{}/stream?streams={}", base, streams.join("/"))
+    }
+
+    /// Unwrap a combined-stream envelope (`{"stream": "<symbol>@<channel>...",
+    /// "data": <payload>}`) if `raw` is one, returning the inner payload
+    /// together with a canonical event-type string derived from the stream's
+    /// channel suffix (mapped onto the same names the raw `/ws` endpoint's own
+    /// `"e"` field uses, e.g. `@depth@100ms` -> `"depthUpdate"`) so the rest of
+    /// [`Self::run_receive_loop`]'s dispatch doesn't need to care which
+    /// endpoint a message came from. Falls back to `raw["e"]` when `raw` isn't
+    /// enveloped, since the plain `/ws` endpoint never wraps its messages.
+    fn unwrap_combined_envelope(raw: serde_json::Value) -> (serde_json::Value, String) {
+        match raw["stream"].as_str() {
+            Some(stream) => {
+                let channel = stream.split('@').nth(1).unwrap_or("");
+                let event_type = match channel {
+                    "depth" => "depthUpdate".to_string(),
+                    "markPrice" => "markPriceUpdate".to_string(),
+                    other => other.to_string(),
+                };
+                (raw["data"].clone(), event_type)
+            }
+            None => {
+                let event_type = raw["e"].as_str().unwrap_or("").to_string();
+                (raw, event_type)
+            }
+        }
+    }
+
+    /// Drain `receiver` until the connection closes, errors out at the
+    /// stream level, or goes idle for longer than `idle_timeout` — tungstenite's
+    /// automatic pong alone won't catch a half-open socket, so an elapsed
+    /// `tokio::time::timeout` is treated the same as an explicit close.
+    /// Returns once the connection is gone so the caller can redial.
+    async fn run_receive_loop(
+        mut receiver: WsReceiver,
+        callbacks: &Arc<RwLock<ExchangeCallbacks>>,
+        depth_sync: &Arc<RwLock<HashMap<Symbol, DepthSyncState>>>,
+        http_client: &Client,
+        rest_url: &str,
+        last_message_ts: &AtomicI64,
+        idle_timeout: Duration,
+    ) {
+        loop {
+            let msg = match tokio::time::timeout(idle_timeout, receiver.next()).await {
+                Ok(Some(msg)) => msg,
+                Ok(None) => {
+                    warn!("Binance WebSocket stream ended");
+                    return;
+                }
+                Err(_) => {
+                    warn!("No frame from Binance within {:?}; treating connection as dead", idle_timeout);
+                    return;
+                }
+            };
+
+            last_message_ts.store(now_nanos() as i64, Ordering::Relaxed);
+
+            match msg {
+                Ok(Message::Text(text)) => {
+                    if let Ok(raw) = serde_json::from_str::<serde_json::Value>(&text) {
+                        let (data, event_type) = Self::unwrap_combined_envelope(raw);
+
+                        let cbs = callbacks.read().await;
+
+                        match event_type.as_str() {
+                            "bookTicker" => {
+                                if let Some(tick) = Self::parse_ticker(&data) {
+                                    if let Some(ref cb) = cbs.on_tick {
+                                        let symbol = Symbol::new(data["s"].as_str().unwrap_or(""));
+                                        cb(symbol, tick);
+                                    }
+                                }
+                            }
+                            "executionReport" => {
+                                if let Some(order) = Self::parse_order_update(&data) {
+                                    if let Some(ref cb) = cbs.on_order_update {
+                                        cb(order);
+                                    }
+                                }
+                            }
+                            "outboundAccountPosition" => {
+                                if let Some(ref cb) = cbs.on_balance_update {
+                                    for balance in Self::parse_balance_update(&data) {
+                                        cb(balance);
+                                    }
+                                }
+                            }
+                            "markPriceUpdate" => {
+                                if let Some(update) = Self::parse_funding_update(&data) {
+                                    if let Some(ref cb) = cbs.on_funding_rate {
+                                        let symbol = Symbol::new(data["s"].as_str().unwrap_or(""));
+                                        cb(symbol, update);
+                                    }
+                                }
+                            }
+                            "depthUpdate" => {
+                                let symbol = Symbol::new(data["s"].as_str().unwrap_or(""));
+                                let mut sync_map = depth_sync.write().await;
+                                if let Some(state) = sync_map.get_mut(&symbol) {
+                                    match &mut state.sync {
+                                        DepthSync::Buffering(buffered) => {
+                                            buffered.push(data);
+                                        }
+                                        DepthSync::Synced { book, last_u } => {
+                                            let first_u = data["U"].as_u64().unwrap_or(0);
+                                            let final_u = data["u"].as_u64().unwrap_or(0);
+
+                                            if first_u == *last_u + 1 {
+                                                if Self::apply_depth_event(book, &data).is_ok() {
+                                                    *last_u = final_u;
+                                                    if let Some(ref cb) = cbs.on_orderbook {
+                                                        cb(book);
+                                                    }
+                                                } else {
+                                                    warn!("Failed to apply depth diff for {}", symbol);
+                                                }
+                                            } else {
+                                                debug!(
+                                                    "Sequence gap for {} (expected U={}, got U={}); resyncing",
+                                                    symbol, *last_u + 1, first_u
+                                                );
+                                                let limit = state.limit;
+                                                drop(cbs);
+                                                drop(sync_map);
+                                                let http_client = http_client.clone();
+                                                let rest_url = rest_url.to_string();
+                                                let depth_sync = depth_sync.clone();
+                                                let callbacks = callbacks.clone();
+                                                tokio::spawn(async move {
+                                                    Self::resync_depth(
+                                                        &http_client,
+                                                        &rest_url,
+                                                        &symbol,
+                                                        limit,
+                                                        &depth_sync,
+                                                        &callbacks,
+                                                        vec![data],
+                                                    )
+                                                    .await;
+                                                });
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            _ => {
+                                debug!("Unknown event type: {}", event_type);
+                            }
+                        }
+                    }
+                }
+                Ok(Message::Ping(_)) => {
+                    debug!("Received ping");
+                    // Pong is handled automatically by tungstenite
+                }
+                Ok(Message::Close(_)) => {
+                    warn!("WebSocket closed");
+                    return;
+                }
+                Err(e) => {
+                    error!("WebSocket error: {}", e);
+                    let cbs = callbacks.read().await;
+                    if let Some(ref cb) = cbs.on_error {
+                        cb(e.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `POST /api/v3/userDataStream` for a fresh `listenKey`. Only the API
+    /// key header is required — unlike `signed_request`, this endpoint takes
+    /// no HMAC signature.
+    async fn create_listen_key(http_client: &Client, rest_url: &str, api_key: &str) -> Result<String, ExchangeError> {
+        let url = format!("{}/api/v3/userDataStream", rest_url);
+
+        let response = http_client
+            .post(&url)
+            .header("X-MBX-APIKEY", api_key)
+            .send()
+            .await
+            .map_err(|e| ExchangeError::RequestFailed(e.to_string()))?;
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| ExchangeError::ParseError(e.to_string()))?;
+
+        data["listenKey"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| ExchangeError::ParseError("Missing listenKey in response".to_string()))
+    }
+
+    /// `PUT /api/v3/userDataStream?listenKey=...` to extend a `listenKey`'s
+    /// validity by another 60 minutes
+    async fn keepalive_listen_key(
+        http_client: &Client,
+        rest_url: &str,
+        api_key: &str,
+        listen_key: &str,
+    ) -> Result<(), ExchangeError> {
+        let url = format!("{}/api/v3/userDataStream?listenKey={}", rest_url, listen_key);
+
+        http_client
+            .put(&url)
+            .header("X-MBX-APIKEY", api_key)
+            .send()
+            .await
+            .map_err(|e| ExchangeError::RequestFailed(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Spawn a task that PUTs `listen_key` every [`LISTEN_KEY_KEEPALIVE_INTERVAL`]
+    /// until `subscribe_user_stream` disconnects (`shutting_down`) or moves on
+    /// to a newer key (`generation` no longer matches `my_generation`),
+    /// whichever comes first
+    fn spawn_listen_key_keepalive(
+        http_client: Client,
+        rest_url: String,
+        api_key: String,
+        listen_key: String,
+        generation: Arc<AtomicU64>,
+        my_generation: u64,
+        shutting_down: Arc<AtomicBool>,
+    ) {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(LISTEN_KEY_KEEPALIVE_INTERVAL).await;
+
+                if shutting_down.load(Ordering::Relaxed) || generation.load(Ordering::Relaxed) != my_generation {
+                    return;
+                }
+
+                if let Err(e) = Self::keepalive_listen_key(&http_client, &rest_url, &api_key, &listen_key).await {
+                    error!("Failed to renew Binance listenKey: {}", e);
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl ExchangeClient for BinanceClient {
+    fn name(&self) -> &str {
+        "binance"
+    }
+
+    /// Connects and hands the connection to a supervisor task that keeps it
+    /// alive: on close, stream error, or `BinanceConfig::idle_timeout_ms` of
+    /// silence (see [`Self::run_receive_loop`]), it redials with exponential
+    /// backoff (doubling from `reconnect_initial_backoff_ms` up to
+    /// `reconnect_max_backoff_ms`, jittered — see [`Self::jittered`]),
+    /// replays every stream registered via `subscribe_*` (see
+    /// [`Self::resubscribe`]), and fires `on_connected`/`on_disconnected`
+    /// across the transition. Only the first dial is synchronous; `connect()`
+    /// returns once that succeeds; `disconnect()` stops the supervisor.
+    async fn connect(&mut self) -> Result<(), ExchangeError> {
+        let ws_url = self.config.base.ws_url.clone();
+        self.connect_to(ws_url).await
+    }
+
+    async fn disconnect(&mut self) -> Result<(), ExchangeError> {
+        self.shutting_down.store(true, Ordering::Relaxed);
+        self.connected.store(false, Ordering::Relaxed);
+
+        if let Some(mut sender) = self.ws_sender.write().await.take() {
+            let _ = sender.close().await;
+        }
+
+        info!("Disconnected from Binance");
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    async fn subscribe_ticker(&mut self, symbol: &Symbol) -> Result<(), ExchangeError> {
+        let stream = format!("{}@bookTicker", symbol.as_str().to_lowercase());
+        let subscribe_msg = serde_json::json!({
+            "method": "SUBSCRIBE",
+            "params": [stream],
+            "id": 1
+        });
+
+        if let Some(ref mut sender) = *self.ws_sender.write().await {
+            sender
+                .send(Message::Text(subscribe_msg.to_string()))
+                .await
+                .map_err(|e| ExchangeError::WebSocketError(e.to_string()))?;
+        }
+        self.subscriptions.write().await.push(stream);
+
+        info!("Subscribed to ticker: {}", symbol);
+        Ok(())
+    }
+
+    /// Subscribes to Binance's raw diff-depth stream (`<symbol>@depth@100ms`)
+    /// and maintains a consistent local book from it, per the documented
+    /// snapshot+diff procedure: a REST snapshot (`limit=depth`) is fetched in
+    /// the background while incoming diffs are buffered, then reconciled
+    /// against the snapshot (see [`Self::reconcile_snapshot`]) and applied
+    /// going forward (see [`Self::resync_depth`]). Any sequence gap
+    /// thereafter drops the book and restarts the whole sequence. The
+    /// synchronized [`OrderBook`] is surfaced via `ExchangeCallbacks::on_orderbook`,
+    /// fired only once fully in sync and on every applied diff after that.
+    async fn subscribe_orderbook(&mut self, symbol: &Symbol, depth: u32) -> Result<(), ExchangeError> {
+        let stream = format!("{}@depth@100ms", symbol.as_str().to_lowercase());
+        let subscribe_msg = serde_json::json!({
+            "method": "SUBSCRIBE",
+            "params": [stream],
+            "id": 2
+        });
+
+        if let Some(ref mut sender) = *self.ws_sender.write().await {
+            sender
+                .send(Message::Text(subscribe_msg.to_string()))
+                .await
+                .map_err(|e| ExchangeError::WebSocketError(e.to_string()))?;
+        }
+        self.subscriptions.write().await.push(stream);
+
+        let limit = depth.max(1);
+        info!("Subscribed to orderbook: {} limit={}", symbol, limit);
+
+        let http_client = self.http_client.clone();
+        let rest_url = self.config.base.rest_url.clone();
+        let depth_sync = self.depth_sync.clone();
+        let callbacks = self.callbacks.clone();
+        let symbol = symbol.clone();
+        tokio::spawn(async move {
+            Self::resync_depth(&http_client, &rest_url, &symbol, limit, &depth_sync, &callbacks, Vec::new()).await;
+        });
+
+        Ok(())
+    }
+
+    async fn subscribe_trades(&mut self, symbol: &Symbol) -> Result<(), ExchangeError> {
+        let stream = format!("{}@trade", symbol.as_str().to_lowercase());
+        let subscribe_msg = serde_json::json!({
+            "method": "SUBSCRIBE",
+            "params": [stream],
+            "id": 3
+        });
+
+        if let Some(ref mut sender) = *self.ws_sender.write().await {
+            sender
+                .send(Message::Text(subscribe_msg.to_string()))
+                .await
+                .map_err(|e| ExchangeError::WebSocketError(e.to_string()))?;
+        }
+        self.subscriptions.write().await.push(stream);
+
+        info!("Subscribed to trades: {}", symbol);
+        Ok(())
+    }
+
+    /// Subscribes to Binance Futures' `<symbol>@markPrice@1s` stream,
+    /// surfacing each `markPriceUpdate` via `ExchangeCallbacks::on_funding_rate`.
+    /// Binance publishes this stream on spot symbols too, but its payload
+    /// (and the funding it reports) is only meaningful for futures.
+    async fn subscribe_funding_rate(&mut self, symbol: &Symbol) -> Result<(), ExchangeError> {
+        let stream = format!("{}@markPrice@1s", symbol.as_str().to_lowercase());
+        let subscribe_msg = serde_json::json!({
+            "method": "SUBSCRIBE",
+            "params": [stream],
+            "id": 4
+        });
+
+        if let Some(ref mut sender) = *self.ws_sender.write().await {
+            sender
+                .send(Message::Text(subscribe_msg.to_string()))
+                .await
+                .map_err(|e| ExchangeError::WebSocketError(e.to_string()))?;
+        }
+        self.subscriptions.write().await.push(stream);
+
+        info!("Subscribed to funding rate: {}", symbol);
+        Ok(())
+    }
+
+    /// Batches every `(symbol, channel)` combination into the stream names
+    /// Binance's combined endpoint expects (`<symbol>@<channel>`), then either:
+    /// - dials straight into `/stream?streams=a@.../b@.../...` (see
+    ///   [`Self::combined_stream_url`]) if not yet connected, so hundreds of
+    ///   symbols share one socket and one supervisor task from the start; or
+    /// - if already connected, sends a single `SUBSCRIBE` with every stream
+    ///   name as one `params` array, instead of one round-trip per symbol.
+    ///
+    /// Orderbook channels still get their own `resync_depth` task per symbol,
+    /// same as [`Self::subscribe_orderbook`].
+    async fn subscribe_many(&mut self, symbols: &[Symbol], channels: &[StreamChannel]) -> Result<(), ExchangeError> {
+        let mut streams = Vec::with_capacity(symbols.len() * channels.len());
+        let mut orderbook_subs = Vec::new();
+
+        for symbol in symbols {
+            for channel in channels {
+                let stream = match channel {
+                    StreamChannel::Ticker => format!("{}@bookTicker", symbol.as_str().to_lowercase()),
+                    StreamChannel::Orderbook { depth } => {
+                        orderbook_subs.push((symbol.clone(), (*depth).max(1)));
+                        format!("{}@depth@100ms", symbol.as_str().to_lowercase())
+                    }
+                    StreamChannel::Trades => format!("{}@trade", symbol.as_str().to_lowercase()),
+                    StreamChannel::FundingRate => format!("{}@markPrice@1s", symbol.as_str().to_lowercase()),
+                };
+                streams.push(stream);
+            }
+        }
+
+        if streams.is_empty() {
+            return Ok(());
+        }
+
+        if self.is_connected() {
+            let subscribe_msg = serde_json::json!({
+                "method": "SUBSCRIBE",
+                "params": streams,
+                "id": 4,
+            });
+            if let Some(ref mut sender) = *self.ws_sender.write().await {
+                sender
+                    .send(Message::Text(subscribe_msg.to_string()))
+                    .await
+                    .map_err(|e| ExchangeError::WebSocketError(e.to_string()))?;
+            }
+            self.subscriptions.write().await.extend(streams.iter().cloned());
+        } else {
+            let url = self.combined_stream_url(&streams);
+            self.connect_to(url).await?;
+        }
+
+        info!("Subscribed to {} combined streams across {} symbols", streams.len(), symbols.len());
+
+        for (symbol, limit) in orderbook_subs {
+            let http_client = self.http_client.clone();
+            let rest_url = self.config.base.rest_url.clone();
+            let depth_sync = self.depth_sync.clone();
+            let callbacks = self.callbacks.clone();
+            tokio::spawn(async move {
+                Self::resync_depth(&http_client, &rest_url, &symbol, limit, &depth_sync, &callbacks, Vec::new()).await;
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn send_order(&self, request: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        let result = self.place_order(&request, false).await?;
+
+        Ok(OrderResponse {
+            success: true,
+            order_id: result["orderId"].as_u64().unwrap_or(0),
+            client_order_id: result["clientOrderId"].as_str().unwrap_or("").to_string(),
+            status: OrderStatus::New,
+            error_message: None,
+        })
+    }
+
+    /// Validate via Binance's order-test endpoint (`/api/v3/order/test` or
+    /// `/fapi/v1/order/test`), which checks the request against matching-engine
+    /// rules and returns an empty body on success, but never rests or fills
+    /// anything. On rejection, surfaces the symbol-specific reason through
+    /// `on_error` in addition to returning it, since a `--validate-orders` run
+    /// has no fill/reject path of its own to report through.
+    async fn test_order(&self, request: OrderRequest) -> Result<(), ExchangeError> {
+        let symbol = request.symbol.clone();
+        if let Err(e) = self.place_order(&request, true).await {
+            let cbs = self.callbacks.read().await;
+            if let Some(ref cb) = cbs.on_error {
+                cb(format!("Order validation rejected for {}: {}", symbol, e));
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn cancel_order(&self, symbol: &Symbol, order_id: OrderId) -> Result<CancelResponse, ExchangeError> {
+        let order_id_str = order_id.to_string();
+        let params = vec![
+            ("symbol", symbol.as_str()),
+            ("orderId", &order_id_str),
+        ];
+
+        let _ = self
+            .signed_request(reqwest::Method::DELETE, "/api/v3/order", &params)
+            .await?;
+
+        Ok(CancelResponse {
+            success: true,
+            order_id,
+            error_message: None,
+        })
+    }
+
+    async fn cancel_all_orders(&self, symbol: &Symbol) -> Result<(), ExchangeError> {
+        let params = vec![("symbol", symbol.as_str())];
+
+        self.signed_request(reqwest::Method::DELETE, "/api/v3/openOrders", &params)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<f64, ExchangeError> {
+        let result = self
+            .signed_request(reqwest::Method::GET, "/api/v3/account", &[])
+            .await?;
+
+        if let Some(balances) = result["balances"].as_array() {
+            for balance in balances {
+                if balance["asset"].as_str() == Some(asset) {
+                    return balance["free"]
+                        .as_str()
+                        .and_then(|s| s.parse().ok())
+                        .ok_or_else(|| ExchangeError::ParseError("Invalid balance".to_string()));
+                }
+            }
+        }
+
+        Ok(0.0)
+    }
+
+    async fn get_open_orders(&self, symbol: &Symbol) -> Result<Vec<Order>, ExchangeError> {
+        let params = vec![("symbol", symbol.as_str())];
+
+        let result = self
+            .signed_request(reqwest::Method::GET, "/api/v3/openOrders", &params)
+            .await?;
+
+        let orders = result
+            .as_array()
+            .ok_or_else(|| ExchangeError::ParseError("Expected array".to_string()))?
+            .iter()
+            .filter_map(Self::parse_order_update)
+            .collect();
+
+        Ok(orders)
+    }
+
+    fn set_callbacks(&mut self, callbacks: ExchangeCallbacks) {
+        let cbs = self.callbacks.clone();
+        tokio::spawn(async move {
+            *cbs.write().await = callbacks;
+        });
+    }
+
+    async fn server_time(&self) -> Result<Timestamp, ExchangeError> {
+        let result = self.public_request("/api/v3/time").await?;
+        result["serverTime"]
+            .as_u64()
+            .map(|t| t * 1_000_000)
+            .ok_or_else(|| ExchangeError::ParseError("Invalid server time".to_string()))
+    }
+
+    /// Exposes the same REST snapshot the internal `DepthSync` state machine
+    /// warms up from, so a caller (e.g. `main`'s startup warmup step) can
+    /// seed its own view of the book before the websocket diff stream is
+    /// trusted.
+    async fn fetch_depth_snapshot(
+        &self,
+        symbol: &Symbol,
+        limit: u32,
+    ) -> Result<(u64, Vec<(Price, Quantity)>, Vec<(Price, Quantity)>), ExchangeError> {
+        Self::fetch_depth_snapshot(&self.http_client, &self.config.base.rest_url, symbol, limit).await
+    }
+}
+
+impl BinanceClient {
+    /// Shared by [`ExchangeClient::send_order`] and [`ExchangeClient::test_order`]:
+    /// builds the same request params either way, only the endpoint differs
+    /// (`.../order` rests it for real, `.../order/test` just validates it).
+    async fn place_order(&self, request: &OrderRequest, test: bool) -> Result<serde_json::Value, ExchangeError> {
+        let side = match request.side {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        };
+
+        let order_type = match request.order_type {
+            OrderType::Limit => "LIMIT",
+            OrderType::Market => "MARKET",
+            OrderType::LimitMaker => "LIMIT_MAKER",
+            _ => "LIMIT",
+        };
+
+        let tif = match request.time_in_force {
+            TimeInForce::Gtc => "GTC",
+            TimeInForce::Ioc => "IOC",
+            TimeInForce::Fok => "FOK",
+            TimeInForce::Gtx => "GTX",
+            TimeInForce::Gtd => "GTC", // Binance has no native GTD; expiry is enforced by our own OMS/book
+        };
+
+        let price_str = from_price(request.price).to_string();
+        let qty_str = from_qty(request.quantity).to_string();
+        let stop_price_str = request.stop_price.map(from_price).map(|p| p.to_string());
+        let activation_price_str = request.activation_price.map(from_price).map(|p| p.to_string());
+        let callback_rate_str = request.callback_rate.map(|r| r.to_string());
+
+        let mut params = vec![
+            ("symbol", request.symbol.as_str()),
+            ("side", side),
+            ("type", order_type),
+            ("timeInForce", tif),
+            ("price", &price_str),
+            ("quantity", &qty_str),
+        ];
+
+        // Futures-only fields: spot orders don't carry reduce-only/hedge-mode/
+        // stop-trigger semantics, so only thread them through for futures.
+        if self.config.use_futures {
+            if request.reduce_only {
+                params.push(("reduceOnly", "true"));
+            }
+            if request.close_position {
+                params.push(("closePosition", "true"));
+            }
+            if let Some(ref stop_price) = stop_price_str {
+                params.push(("stopPrice", stop_price));
+            }
+            if let Some(ref activation_price) = activation_price_str {
+                params.push(("activationPrice", activation_price));
+            }
+            if let Some(ref callback_rate) = callback_rate_str {
+                params.push(("callbackRate", callback_rate));
+            }
+            if let Some(position_side) = request.position_side {
+                params.push(("positionSide", match position_side {
+                    PositionSide::Both => "BOTH",
+                    PositionSide::Long => "LONG",
+                    PositionSide::Short => "SHORT",
+                }));
+            }
+            params.push(("workingType", match request.working_type {
+                WorkingType::MarkPrice => "MARK_PRICE",
+                WorkingType::ContractPrice => "CONTRACT_PRICE",
+            }));
+        }
+
+        let base_endpoint = if self.config.use_futures { "/fapi/v1/order" } else { "/api/v3/order" };
+        let endpoint = if test { format!("{}/test", base_endpoint) } else { base_endpoint.to_string() };
+
+        self.signed_request(reqwest::Method::POST, &endpoint, &params).await
+    }
+
+    /// Opens Binance's authenticated user data stream: obtains a `listenKey`
+    /// via `POST /api/v3/userDataStream`, connects to `<ws_url>/<listenKey>`,
+    /// and spawns a task that `PUT`s the same endpoint every
+    /// [`LISTEN_KEY_KEEPALIVE_INTERVAL`] so it doesn't expire.
+    /// `executionReport`/`outboundAccountPosition` events arrive on this
+    /// socket and route into `on_order_update`/`on_balance_update` via the
+    /// same [`Self::run_receive_loop`] the market-data socket uses. Separate
+    /// from `ws_sender`/`subscriptions`/`shutting_down` so a drop here never
+    /// touches the market-data connection. A disconnect gets a fresh
+    /// `listenKey` and a freshly re-dialed stream, since the old key may have
+    /// expired along with the connection.
+    pub async fn subscribe_user_stream(&mut self) -> Result<(), ExchangeError> {
+        let listen_key = Self::create_listen_key(&self.http_client, &self.config.base.rest_url, &self.config.base.api_key).await?;
+
+        self.user_stream_shutting_down.store(false, Ordering::Relaxed);
+        let generation = self.listen_key_generation.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let callbacks = self.callbacks.clone();
+        let depth_sync = self.depth_sync.clone();
+        let http_client = self.http_client.clone();
+        let rest_url = self.config.base.rest_url.clone();
+        let ws_base = self.config.base.ws_url.clone();
+        let api_key = self.config.base.api_key.clone();
+        let user_stream_sender = self.user_stream_sender.clone();
+        let last_message_ts = self.last_message_ts.clone();
+        let shutting_down = self.user_stream_shutting_down.clone();
+        let listen_key_generation = self.listen_key_generation.clone();
+        let initial_backoff = Duration::from_millis(self.config.reconnect_initial_backoff_ms);
+        let max_backoff = Duration::from_millis(self.config.reconnect_max_backoff_ms);
+        let idle_timeout = Duration::from_millis(self.config.idle_timeout_ms);
+
+        let ws_url = format!("{}/{}", ws_base, listen_key);
+        let receiver = Self::dial(&ws_url, &user_stream_sender, &callbacks).await?;
+
+        Self::spawn_listen_key_keepalive(
+            http_client.clone(),
+            rest_url.clone(),
+            api_key.clone(),
+            listen_key,
+            listen_key_generation.clone(),
+            generation,
+            shutting_down.clone(),
+        );
+
+        tokio::spawn(async move {
+            let mut receiver = receiver;
+            let mut backoff = initial_backoff;
+
+            loop {
+                Self::run_receive_loop(
+                    receiver,
+                    &callbacks,
+                    &depth_sync,
+                    &http_client,
+                    &rest_url,
+                    &last_message_ts,
+                    idle_timeout,
+                )
+                .await;
+
+                {
+                    let cbs = callbacks.read().await;
+                    if let Some(ref cb) = cbs.on_disconnected {
+                        cb();
+                    }
+                }
+
+                if shutting_down.load(Ordering::Relaxed) {
+                    info!("Binance user data stream closed intentionally; supervisor exiting");
+                    return;
+                }
+
+                receiver = loop {
+                    tokio::time::sleep(Self::jittered(backoff)).await;
+
+                    if shutting_down.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    let fresh_key = match Self::create_listen_key(&http_client, &rest_url, &api_key).await {
+                        Ok(key) => key,
+                        Err(e) => {
+                            error!("Failed to refresh Binance listenKey: {}", e);
+                            backoff = (backoff * 2).min(max_backoff);
+                            continue;
+                        }
+                    };
+
+                    let ws_url = format!("{}/{}", ws_base, fresh_key);
+                    match Self::dial(&ws_url, &user_stream_sender, &callbacks).await {
+                        Ok(r) => {
+                            let generation = listen_key_generation.fetch_add(1, Ordering::Relaxed) + 1;
+                            Self::spawn_listen_key_keepalive(
+                                http_client.clone(),
+                                rest_url.clone(),
+                                api_key.clone(),
+                                fresh_key,
+                                listen_key_generation.clone(),
+                                generation,
+                                shutting_down.clone(),
+                            );
+                            backoff = initial_backoff;
+                            break r;
+                        }
+                        Err(e) => {
+                            error!("Reconnect to Binance user data stream failed: {}", e);
+                            backoff = (backoff * 2).min(max_backoff);
+                        }
+                    }
+                };
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! Exchange-agnostic client construction keyed on `ExchangeConfig::name`.
+//!
+//! `main` used to hard-code `BinanceClient`, so driving a second venue meant
+//! editing `main` itself. [`ExchangeClientFactory::build`] lets the same
+//! binary drive Binance, Binance Futures, or Kraken purely from config, with
+//! callers only ever talking to the [`ExchangeClient`] trait object.
+
+use crate::exchange::{binance, kraken, ExchangeClient, ExchangeConfig, ExchangeError};
+
+pub struct ExchangeClientFactory;
+
+impl ExchangeClientFactory {
+    /// Construct the right [`ExchangeClient`] for `exchange_config.name`.
+    /// `paper_trading` selects each exchange's testnet variant where one
+    /// exists, carrying over the configured API credentials.
+    pub fn build(
+        exchange_config: &ExchangeConfig,
+        paper_trading: bool,
+    ) -> Result<Box<dyn ExchangeClient>, ExchangeError> {
+        match exchange_config.name.as_str() {
+            "binance" => {
+                let config = if paper_trading {
+                    let mut cfg = binance::BinanceConfig::testnet();
+                    cfg.base.api_key = exchange_config.api_key.clone();
+                    cfg.base.api_secret = exchange_config.api_secret.clone();
+                    cfg
+                } else {
+                    binance::BinanceConfig {
+                        base: exchange_config.clone(),
+                        use_futures: false,
+                        ..Default::default()
+                    }
+                };
+                Ok(Box::new(binance::BinanceClient::new(config)))
+            }
+            "binance_futures" => {
+                let config = if paper_trading {
+                    let mut cfg = binance::BinanceConfig::futures_testnet();
+                    cfg.base.api_key = exchange_config.api_key.clone();
+                    cfg.base.api_secret = exchange_config.api_secret.clone();
+                    cfg
+                } else {
+                    binance::BinanceConfig {
+                        base: exchange_config.clone(),
+                        use_futures: true,
+                        ..Default::default()
+                    }
+                };
+                Ok(Box::new(binance::BinanceClient::new(config)))
+            }
+            "kraken" => {
+                let config = kraken::KrakenConfig { base: exchange_config.clone() };
+                Ok(Box::new(kraken::KrakenClient::new(config)))
+            }
+            other => Err(ExchangeError::ConnectionFailed(format!("unknown exchange: {}", other))),
+        }
+    }
+}
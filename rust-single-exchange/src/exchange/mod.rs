@@ -0,0 +1,469 @@
+//! Exchange client module
+
+use crate::core::types::*;
+use crate::orderbook::OrderBook;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub mod binance;
+pub mod factory;
+pub mod historical_replay;
+pub mod kraken;
+pub mod simulated;
+
+pub use binance::BinanceClient;
+pub use factory::ExchangeClientFactory;
+pub use historical_replay::HistoricalReplayClient;
+pub use kraken::KrakenClient;
+pub use simulated::SimulatedExchange;
+
+/// A structured error payload exactly as an exchange returns it over the
+/// wire, e.g. Binance's `{"code":-2010,"msg":"..."}`. Kept separate from
+/// [`ExchangeError`] so a raw exchange string never leaks into strategy
+/// logic: parse the REST/WS error body into this first, then convert into
+/// an [`ExchangeError`] (via [`ExchangeError::from_wire`]) to attach request
+/// context and a stable retry classification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WireError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl WireError {
+    /// Parse a Binance-style `{"code": ..., "msg": "..."}` error body
+    pub fn from_binance_json(value: &serde_json::Value) -> Option<Self> {
+        Some(WireError {
+            code: value["code"].as_i64()?,
+            message: value["msg"].as_str().unwrap_or_default().to_string(),
+        })
+    }
+}
+
+impl From<WireError> for ExchangeError {
+    fn from(wire: WireError) -> Self {
+        ExchangeError::Remote {
+            code: wire.code,
+            message: wire.message,
+            endpoint: None,
+            request_id: None,
+        }
+    }
+}
+
+/// How a failed request should be handled by reconnect/backoff code, without
+/// branching on exchange-specific error strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Transient; safe to retry with backoff (rate limits, timeouts, dropped connections)
+    Retryable,
+    /// Credentials/permissions issue; retrying without operator action won't help
+    Auth,
+    /// The request itself is invalid (bad params, rejected order); retrying as-is won't help
+    Fatal,
+}
+
+/// Binance error codes that are safe to retry rather than treat as fatal.
+/// See <https://binance-docs.github.io/apidocs/spot/en/#error-codes>.
+const BINANCE_RETRYABLE_CODES: &[i64] = &[-1003, -1006, -1007, -1021];
+/// Binance error codes indicating an auth/permissions problem
+const BINANCE_AUTH_CODES: &[i64] = &[-1002, -2014, -2015];
+
+/// Exchange errors
+#[derive(Debug, Error)]
+pub enum ExchangeError {
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(String),
+
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
+    #[error("Request failed: {0}")]
+    RequestFailed(String),
+
+    #[error("Rate limit exceeded")]
+    RateLimitExceeded,
+
+    #[error("Order rejected: {0}")]
+    OrderRejected(String),
+
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    #[error("WebSocket error: {0}")]
+    WebSocketError(String),
+
+    #[error("Timeout")]
+    Timeout,
+
+    /// A structured error the exchange itself returned, with request context
+    /// attached for logging/metrics
+    #[error("Remote error {code} on {endpoint:?} (request {request_id:?}): {message}")]
+    Remote {
+        code: i64,
+        message: String,
+        endpoint: Option<String>,
+        request_id: Option<String>,
+    },
+}
+
+impl ExchangeError {
+    /// Convert a parsed wire error into an [`ExchangeError::Remote`], attaching
+    /// the endpoint and request id for logging/metrics
+    pub fn from_wire(wire: WireError, endpoint: impl Into<String>, request_id: Option<String>) -> Self {
+        ExchangeError::Remote {
+            code: wire.code,
+            message: wire.message,
+            endpoint: Some(endpoint.into()),
+            request_id,
+        }
+    }
+
+    /// Stable retry classification, so reconnect/backoff code can branch on
+    /// a typed category instead of matching exchange-specific strings
+    pub fn classify(&self) -> ErrorClass {
+        match self {
+            ExchangeError::ConnectionFailed(_) | ExchangeError::Timeout | ExchangeError::RateLimitExceeded => {
+                ErrorClass::Retryable
+            }
+            ExchangeError::AuthenticationFailed(_) => ErrorClass::Auth,
+            ExchangeError::Remote { code, .. } => {
+                if BINANCE_RETRYABLE_CODES.contains(code) {
+                    ErrorClass::Retryable
+                } else if BINANCE_AUTH_CODES.contains(code) {
+                    ErrorClass::Auth
+                } else {
+                    ErrorClass::Fatal
+                }
+            }
+            ExchangeError::RequestFailed(_)
+            | ExchangeError::OrderRejected(_)
+            | ExchangeError::ParseError(_)
+            | ExchangeError::WebSocketError(_) => ErrorClass::Fatal,
+        }
+    }
+}
+
+/// Exchange configuration
+#[derive(Debug, Clone)]
+pub struct ExchangeConfig {
+    pub name: String,
+    pub rest_url: String,
+    pub ws_url: String,
+    pub api_key: String,
+    pub api_secret: String,
+    pub passphrase: Option<String>,
+
+    pub connect_timeout_ms: u64,
+    pub read_timeout_ms: u64,
+    pub max_requests_per_second: u32,
+    pub testnet: bool,
+}
+
+impl Default for ExchangeConfig {
+    fn default() -> Self {
+        ExchangeConfig {
+            name: "binance".to_string(),
+            rest_url: "https://api.binance.com".to_string(),
+            ws_url: "wss://stream.binance.com:9443/ws".to_string(),
+            api_key: String::new(),
+            api_secret: String::new(),
+            passphrase: None,
+            connect_timeout_ms: 5000,
+            read_timeout_ms: 1000,
+            max_requests_per_second: 10,
+            testnet: false,
+        }
+    }
+}
+
+/// Which side of a hedge-mode futures position an order applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionSide {
+    Both,
+    Long,
+    Short,
+}
+
+/// What price a stop/trailing trigger watches, as exchanges like Binance
+/// futures let a caller choose
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkingType {
+    MarkPrice,
+    ContractPrice,
+}
+
+/// Order request
+#[derive(Debug, Clone)]
+pub struct OrderRequest {
+    pub symbol: Symbol,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub time_in_force: TimeInForce,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub client_order_id: Option<String>,
+
+    /// Only allowed to reduce an existing position, never open/flip one
+    pub reduce_only: bool,
+    /// Closes the entire position on trigger, ignoring `quantity`
+    pub close_position: bool,
+    /// Trigger price for stop/take-profit orders
+    pub stop_price: Option<Price>,
+    /// Trigger price a trailing-stop's callback rate activates from
+    pub activation_price: Option<Price>,
+    /// Trailing-stop callback, as a percent of the activation price
+    pub callback_rate: Option<f64>,
+    /// Which side of a hedge-mode position this order applies to
+    pub position_side: Option<PositionSide>,
+    /// Which price a stop/trailing trigger watches
+    pub working_type: WorkingType,
+}
+
+impl OrderRequest {
+    fn new(
+        symbol: Symbol,
+        side: Side,
+        order_type: OrderType,
+        time_in_force: TimeInForce,
+        price: Price,
+        quantity: Quantity,
+    ) -> Self {
+        OrderRequest {
+            symbol,
+            side,
+            order_type,
+            time_in_force,
+            price,
+            quantity,
+            client_order_id: None,
+            reduce_only: false,
+            close_position: false,
+            stop_price: None,
+            activation_price: None,
+            callback_rate: None,
+            position_side: None,
+            working_type: WorkingType::ContractPrice,
+        }
+    }
+
+    pub fn limit_buy(symbol: Symbol, price: Price, quantity: Quantity) -> Self {
+        Self::new(symbol, Side::Buy, OrderType::Limit, TimeInForce::Gtc, price, quantity)
+    }
+
+    pub fn limit_sell(symbol: Symbol, price: Price, quantity: Quantity) -> Self {
+        Self::new(symbol, Side::Sell, OrderType::Limit, TimeInForce::Gtc, price, quantity)
+    }
+
+    pub fn market_buy(symbol: Symbol, quantity: Quantity) -> Self {
+        Self::new(symbol, Side::Buy, OrderType::Market, TimeInForce::Gtc, 0, quantity)
+    }
+
+    pub fn market_sell(symbol: Symbol, quantity: Quantity) -> Self {
+        Self::new(symbol, Side::Sell, OrderType::Market, TimeInForce::Gtc, 0, quantity)
+    }
+
+    /// A stop-market order: rests untriggered until the mark/contract price
+    /// (per `working_type`) crosses `stop_price`, then sends as a market order
+    pub fn stop_market(symbol: Symbol, side: Side, stop_price: Price, quantity: Quantity) -> Self {
+        let mut request = Self::new(symbol, side, OrderType::Market, TimeInForce::Gtc, 0, quantity);
+        request.stop_price = Some(stop_price);
+        request
+    }
+}
+
+/// Order response
+#[derive(Debug, Clone)]
+pub struct OrderResponse {
+    pub success: bool,
+    pub order_id: OrderId,
+    pub client_order_id: String,
+    pub status: OrderStatus,
+    pub error_message: Option<String>,
+}
+
+/// Cancel response
+#[derive(Debug, Clone)]
+pub struct CancelResponse {
+    pub success: bool,
+    pub order_id: OrderId,
+    pub error_message: Option<String>,
+}
+
+/// A market-data channel a symbol can be subscribed to, as used by
+/// [`ExchangeClient::subscribe_many`] to batch a whole universe of
+/// subscriptions into one call
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamChannel {
+    Ticker,
+    Orderbook { depth: u32 },
+    Trades,
+    /// Mark-price/funding-rate stream; only meaningful on futures venues, see
+    /// [`ExchangeClient::subscribe_funding_rate`]
+    FundingRate,
+}
+
+/// Exchange callbacks
+pub struct ExchangeCallbacks {
+    /// Fired with the symbol a tick belongs to alongside the tick itself, so
+    /// one callback registered on a single connection can still tell apart
+    /// ticks from multiple subscribed symbols (see
+    /// `subscribe_many`/`EngineBuilder` callers routing by symbol to
+    /// independent `TradingEngine`s).
+    pub on_tick: Option<Box<dyn Fn(Symbol, Tick) + Send + Sync>>,
+    pub on_order_update: Option<Box<dyn Fn(Order) + Send + Sync>>,
+    pub on_trade: Option<Box<dyn Fn(Trade) + Send + Sync>>,
+    pub on_error: Option<Box<dyn Fn(String) + Send + Sync>>,
+    pub on_connected: Option<Box<dyn Fn() + Send + Sync>>,
+    pub on_disconnected: Option<Box<dyn Fn() + Send + Sync>>,
+    /// Fired with the managed local book once a depth subscription is fully
+    /// in sync (see [`binance::BinanceClient::subscribe_orderbook`]), and on
+    /// every subsequent applied diff. Takes a reference rather than an owned
+    /// [`OrderBook`] since the book isn't cheap to clone.
+    pub on_orderbook: Option<Box<dyn Fn(&OrderBook) + Send + Sync>>,
+    /// Fired once per asset on an account balance update from the
+    /// authenticated user data stream (see
+    /// [`binance::BinanceClient::subscribe_user_stream`])
+    pub on_balance_update: Option<Box<dyn Fn(Balance) + Send + Sync>>,
+    /// Fired with the symbol a mark-price/funding-rate update belongs to
+    /// alongside the update itself; see [`ExchangeClient::subscribe_funding_rate`]
+    pub on_funding_rate: Option<Box<dyn Fn(Symbol, FundingUpdate) + Send + Sync>>,
+}
+
+impl Default for ExchangeCallbacks {
+    fn default() -> Self {
+        ExchangeCallbacks {
+            on_tick: None,
+            on_order_update: None,
+            on_trade: None,
+            on_error: None,
+            on_connected: None,
+            on_disconnected: None,
+            on_orderbook: None,
+            on_balance_update: None,
+            on_funding_rate: None,
+        }
+    }
+}
+
+/// Exchange client trait
+#[async_trait]
+pub trait ExchangeClient: Send + Sync {
+    /// Get exchange name
+    fn name(&self) -> &str;
+
+    /// Connect to exchange
+    async fn connect(&mut self) -> Result<(), ExchangeError>;
+
+    /// Disconnect from exchange
+    async fn disconnect(&mut self) -> Result<(), ExchangeError>;
+
+    /// Check if connected
+    fn is_connected(&self) -> bool;
+
+    /// Subscribe to ticker
+    async fn subscribe_ticker(&mut self, symbol: &Symbol) -> Result<(), ExchangeError>;
+
+    /// Subscribe to orderbook
+    async fn subscribe_orderbook(&mut self, symbol: &Symbol, depth: u32) -> Result<(), ExchangeError>;
+
+    /// Subscribe to trades
+    async fn subscribe_trades(&mut self, symbol: &Symbol) -> Result<(), ExchangeError>;
+
+    /// Subscribe to `symbol`'s mark-price/funding-rate stream, surfaced via
+    /// [`ExchangeCallbacks::on_funding_rate`] rather than `on_tick`, since its
+    /// payload (funding rate, next funding time) shares nothing with
+    /// [`Tick`]'s best-bid/ask shape.
+    ///
+    /// The default implementation reports the venue as unsupported; override
+    /// it for exchanges (futures venues) that expose one.
+    async fn subscribe_funding_rate(&mut self, symbol: &Symbol) -> Result<(), ExchangeError> {
+        let _ = symbol;
+        Err(ExchangeError::RequestFailed(format!(
+            "{} does not support funding-rate subscriptions",
+            self.name()
+        )))
+    }
+
+    /// Subscribe to every `(symbol, channel)` combination in `symbols` x `channels`
+    /// in as few round-trips as the exchange allows, instead of the caller issuing
+    /// `symbols.len() * channels.len()` separate `subscribe_*` awaits. Useful for
+    /// registering a whole trading universe at once (e.g. an arbitrage engine
+    /// watching hundreds of symbols across venues).
+    ///
+    /// The default implementation just falls back to one `subscribe_*` call per
+    /// combination; exchanges with genuine wire-level multiplexing (see
+    /// [`binance::BinanceClient::subscribe_many`]) should override it.
+    async fn subscribe_many(&mut self, symbols: &[Symbol], channels: &[StreamChannel]) -> Result<(), ExchangeError> {
+        for symbol in symbols {
+            for channel in channels {
+                match channel {
+                    StreamChannel::Ticker => self.subscribe_ticker(symbol).await?,
+                    StreamChannel::Orderbook { depth } => self.subscribe_orderbook(symbol, *depth).await?,
+                    StreamChannel::Trades => self.subscribe_trades(symbol).await?,
+                    StreamChannel::FundingRate => self.subscribe_funding_rate(symbol).await?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Send order
+    async fn send_order(&self, request: OrderRequest) -> Result<OrderResponse, ExchangeError>;
+
+    /// Cancel order
+    async fn cancel_order(&self, symbol: &Symbol, order_id: OrderId) -> Result<CancelResponse, ExchangeError>;
+
+    /// Cancel all orders
+    async fn cancel_all_orders(&self, symbol: &Symbol) -> Result<(), ExchangeError>;
+
+    /// Get balance
+    async fn get_balance(&self, asset: &str) -> Result<f64, ExchangeError>;
+
+    /// Get open orders
+    async fn get_open_orders(&self, symbol: &Symbol) -> Result<Vec<Order>, ExchangeError>;
+
+    /// Set callbacks
+    fn set_callbacks(&mut self, callbacks: ExchangeCallbacks);
+
+    /// Get server time
+    async fn server_time(&self) -> Result<Timestamp, ExchangeError>;
+
+    /// Validate `request` against the venue's matching-engine rules (min
+    /// notional, lot size, price tick, etc.) without ever resting or filling
+    /// it, via the exchange's order-test endpoint. Lets a `--validate-orders`
+    /// run confirm a strategy's quotes are exchange-acceptable before an
+    /// engine is ever pointed at [`Self::send_order`] for real.
+    ///
+    /// The default implementation reports the venue as unsupported; override
+    /// it for exchanges that expose a dedicated test-order endpoint.
+    async fn test_order(&self, request: OrderRequest) -> Result<(), ExchangeError> {
+        let _ = request;
+        Err(ExchangeError::RequestFailed(format!(
+            "{} does not support order validation",
+            self.name()
+        )))
+    }
+
+    /// Fetch a full REST order book snapshot for `symbol`, returning its
+    /// sequence number alongside the bid/ask levels, so a caller can seed a
+    /// local book before trusting a websocket diff stream and re-sync it if
+    /// one ever falls behind (see [`binance::BinanceClient`]'s internal
+    /// `DepthSync` state machine, which already drives itself off this same
+    /// snapshot shape).
+    ///
+    /// The default implementation reports the venue as unsupported; override
+    /// it for exchanges (or simulated/replay clients) that can actually
+    /// produce one.
+    async fn fetch_depth_snapshot(
+        &self,
+        symbol: &Symbol,
+        limit: u32,
+    ) -> Result<(u64, Vec<(Price, Quantity)>, Vec<(Price, Quantity)>), ExchangeError> {
+        let _ = (symbol, limit);
+        Err(ExchangeError::RequestFailed(format!(
+            "{} does not support REST depth snapshots",
+            self.name()
+        )))
+    }
+}
@@ -0,0 +1,890 @@
+//! Simulated exchange for deterministic backtesting
+//!
+//! Implements [`ExchangeClient`] end-to-end without network I/O, modeled on
+//! lfest's simulated futures exchange: a replayed `Tick`/`Trade` tape is
+//! matched against a real in-process [`OrderBook`] (crossing price, FIFO
+//! within a level), queued stop orders fire when the trigger is crossed, and
+//! fills go through a configurable fee/slippage model before the registered
+//! [`ExchangeCallbacks`] are invoked. A pluggable [`FillModel`] additionally
+//! governs submission latency and queue-position priority for resting limit
+//! orders, so a backtest isn't stuck assuming every order fills instantly at
+//! the touch. Because this is the same trait the live `BinanceClient` uses, a
+//! strategy can be backtested against a replayed tape without changing a
+//! line of strategy code.
+
+use super::*;
+use crate::core::types::*;
+use crate::orderbook::{Fill, OrderBook};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Pluggable fill behavior for [`SimulatedExchange`]: how much latency a
+/// submitted order incurs before it's visible to the replayed tape at all,
+/// and how much of a price level's already-quoted size must trade through
+/// (per replayed prints) before a newly-queued resting order is next in line.
+/// `SimulatedExchange` defaults to [`ImmediateFillModel`] — zero latency, no
+/// queue priority — matching its original "cross the tape and fill
+/// instantly" behavior; swap in [`QueuePositionFillModel`] for a more
+/// realistic backtest.
+pub trait FillModel: Send + Sync {
+    /// Delay between an order being submitted and it becoming eligible to
+    /// match against the replayed tape. An order submitted at `T` only
+    /// starts matching against book/tape state from `T + get_latency()`
+    /// onward.
+    fn get_latency(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Given the size already quoted at a newly-resting order's price when
+    /// it was queued, return how much of that volume must trade through
+    /// (via replayed prints at the same price) before the order itself
+    /// becomes eligible to fill — approximating FIFO queue priority against
+    /// participants the simulation can't otherwise observe.
+    fn initial_queue_ahead(&self, quoted_size: Quantity) -> Quantity {
+        let _ = quoted_size;
+        0
+    }
+}
+
+/// Fills resting orders the instant the replayed tape crosses them, with no
+/// queue priority and no latency — the original `SimulatedExchange` behavior.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImmediateFillModel;
+
+impl FillModel for ImmediateFillModel {}
+
+/// Requires the size already quoted at a price level when an order is queued
+/// to trade through first, and delays a submitted order's visibility to the
+/// tape by `latency`.
+#[derive(Debug, Clone, Copy)]
+pub struct QueuePositionFillModel {
+    pub latency: Duration,
+}
+
+impl FillModel for QueuePositionFillModel {
+    fn get_latency(&self) -> Duration {
+        self.latency
+    }
+
+    fn initial_queue_ahead(&self, quoted_size: Quantity) -> Quantity {
+        quoted_size
+    }
+}
+
+/// An order whose visibility to the tape has been delayed by
+/// `FillModel::get_latency`; held here until `release_latent_orders` finds
+/// the simulated clock has reached `visible_at`.
+struct LatentOrder {
+    visible_at: Timestamp,
+    order: Order,
+    client_order_id: String,
+}
+
+/// Flat-bps fee/slippage model applied to every simulated fill
+#[derive(Debug, Clone, Copy)]
+pub struct SimulatedFeeModel {
+    pub taker_fee_bps: f64,
+    pub slippage_bps: f64,
+}
+
+impl Default for SimulatedFeeModel {
+    fn default() -> Self {
+        SimulatedFeeModel {
+            taker_fee_bps: 4.0,
+            slippage_bps: 0.0,
+        }
+    }
+}
+
+/// A stop/take-profit order queued until the replayed mark price crosses
+/// `trigger_price`, then sent into the book as a market order
+struct PendingStop {
+    order: Order,
+    trigger_price: Price,
+}
+
+struct SimulatedAccount {
+    balance: f64,
+    open_orders: HashMap<OrderId, Order>,
+}
+
+/// In-memory venue that matches a replayed tape against a real [`OrderBook`],
+/// for reproducible strategy backtests
+pub struct SimulatedExchange {
+    symbol: Symbol,
+    book: Arc<RwLock<OrderBook>>,
+    account: Arc<RwLock<SimulatedAccount>>,
+    stop_orders: Arc<RwLock<Vec<PendingStop>>>,
+    fee_model: SimulatedFeeModel,
+    next_order_id: Arc<AtomicU64>,
+    connected: Arc<AtomicBool>,
+    callbacks: Arc<RwLock<ExchangeCallbacks>>,
+    /// Last replayed external mid/trade price, used to trigger stop orders.
+    /// Kept separate from the book's own `mid_price()`, which reflects our
+    /// own resting orders rather than the venue's tape.
+    mark_price: Arc<AtomicI64>,
+    /// Simulated clock, advanced to the replayed event's own timestamp at the
+    /// top of `on_market_tick`/`on_market_trade` rather than read from the
+    /// wall clock — latency/queue/expiry checks must key off the tape's own
+    /// time so a replay is deterministic regardless of how fast it's driven.
+    sim_clock: Arc<AtomicU64>,
+    /// Governs submission latency and queue-position fills; see [`FillModel`]
+    fill_model: Arc<dyn FillModel>,
+    /// Orders not yet visible to the tape, per `fill_model.get_latency()`
+    latent_orders: Arc<RwLock<Vec<LatentOrder>>>,
+    /// Remaining externally-quoted volume that must trade through a resting
+    /// order's price level before it's next in line; see
+    /// [`FillModel::initial_queue_ahead`]
+    queue_ahead: Arc<RwLock<HashMap<OrderId, Quantity>>>,
+    /// Last replayed tick, used as the quoted size a newly-resting order's
+    /// queue position is measured against
+    last_tick: Arc<RwLock<Option<Tick>>>,
+}
+
+impl SimulatedExchange {
+    pub fn new(symbol: Symbol, starting_balance: f64) -> Self {
+        Self::with_fee_model(symbol, starting_balance, SimulatedFeeModel::default())
+    }
+
+    pub fn with_fee_model(symbol: Symbol, starting_balance: f64, fee_model: SimulatedFeeModel) -> Self {
+        Self::with_fill_model(symbol, starting_balance, fee_model, Arc::new(ImmediateFillModel))
+    }
+
+    /// Construct with a non-default [`FillModel`], e.g. [`QueuePositionFillModel`]
+    /// for a backtest that models submission latency and queue priority.
+    pub fn with_fill_model(
+        symbol: Symbol,
+        starting_balance: f64,
+        fee_model: SimulatedFeeModel,
+        fill_model: Arc<dyn FillModel>,
+    ) -> Self {
+        SimulatedExchange {
+            book: Arc::new(RwLock::new(OrderBook::new(symbol.clone()))),
+            symbol,
+            account: Arc::new(RwLock::new(SimulatedAccount {
+                balance: starting_balance,
+                open_orders: HashMap::new(),
+            })),
+            stop_orders: Arc::new(RwLock::new(Vec::new())),
+            fee_model,
+            next_order_id: Arc::new(AtomicU64::new(1)),
+            connected: Arc::new(AtomicBool::new(false)),
+            callbacks: Arc::new(RwLock::new(ExchangeCallbacks::default())),
+            mark_price: Arc::new(AtomicI64::new(0)),
+            sim_clock: Arc::new(AtomicU64::new(0)),
+            fill_model,
+            latent_orders: Arc::new(RwLock::new(Vec::new())),
+            queue_ahead: Arc::new(RwLock::new(HashMap::new())),
+            last_tick: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Access the underlying book, e.g. so a backtest harness can assert on
+    /// depth/mid after replaying a tape
+    pub fn book(&self) -> &Arc<RwLock<OrderBook>> {
+        &self.book
+    }
+
+    /// Current simulated wallet balance, net of fees charged on fills
+    pub fn balance(&self) -> f64 {
+        self.account.read().balance
+    }
+
+    /// Current simulated time: the timestamp of the most recently replayed
+    /// `Tick`/`Trade`, not the wall clock — see `sim_clock`.
+    fn now(&self) -> Timestamp {
+        self.sim_clock.load(Ordering::Relaxed)
+    }
+
+    /// Replay a top-of-book tick: sweeps any of our resting orders the new
+    /// NBBO would cross, then checks queued stop orders against the new mark
+    /// price. Deliberately does not write the tick into our own book via
+    /// `update_bid`/`update_ask` — those replace a price level's entire
+    /// `PriceLevel` wholesale, which would silently wipe out a real resting
+    /// order's FIFO queue entry if it happened to sit at the quoted price.
+    pub fn on_market_tick(&self, tick: Tick) {
+        self.sim_clock.store(tick.exchange_ts, Ordering::Relaxed);
+        *self.last_tick.write() = Some(tick);
+        self.release_latent_orders();
+
+        let mid = (tick.bid + tick.ask) / 2;
+        self.mark_price.store(mid, Ordering::Relaxed);
+
+        if let Some(ref cb) = self.callbacks.read().on_tick {
+            cb(self.symbol.clone(), tick);
+        }
+
+        self.sweep_against_tick(tick);
+        self.fire_stop_orders(mid);
+    }
+
+    /// Cross any of our resting orders that the replayed NBBO reaches: a
+    /// tick bid at or above our best ask, or a tick ask at or below our best
+    /// bid, models an external taker wide enough to take out that side
+    fn sweep_against_tick(&self, tick: Tick) {
+        let now = self.now();
+
+        let ask_cross = self.book.read().best_ask().is_some_and(|ask| tick.bid >= ask);
+        if ask_cross {
+            let incoming = Order::new(self.symbol.clone(), Side::Buy, OrderType::Limit, tick.bid, tick.bid_qty);
+            let fills = self.book.write().match_order(incoming, now).1;
+            self.settle_fills(&fills, now);
+        }
+
+        let bid_cross = self.book.read().best_bid().is_some_and(|bid| tick.ask <= bid);
+        if bid_cross {
+            let incoming = Order::new(self.symbol.clone(), Side::Sell, OrderType::Limit, tick.ask, tick.ask_qty);
+            let fills = self.book.write().match_order(incoming, now).1;
+            self.settle_fills(&fills, now);
+        }
+    }
+
+    /// Replay a trade print: matches it against our resting orders as the
+    /// counterparty, as if the venue's own tape crossed the book. The taker
+    /// order takes the trade's own side (a `Sell` print is an aggressor
+    /// hitting the bid, so it consumes our resting bids the same way).
+    pub fn on_market_trade(&self, trade: Trade) {
+        self.sim_clock.store(trade.timestamp, Ordering::Relaxed);
+        self.release_latent_orders();
+
+        let fillable_qty = self.advance_queue(trade.side, trade.price, trade.quantity);
+        if fillable_qty > 0 {
+            let taker = Order::new(self.symbol.clone(), trade.side, OrderType::Market, trade.price, fillable_qty);
+
+            let fills = {
+                let mut book = self.book.write();
+                book.match_order(taker, trade.timestamp).1
+            };
+
+            self.settle_fills(&fills, trade.timestamp);
+        }
+
+        self.mark_price.store(trade.price, Ordering::Relaxed);
+
+        if let Some(ref cb) = self.callbacks.read().on_trade {
+            cb(trade.clone());
+        }
+
+        self.fire_stop_orders(trade.price);
+    }
+
+    /// Consume `trade_qty` of tape volume against any queue position still
+    /// ahead of our own resting orders on the side the print hits (a `Sell`
+    /// print hits resting bids, a `Buy` print hits resting asks), FIFO by
+    /// order id. Returns the remainder, if any, actually available to match
+    /// against our orders this print.
+    fn advance_queue(&self, trade_side: Side, price: Price, trade_qty: Quantity) -> Quantity {
+        let target_side = match trade_side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+
+        let mut resting_ids: Vec<OrderId> = self
+            .account
+            .read()
+            .open_orders
+            .values()
+            .filter(|o| o.side == target_side && o.price == price)
+            .map(|o| o.id)
+            .collect();
+        resting_ids.sort_unstable();
+
+        let mut queue_ahead = self.queue_ahead.write();
+        let mut remaining = trade_qty;
+        for order_id in resting_ids {
+            if remaining == 0 {
+                break;
+            }
+            if let Some(ahead) = queue_ahead.get_mut(&order_id) {
+                let consumed = (*ahead).min(remaining);
+                *ahead -= consumed;
+                remaining -= consumed;
+                if *ahead == 0 {
+                    queue_ahead.remove(&order_id);
+                }
+            }
+        }
+        remaining
+    }
+
+    /// Release any latent orders whose `FillModel::get_latency` has elapsed,
+    /// submitting each exactly as [`Self::submit_immediate`] would have at
+    /// the time it was sent.
+    fn release_latent_orders(&self) {
+        let now = self.now();
+        let ready: Vec<LatentOrder> = {
+            let mut latent = self.latent_orders.write();
+            let (ready, still_latent): (Vec<_>, Vec<_>) =
+                latent.drain(..).partition(|p| now >= p.visible_at);
+            *latent = still_latent;
+            ready
+        };
+
+        for pending in ready {
+            let response = self.submit_immediate(pending.order, pending.client_order_id);
+            if let Some(ref cb) = self.callbacks.read().on_order_update {
+                if let Some(order) = self.account.read().open_orders.get(&response.order_id) {
+                    cb(order.clone());
+                }
+            }
+        }
+    }
+
+    /// Send any queued stop order whose trigger has been crossed by
+    /// `mark_price` into the book as a market order
+    fn fire_stop_orders(&self, mark_price: Price) {
+        let triggered: Vec<Order> = {
+            let mut stops = self.stop_orders.write();
+            let mut triggered = Vec::new();
+            stops.retain(|pending| {
+                let crosses = match pending.order.side {
+                    Side::Buy => mark_price >= pending.trigger_price,
+                    Side::Sell => mark_price <= pending.trigger_price,
+                };
+                if crosses {
+                    triggered.push(pending.order.clone());
+                }
+                !crosses
+            });
+            triggered
+        };
+
+        for mut order in triggered {
+            let now = self.now();
+            order.timestamp = now;
+            let (taker, fills) = {
+                let mut book = self.book.write();
+                book.match_order(order, now)
+            };
+            self.settle_fills(&fills, now);
+
+            let mut account = self.account.write();
+            if taker.remaining() > 0 && !matches!(taker.status, OrderStatus::Canceled | OrderStatus::Rejected) {
+                account.open_orders.insert(taker.id, taker.clone());
+            } else {
+                account.open_orders.remove(&taker.id);
+            }
+            drop(account);
+
+            if let Some(ref cb) = self.callbacks.read().on_order_update {
+                cb(taker);
+            }
+        }
+    }
+
+    /// Apply the fee model to each fill, update the resting (maker) order's
+    /// filled quantity/status in `open_orders`, and notify `on_order_update`
+    fn settle_fills(&self, fills: &[Fill], now: Timestamp) {
+        if fills.is_empty() {
+            return;
+        }
+
+        let mut updated = Vec::new();
+        {
+            let mut account = self.account.write();
+            for fill in fills {
+                let slipped_price = apply_slippage(fill.price, self.fee_model.slippage_bps);
+                let notional = from_qty(fill.quantity) * from_price(slipped_price);
+                let fee = notional * self.fee_model.taker_fee_bps / 10_000.0;
+                account.balance -= fee;
+
+                if let Some(order) = account.open_orders.get_mut(&fill.maker_id) {
+                    order.filled_qty += fill.quantity;
+                    order.timestamp = now;
+                    order.status = if order.filled_qty >= order.quantity {
+                        OrderStatus::Filled
+                    } else {
+                        OrderStatus::PartiallyFilled
+                    };
+                    updated.push(order.clone());
+                }
+            }
+            account.open_orders.retain(|_, o| o.status != OrderStatus::Filled);
+        }
+
+        let mut queue_ahead = self.queue_ahead.write();
+        for order in &updated {
+            if order.status == OrderStatus::Filled {
+                queue_ahead.remove(&order.id);
+            }
+        }
+        drop(queue_ahead);
+
+        if let Some(ref cb) = self.callbacks.read().on_order_update {
+            for order in updated {
+                cb(order);
+            }
+        }
+    }
+
+    /// Match `order` against the book right now (no further latency), and
+    /// rest any unfilled remainder as a limit order — queuing it behind
+    /// `fill_model.initial_queue_ahead` worth of the size already quoted at
+    /// its price. This is `send_order`'s own path when `get_latency()` is
+    /// zero, and the path a latent order takes once `release_latent_orders`
+    /// finds it's finally visible to the tape.
+    fn submit_immediate(&self, order: Order, client_order_id: String) -> OrderResponse {
+        let order_id = order.id;
+        let now = self.now();
+        let (resting, fills) = {
+            let mut book = self.book.write();
+            book.match_order(order, now)
+        };
+
+        self.settle_fills(&fills, now);
+
+        if resting.order_type == OrderType::Limit
+            && resting.remaining() > 0
+            && !matches!(resting.status, OrderStatus::Canceled | OrderStatus::Rejected)
+        {
+            let mut book = self.book.write();
+            if let Err(e) = book.add_order(resting.clone()) {
+                return OrderResponse {
+                    success: false,
+                    order_id,
+                    client_order_id,
+                    status: OrderStatus::Rejected,
+                    error_message: Some(e.to_string()),
+                };
+            }
+            drop(book);
+            self.account.write().open_orders.insert(resting.id, resting.clone());
+
+            let queue_ahead = self.fill_model.initial_queue_ahead(self.quoted_size_at(resting.side, resting.price));
+            if queue_ahead > 0 {
+                self.queue_ahead.write().insert(resting.id, queue_ahead);
+            }
+        }
+
+        OrderResponse {
+            success: !matches!(resting.status, OrderStatus::Rejected),
+            order_id,
+            client_order_id,
+            status: resting.status,
+            error_message: None,
+        }
+    }
+
+    /// Size quoted by the last replayed tick at exactly `price` on `side`,
+    /// or `0` if there's no tick yet or `price` isn't the current touch —
+    /// the proxy used for how much volume sits ahead of a newly-resting
+    /// order at that price.
+    fn quoted_size_at(&self, side: Side, price: Price) -> Quantity {
+        match (*self.last_tick.read(), side) {
+            (Some(tick), Side::Buy) if tick.bid == price => tick.bid_qty,
+            (Some(tick), Side::Sell) if tick.ask == price => tick.ask_qty,
+            _ => 0,
+        }
+    }
+}
+
+/// Widen the fill price against the taker by `slippage_bps`, in the adverse
+/// direction for whichever side crossed (approximated here via the fill price
+/// itself, since the taker side isn't threaded through `Fill`)
+fn apply_slippage(price: Price, slippage_bps: f64) -> Price {
+    if slippage_bps == 0.0 {
+        return price;
+    }
+    to_price(from_price(price) * (1.0 + slippage_bps / 10_000.0))
+}
+
+#[async_trait]
+impl ExchangeClient for SimulatedExchange {
+    fn name(&self) -> &str {
+        "simulated"
+    }
+
+    async fn connect(&mut self) -> Result<(), ExchangeError> {
+        self.connected.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), ExchangeError> {
+        self.connected.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    async fn subscribe_ticker(&mut self, _symbol: &Symbol) -> Result<(), ExchangeError> {
+        Ok(())
+    }
+
+    async fn subscribe_orderbook(&mut self, _symbol: &Symbol, _depth: u32) -> Result<(), ExchangeError> {
+        Ok(())
+    }
+
+    async fn subscribe_trades(&mut self, _symbol: &Symbol) -> Result<(), ExchangeError> {
+        Ok(())
+    }
+
+    async fn send_order(&self, request: OrderRequest) -> Result<OrderResponse, ExchangeError> {
+        if request.symbol != self.symbol {
+            return Err(ExchangeError::OrderRejected(format!(
+                "unknown symbol {}",
+                request.symbol
+            )));
+        }
+
+        let order_id = self.next_order_id.fetch_add(1, Ordering::Relaxed);
+        let mut order = Order::new(
+            request.symbol.clone(),
+            request.side,
+            request.order_type,
+            request.price,
+            request.quantity,
+        );
+        order.id = order_id;
+        order.time_in_force = request.time_in_force;
+
+        let client_order_id = request.client_order_id.clone().unwrap_or_default();
+
+        // Stop orders queue until the trigger price is crossed rather than
+        // hitting the book immediately.
+        if let Some(trigger_price) = request.stop_price {
+            self.account.write().open_orders.insert(order.id, order.clone());
+            self.stop_orders.write().push(PendingStop { order, trigger_price });
+            return Ok(OrderResponse {
+                success: true,
+                order_id,
+                client_order_id,
+                status: OrderStatus::New,
+                error_message: None,
+            });
+        }
+
+        let latency = self.fill_model.get_latency();
+        if latency > Duration::ZERO {
+            let visible_at = self.now() + latency.as_nanos() as u64;
+            self.latent_orders.write().push(LatentOrder { visible_at, order, client_order_id: client_order_id.clone() });
+            return Ok(OrderResponse {
+                success: true,
+                order_id,
+                client_order_id,
+                status: OrderStatus::New,
+                error_message: None,
+            });
+        }
+
+        Ok(self.submit_immediate(order, client_order_id))
+    }
+
+    async fn cancel_order(&self, symbol: &Symbol, order_id: OrderId) -> Result<CancelResponse, ExchangeError> {
+        if *symbol != self.symbol {
+            return Ok(CancelResponse {
+                success: false,
+                order_id,
+                error_message: Some(format!("unknown symbol {}", symbol)),
+            });
+        }
+
+        let was_stop = {
+            let mut stops = self.stop_orders.write();
+            match stops.iter().position(|pending| pending.order.id == order_id) {
+                Some(pos) => {
+                    stops.remove(pos);
+                    true
+                }
+                None => false,
+            }
+        };
+
+        let was_latent = {
+            let mut latent = self.latent_orders.write();
+            let before = latent.len();
+            latent.retain(|pending| pending.order.id != order_id);
+            latent.len() != before
+        };
+
+        let removed = was_stop || was_latent || self.book.write().remove_order(order_id).is_some();
+        self.account.write().open_orders.remove(&order_id);
+        self.queue_ahead.write().remove(&order_id);
+
+        Ok(CancelResponse {
+            success: removed,
+            order_id,
+            error_message: if removed { None } else { Some("order not found".to_string()) },
+        })
+    }
+
+    async fn cancel_all_orders(&self, symbol: &Symbol) -> Result<(), ExchangeError> {
+        if *symbol != self.symbol {
+            return Ok(());
+        }
+
+        let order_ids: Vec<OrderId> = self.account.read().open_orders.keys().copied().collect();
+        for order_id in order_ids {
+            let _ = self.book.write().remove_order(order_id);
+        }
+        self.stop_orders.write().clear();
+        self.latent_orders.write().clear();
+        self.queue_ahead.write().clear();
+        self.account.write().open_orders.clear();
+
+        Ok(())
+    }
+
+    async fn get_balance(&self, _asset: &str) -> Result<f64, ExchangeError> {
+        Ok(self.account.read().balance)
+    }
+
+    async fn get_open_orders(&self, symbol: &Symbol) -> Result<Vec<Order>, ExchangeError> {
+        if *symbol != self.symbol {
+            return Ok(Vec::new());
+        }
+        Ok(self.account.read().open_orders.values().cloned().collect())
+    }
+
+    fn set_callbacks(&mut self, callbacks: ExchangeCallbacks) {
+        *self.callbacks.write() = callbacks;
+    }
+
+    async fn server_time(&self) -> Result<Timestamp, ExchangeError> {
+        Ok(now_nanos())
+    }
+
+    /// There's no real matching-engine filter set to validate against here —
+    /// just confirm `request.symbol` matches what this exchange trades, the
+    /// same check `send_order` itself would reject on.
+    async fn test_order(&self, request: OrderRequest) -> Result<(), ExchangeError> {
+        if request.symbol != self.symbol {
+            return Err(ExchangeError::OrderRejected(format!(
+                "unknown symbol {}",
+                request.symbol
+            )));
+        }
+        Ok(())
+    }
+
+    /// Synthesizes a snapshot from the in-memory book's own checkpoint rather
+    /// than hitting a REST endpoint, since a simulated/replayed run has no
+    /// such endpoint to hit — the checkpoint's `seq` serves the same "resync
+    /// point" role a real exchange's `lastUpdateId` does.
+    async fn fetch_depth_snapshot(
+        &self,
+        _symbol: &Symbol,
+        _limit: u32,
+    ) -> Result<(u64, Vec<(Price, Quantity)>, Vec<(Price, Quantity)>), ExchangeError> {
+        let checkpoint = self.book.read().book_checkpoint();
+        Ok((checkpoint.seq, checkpoint.bids, checkpoint.asks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_limit_order_rests_and_fills_against_replayed_trade() {
+        let exchange = SimulatedExchange::new(Symbol::new("BTCUSDT"), 10000.0);
+
+        let response = exchange
+            .send_order(OrderRequest::limit_buy(Symbol::new("BTCUSDT"), to_price(50000.0), to_qty(1.0)))
+            .await
+            .unwrap();
+        assert!(response.success);
+        assert_eq!(response.status, OrderStatus::New);
+
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert_eq!(open.len(), 1);
+
+        // A sell print trading through our resting bid should fill it.
+        exchange.on_market_trade(Trade {
+            order_id: 0,
+            trade_id: 1,
+            symbol: Symbol::new("BTCUSDT"),
+            side: Side::Sell,
+            price: to_price(50000.0),
+            quantity: to_qty(1.0),
+            timestamp: 1,
+            is_maker: false,
+        });
+
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert!(open.is_empty());
+        assert!(exchange.balance() < 10000.0, "taker fee should have been charged");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order_removes_resting_order() {
+        let exchange = SimulatedExchange::new(Symbol::new("BTCUSDT"), 10000.0);
+
+        let response = exchange
+            .send_order(OrderRequest::limit_buy(Symbol::new("BTCUSDT"), to_price(50000.0), to_qty(1.0)))
+            .await
+            .unwrap();
+
+        let cancel = exchange
+            .cancel_order(&Symbol::new("BTCUSDT"), response.order_id)
+            .await
+            .unwrap();
+        assert!(cancel.success);
+
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert!(open.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stop_market_order_queued_until_trigger_crossed() {
+        let exchange = SimulatedExchange::new(Symbol::new("BTCUSDT"), 10000.0);
+
+        let response = exchange
+            .send_order(OrderRequest::stop_market(
+                Symbol::new("BTCUSDT"),
+                Side::Sell,
+                to_price(49000.0),
+                to_qty(1.0),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status, OrderStatus::New);
+
+        // Resting bid for the stop to match against once triggered.
+        exchange
+            .send_order(OrderRequest::limit_buy(Symbol::new("BTCUSDT"), to_price(48900.0), to_qty(1.0)))
+            .await
+            .unwrap();
+
+        // Tick above the trigger: stop should not fire yet.
+        exchange.on_market_tick(Tick {
+            bid: to_price(50000.0),
+            ask: to_price(50001.0),
+            bid_qty: to_qty(1.0),
+            ask_qty: to_qty(1.0),
+            last_price: to_price(50000.0),
+            last_qty: 0,
+            exchange_ts: 0,
+            local_ts: 0,
+            sequence: 0,
+        });
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert_eq!(open.len(), 2);
+
+        // Tick through the trigger: the stop should fire and match the resting bid.
+        exchange.on_market_tick(Tick {
+            bid: to_price(48900.0),
+            ask: to_price(48950.0),
+            bid_qty: to_qty(1.0),
+            ask_qty: to_qty(1.0),
+            last_price: to_price(48900.0),
+            last_qty: 0,
+            exchange_ts: 0,
+            local_ts: 0,
+            sequence: 0,
+        });
+
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert!(open.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queue_position_model_requires_ahead_volume_to_trade_through() {
+        let exchange = SimulatedExchange::with_fill_model(
+            Symbol::new("BTCUSDT"),
+            10000.0,
+            SimulatedFeeModel::default(),
+            Arc::new(QueuePositionFillModel { latency: Duration::ZERO }),
+        );
+
+        // The tape already quotes 2.0 resting at 50000 before our own order
+        // joins the back of that queue.
+        exchange.on_market_tick(Tick {
+            bid: to_price(50000.0),
+            ask: to_price(50010.0),
+            bid_qty: to_qty(2.0),
+            ask_qty: to_qty(1.0),
+            last_price: to_price(50000.0),
+            last_qty: 0,
+            exchange_ts: 0,
+            local_ts: 0,
+            sequence: 0,
+        });
+
+        exchange
+            .send_order(OrderRequest::limit_buy(Symbol::new("BTCUSDT"), to_price(50000.0), to_qty(1.0)))
+            .await
+            .unwrap();
+
+        // A print smaller than the queue ahead of us shouldn't fill us yet.
+        exchange.on_market_trade(Trade {
+            order_id: 0,
+            trade_id: 1,
+            symbol: Symbol::new("BTCUSDT"),
+            side: Side::Sell,
+            price: to_price(50000.0),
+            quantity: to_qty(1.5),
+            timestamp: 1,
+            is_maker: false,
+        });
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].filled_qty, 0);
+
+        // Once the remaining ahead volume trades through, further volume
+        // fills us.
+        exchange.on_market_trade(Trade {
+            order_id: 0,
+            trade_id: 2,
+            symbol: Symbol::new("BTCUSDT"),
+            side: Side::Sell,
+            price: to_price(50000.0),
+            quantity: to_qty(1.5),
+            timestamp: 2,
+            is_maker: false,
+        });
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert!(open.is_empty(), "remaining 1.0 beyond the queue should have fully filled us");
+    }
+
+    #[tokio::test]
+    async fn test_latency_model_delays_order_visibility() {
+        let exchange = SimulatedExchange::with_fill_model(
+            Symbol::new("BTCUSDT"),
+            10000.0,
+            SimulatedFeeModel::default(),
+            Arc::new(QueuePositionFillModel { latency: Duration::from_millis(20) }),
+        );
+
+        exchange
+            .send_order(OrderRequest::limit_buy(Symbol::new("BTCUSDT"), to_price(50000.0), to_qty(1.0)))
+            .await
+            .unwrap();
+
+        // Still latent: not yet visible to the book, so a crossing trade
+        // doesn't touch it.
+        exchange.on_market_trade(Trade {
+            order_id: 0,
+            trade_id: 1,
+            symbol: Symbol::new("BTCUSDT"),
+            side: Side::Sell,
+            price: to_price(50000.0),
+            quantity: to_qty(1.0),
+            timestamp: 1,
+            is_maker: false,
+        });
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert!(open.is_empty(), "latent order shouldn't be visible yet");
+
+        // Now visible: a tick timestamped past the latency window (the
+        // simulated clock, not a real-time sleep) should release and rest it.
+        exchange.on_market_tick(Tick {
+            bid: to_price(49990.0),
+            ask: to_price(50010.0),
+            bid_qty: 0,
+            ask_qty: 0,
+            last_price: to_price(50000.0),
+            last_qty: 0,
+            exchange_ts: Duration::from_millis(30).as_nanos() as u64,
+            local_ts: 0,
+            sequence: 0,
+        });
+        let open = exchange.get_open_orders(&Symbol::new("BTCUSDT")).await.unwrap();
+        assert_eq!(open.len(), 1, "order should have been released and rested");
+    }
+}
@@ -13,6 +13,7 @@ pub mod exchange;
 pub mod orderbook;
 pub mod strategy;
 pub mod risk;
+pub mod oms;
 pub mod utils;
 
 pub use core::types::*;
@@ -25,8 +26,9 @@ pub use risk::RiskManager;
 pub mod prelude {
     pub use crate::core::types::*;
     pub use crate::core::engine::{TradingEngine, EngineConfig};
-    pub use crate::orderbook::{OrderBook, PriceLevel};
-    pub use crate::strategy::{MarketMaker, MarketMakerParams, QuoteDecision};
-    pub use crate::risk::{RiskManager, RiskLimits, RiskCheckResult};
+    pub use crate::orderbook::{BookError, MarketSpec, OrderBook, PegOrder, PegReference, PriceLevel};
+    pub use crate::strategy::{MarketMaker, MarketMakerParams, QuoteDecision, QuoteLevel};
+    pub use crate::risk::{RiskManager, RiskLimits, RiskCheckResult, FeeModel};
+    pub use crate::oms::{OrderStore, ManagedOrder};
     pub use crate::exchange::{ExchangeClient, ExchangeConfig};
 }
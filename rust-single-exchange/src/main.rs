@@ -2,14 +2,17 @@
 //!
 //! A high-performance market making system for cryptocurrency exchanges.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use hft::prelude::*;
-use hft::core::engine::{EngineBuilder, TradingEngine};
-use hft::exchange::{BinanceClient, ExchangeCallbacks, ExchangeClient};
-use hft::exchange::binance::BinanceConfig;
-use hft::utils::{init_logging, AppConfig};
+use hft::core::engine::{EngineBuilder, EngineCommand, TradingEngine};
+use hft::exchange::{ExchangeCallbacks, ExchangeClient, ExchangeClientFactory, HistoricalReplayClient};
+use hft::exchange::StreamChannel;
+use hft::utils::{init_logging, AppConfig, FuturesConfig};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
+use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
 #[derive(Parser, Debug)]
@@ -20,9 +23,11 @@ struct Args {
     #[arg(short, long, default_value = "config/config.json")]
     config: String,
 
-    /// Trading symbol
+    /// Trading symbol; repeatable to run several symbols concurrently from
+    /// one process, each with its own `TradingEngine`, sharing one exchange
+    /// connection
     #[arg(short, long)]
-    symbol: Option<String>,
+    symbol: Vec<String>,
 
     /// Use testnet
     #[arg(short, long)]
@@ -32,9 +37,54 @@ struct Args {
     #[arg(short, long)]
     paper: bool,
 
+    /// Resume after a restart in maintenance mode: reconcile and wind down
+    /// outstanding orders/position without opening new exposure
+    #[arg(long)]
+    resume: bool,
+
     /// Verbose logging
     #[arg(short, long)]
     verbose: bool,
+
+    /// Bind a Unix-domain control socket at this path, accepting one
+    /// `EngineCommand` line per connection write (e.g. `ENABLE_TRADING`,
+    /// `UPDATE_SPREAD 12.5`), so the running engine can be steered without
+    /// a restart
+    #[arg(long)]
+    control_socket: Option<String>,
+
+    /// Route every computed quote through the exchange's order-test endpoint
+    /// instead of actually resting it, so strategy output can be checked
+    /// against real symbol filters (min notional, lot size, price tick)
+    /// before going live
+    #[arg(long)]
+    validate_orders: bool,
+
+    /// Trade Binance Futures instead of spot: selects the `binance_futures`
+    /// exchange, subscribes each symbol's mark-price/funding-rate stream,
+    /// and (if `futures.contract_expiry_unix` is configured) schedules
+    /// automatic contract rollover
+    #[arg(long)]
+    futures: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Replay a historical tape through the same TradingEngine/strategy code
+    /// used for live trading, instead of connecting to a live exchange
+    Backtest {
+        /// Path to a JSON-lines tape of ticks/trades to replay, in the same
+        /// shape `EventBus::bind_unix_socket` streams out
+        #[arg(long)]
+        data: String,
+
+        /// Starting simulated wallet balance
+        #[arg(long, default_value_t = 10_000.0)]
+        balance: f64,
+    },
 }
 
 fn print_banner() {
@@ -63,12 +113,16 @@ async fn main() -> anyhow::Result<()> {
     };
 
     // Apply command line overrides
-    if let Some(symbol) = args.symbol {
-        config.trading.symbol = symbol;
+    if !args.symbol.is_empty() {
+        config.trading.symbol = args.symbol[0].clone();
+        config.trading.symbols = args.symbol.clone();
     }
     if args.testnet || args.paper {
         config.trading.paper_trading = true;
     }
+    if args.futures {
+        config.exchange.name = "binance_futures".to_string();
+    }
     if args.verbose {
         config.system.log_level = "DEBUG".to_string();
     }
@@ -85,43 +139,75 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Starting HFT Market Making Bot");
     info!("Configuration loaded from: {}", args.config);
-    info!("Trading symbol: {}", config.trading.symbol);
-    info!("Mode: {}", if config.trading.paper_trading { "Paper Trading" } else { "LIVE TRADING" });
 
-    // Build trading engine
-    let symbol = Symbol::new(&config.trading.symbol);
+    if let Some(Command::Backtest { data, balance }) = args.command {
+        let symbol = Symbol::new(&config.trading.symbol);
+        return run_backtest(&config, symbol, &data, balance).await;
+    }
 
-    let engine = EngineBuilder::new()
-        .symbol(symbol.clone())
-        .exchange(&config.exchange.name)
-        .strategy_params(config.to_strategy_params())
-        .risk_limits(config.to_risk_limits())
-        .enable_trading(!config.trading.paper_trading)
-        .build();
+    let symbols: Vec<Symbol> = config.trading_symbols().iter().map(Symbol::new).collect();
+    info!("Trading symbols: {}", config.trading_symbols().join(", "));
 
-    // Create exchange client
-    let binance_config = if config.trading.paper_trading {
-        let mut cfg = BinanceConfig::testnet();
-        cfg.base.api_key = config.exchange.api_key.clone();
-        cfg.base.api_secret = config.exchange.api_secret.clone();
-        cfg
-    } else {
-        BinanceConfig {
-            base: config.to_exchange_config(),
-            use_futures: false,
-            recv_window: 5000,
-        }
-    };
+    info!("Mode: {}", if config.trading.paper_trading { "Paper Trading" } else { "LIVE TRADING" });
 
-    let mut client = BinanceClient::new(binance_config);
+    // Build one independent trading engine per symbol, each with its own
+    // risk manager, sharing the single exchange connection below
+    let mut engines: HashMap<Symbol, Arc<TradingEngine>> = HashMap::new();
+    for symbol in &symbols {
+        let engine = EngineBuilder::new()
+            .symbol(symbol.clone())
+            .exchange(&config.exchange.name)
+            .strategy_params(config.to_strategy_params())
+            .risk_limits(config.to_risk_limits())
+            .fee_model(config.to_fee_model())
+            .enable_trading(!config.trading.paper_trading)
+            .validate_orders(args.validate_orders)
+            .build();
+        engines.insert(symbol.clone(), Arc::new(engine));
+    }
+    let engines = Arc::new(engines);
 
-    // Setup callbacks
-    let engine_ref = Arc::new(engine);
-    let engine_clone = engine_ref.clone();
+    // Create exchange client purely from config, so driving a second venue
+    // (or Binance Futures) never requires editing `main`. Wrapped in a mutex
+    // so the periodic depth-reconciliation task below can borrow it alongside
+    // the main control flow.
+    let client = Arc::new(Mutex::new(
+        ExchangeClientFactory::build(&config.to_exchange_config(), config.trading.paper_trading)?,
+    ));
 
-    client.set_callbacks(ExchangeCallbacks {
-        on_tick: Some(Box::new(move |tick| {
-            engine_clone.on_tick(tick);
+    // Setup callbacks; on_tick/on_funding_rate route by each engine's current
+    // `active_symbol()` rather than the symbol it was originally built for,
+    // so a `SetSymbol` (e.g. from contract rollover, see
+    // `spawn_contract_rollover`) keeps routing market data to the same engine
+    // under its new symbol instead of orphaning it.
+    let engines_for_tick = engines.clone();
+    let engines_for_funding = engines.clone();
+    let engines_for_book = engines.clone();
+
+    client.lock().await.set_callbacks(ExchangeCallbacks {
+        on_tick: Some(Box::new(move |symbol, tick| {
+            match engines_for_tick.values().find(|e| e.active_symbol() == symbol) {
+                Some(engine) => engine.on_tick(tick),
+                None => warn!("Tick for unsubscribed symbol {}", symbol),
+            }
+        })),
+        // The synced, gap-checked depth book a client maintains internally
+        // (REST snapshot + contiguous diffs, e.g. `BinanceClient`'s
+        // `DepthSync`) is mirrored into the matching engine's own order book
+        // here, rather than main re-implementing snapshot/gap handling.
+        on_orderbook: Some(Box::new(move |book| {
+            let symbol = book.symbol().clone();
+            match engines_for_book.values().find(|e| e.active_symbol() == symbol) {
+                Some(engine) => {
+                    let checkpoint = book.book_checkpoint();
+                    let mut target = engine.orderbook().write();
+                    match target.apply_snapshot(checkpoint.bids, checkpoint.asks) {
+                        Ok(()) => target.set_sequence(checkpoint.seq),
+                        Err(e) => warn!("Rejected order book sync for {}: {}", symbol, e),
+                    }
+                }
+                None => warn!("Order book sync for unsubscribed symbol {}", symbol),
+            }
         })),
         on_order_update: Some(Box::new(|order| {
             info!("Order update: {:?} status={:?}", order.id, order.status);
@@ -144,23 +230,62 @@ async fn main() -> anyhow::Result<()> {
         on_disconnected: Some(Box::new(|| {
             warn!("Disconnected from exchange");
         })),
+        on_funding_rate: Some(Box::new(move |symbol, update| {
+            match engines_for_funding.values().find(|e| e.active_symbol() == symbol) {
+                Some(engine) => engine.on_funding_update(update),
+                None => warn!("Funding update for unsubscribed symbol {}", symbol),
+            }
+        })),
+        ..Default::default()
     });
 
     // Connect to exchange
     info!("Connecting to exchange...");
-    client.connect().await?;
+    client.lock().await.connect().await?;
 
-    // Subscribe to market data
+    // Subscribe every symbol's ticker and order book in as few round-trips
+    // as the exchange allows
     info!("Subscribing to market data...");
-    client.subscribe_ticker(&symbol).await?;
-    client.subscribe_orderbook(&symbol, 20).await?;
+    client
+        .lock()
+        .await
+        .subscribe_many(&symbols, &[StreamChannel::Ticker, StreamChannel::Orderbook { depth: 20 }])
+        .await?;
+
+    if args.futures {
+        client.lock().await.subscribe_many(&symbols, &[StreamChannel::FundingRate]).await?;
+    }
 
-    // Start engine
-    info!("Starting trading engine...");
-    engine_ref.start().await?;
+    // Start each engine
+    info!("Starting trading engines...");
+    for engine in engines.values() {
+        engine.start(args.resume).await?;
+    }
+
+    // Schedule automatic contract rollover per engine; no-ops unless the
+    // futures config actually names an expiry and a next contract.
+    if args.futures {
+        for engine in engines.values() {
+            spawn_contract_rollover(client.clone(), engine.clone(), config.futures.clone());
+        }
+    }
 
-    if !config.trading.paper_trading {
-        engine_ref.enable_trading();
+    // The control socket steers a single engine; with more than one symbol,
+    // bind it to the first rather than guessing which one the operator means.
+    #[cfg(unix)]
+    if let Some(path) = args.control_socket.clone() {
+        if let Some(engine) = engines.values().next() {
+            if engines.len() > 1 {
+                warn!("Control socket only steers symbol {}", engine.active_symbol());
+            }
+            spawn_control_socket(engine.clone(), path);
+        }
+    }
+
+    if !config.trading.paper_trading && !args.resume {
+        for engine in engines.values() {
+            engine.enable_trading();
+        }
     }
 
     info!("System started. Press Ctrl+C to stop.");
@@ -170,16 +295,178 @@ async fn main() -> anyhow::Result<()> {
 
     // Shutdown
     info!("Shutting down...");
+    for engine in engines.values() {
+        engine.disable_trading();
+        engine.stop().await;
+    }
+    client.lock().await.disconnect().await?;
+
+    info!("Final Statistics:");
+    let (mut ticks, mut orders, mut trades) = (0u64, 0u64, 0u64);
+    for (symbol, engine) in engines.iter() {
+        let stats = engine.stats();
+        info!(
+            "  {}: ticks={} orders={} trades={}",
+            symbol, stats.ticks_processed, stats.orders_sent, stats.trades_executed
+        );
+        ticks += stats.ticks_processed;
+        orders += stats.orders_sent;
+        trades += stats.trades_executed;
+    }
+    info!("  Total: ticks={} orders={} trades={}", ticks, orders, trades);
+
+    info!("Shutdown complete.");
+    Ok(())
+}
+
+/// Accept connections on a Unix-domain socket at `path`, treating each line
+/// written to a connection as one [`EngineCommand`] in `EngineCommand::parse`'s
+/// line protocol, and forwarding parsed commands into `engine`.
+#[cfg(unix)]
+fn spawn_control_socket(engine: Arc<TradingEngine>, path: String) {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    tokio::spawn(async move {
+        let _ = std::fs::remove_file(&path);
+        let listener = match tokio::net::UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                error!("Failed to bind control socket {}: {}", path, e);
+                return;
+            }
+        };
+        info!("Control socket listening on {}", path);
+
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                break;
+            };
+            let engine = engine.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stream).lines();
+                loop {
+                    match lines.next_line().await {
+                        Ok(Some(line)) => match EngineCommand::parse(&line) {
+                            Ok(command) => engine.send_command(command),
+                            Err(e) => warn!("Invalid control command {:?}: {}", line, e),
+                        },
+                        Ok(None) => break,
+                        Err(e) => {
+                            warn!("Control socket read error: {}", e);
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+    });
+}
+
+/// Poll towards automatic rollover of a dated futures contract: once within
+/// `futures.rollover_window_secs` of `futures.contract_expiry_unix`, flatten
+/// `engine`'s position, subscribe `futures.next_contract_symbol`, then
+/// `SetSymbol` the engine onto it and resume out of maintenance mode.
+/// No-ops unless both `contract_expiry_unix` and `next_contract_symbol` are
+/// configured.
+fn spawn_contract_rollover(client: Arc<Mutex<Box<dyn ExchangeClient>>>, engine: Arc<TradingEngine>, futures: FuturesConfig) {
+    let (Some(expiry), Some(next_symbol)) = (futures.contract_expiry_unix, futures.next_contract_symbol) else {
+        return;
+    };
+    let next_symbol = Symbol::new(&next_symbol);
+    let check_interval = Duration::from_secs(futures.rollover_check_interval_secs);
+    let rollover_window = futures.rollover_window_secs as i64;
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(check_interval).await;
+
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64;
+            if expiry - now > rollover_window {
+                continue;
+            }
+
+            info!("Rolling {} to {}: flattening position", engine.active_symbol(), next_symbol);
+            engine.send_command(EngineCommand::FlattenPosition);
+
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                if engine.risk_manager().read().get_position() == 0 {
+                    break;
+                }
+            }
+
+            let channels = [StreamChannel::Ticker, StreamChannel::Orderbook { depth: 20 }, StreamChannel::FundingRate];
+            if let Err(e) = client.lock().await.subscribe_many(&[next_symbol.clone()], &channels).await {
+                error!("Failed to subscribe rollover contract {}: {}", next_symbol, e);
+                return;
+            }
+
+            engine.send_command(EngineCommand::SetSymbol(next_symbol.clone()));
+            engine.exit_maintenance_mode();
+            info!("Rolled {} onto {}", engine.active_symbol(), next_symbol);
+            return;
+        }
+    });
+}
+
+/// Replay `data` through a [`HistoricalReplayClient`], driving the exact same
+/// `EngineBuilder`/`TradingEngine`/callback wiring as live trading, then print
+/// the resulting stats as a backtest report.
+async fn run_backtest(config: &AppConfig, symbol: Symbol, data: &str, balance: f64) -> anyhow::Result<()> {
+    info!("Mode: Backtest");
+    info!("Replaying tape: {}", data);
+
+    let engine = EngineBuilder::new()
+        .symbol(symbol.clone())
+        .exchange("historical_replay")
+        .strategy_params(config.to_strategy_params())
+        .risk_limits(config.to_risk_limits())
+        .fee_model(config.to_fee_model())
+        .enable_trading(true)
+        .build();
+
+    let mut client = HistoricalReplayClient::from_jsonl_file(symbol.clone(), balance, data)?;
+
+    let engine_ref = Arc::new(engine);
+    let engine_clone = engine_ref.clone();
+
+    client.set_callbacks(ExchangeCallbacks {
+        on_tick: Some(Box::new(move |_symbol, tick| {
+            engine_clone.on_tick(tick);
+        })),
+        on_trade: Some(Box::new(|trade| {
+            info!(
+                "Trade: {} {} @ {} qty={}",
+                trade.symbol,
+                trade.side,
+                from_price(trade.price),
+                from_qty(trade.quantity)
+            );
+        })),
+        ..Default::default()
+    });
+
+    client.connect().await?;
+    engine_ref.start(false).await?;
+    engine_ref.enable_trading();
+
+    info!("Replaying {} events...", client.event_count());
+    client.run_to_completion().await;
+
     engine_ref.disable_trading();
     engine_ref.stop().await;
     client.disconnect().await?;
 
     let stats = engine_ref.stats();
+    info!("Backtest complete.");
     info!("Final Statistics:");
     info!("  Ticks processed: {}", stats.ticks_processed);
     info!("  Orders sent: {}", stats.orders_sent);
     info!("  Trades executed: {}", stats.trades_executed);
+    info!("  Final balance: {:.2}", client.balance());
 
-    info!("Shutdown complete.");
     Ok(())
 }
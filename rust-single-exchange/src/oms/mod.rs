@@ -0,0 +1,201 @@
+//! Order lifecycle management
+//!
+//! `Order`/`OrderStatus` can represent a terminal state, but nothing in `core::types`
+//! actually drives an order *into* one: `TimeInForce` semantics and wall-clock TTL
+//! expiry have to be enforced by whoever owns the order set. `OrderStore` is that
+//! owner — it holds the currently active orders keyed by `OrderId`, applies those
+//! lifecycle rules, and prunes terminal orders off on every tick.
+
+use crate::core::types::*;
+use std::collections::HashMap;
+
+/// An order tracked by `OrderStore`, with an optional wall-clock TTL layered on top
+/// of its exchange-reported `status`/`time_in_force`
+#[derive(Debug, Clone)]
+pub struct ManagedOrder {
+    pub order: Order,
+    /// Nanoseconds after `order.timestamp` at which a `Gtc` order auto-expires;
+    /// `None` means it lives until explicitly filled/canceled/rejected
+    pub ttl_nanos: Option<u64>,
+}
+
+/// Owns the set of currently active orders and enforces the lifecycle transitions
+/// `TimeInForce` and TTL expiry imply but `Order` alone can't express
+#[derive(Debug, Default)]
+pub struct OrderStore {
+    orders: HashMap<OrderId, ManagedOrder>,
+    expired: u64,
+    pruned: u64,
+}
+
+impl OrderStore {
+    pub fn new() -> Self {
+        OrderStore::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    pub fn get(&self, id: OrderId) -> Option<&Order> {
+        self.orders.get(&id).map(|managed| &managed.order)
+    }
+
+    /// Total orders auto-expired so far, via either TTL or a non-`Gtc` `TimeInForce`
+    /// that wasn't immediately (fully) satisfied
+    pub fn expired_count(&self) -> u64 {
+        self.expired
+    }
+
+    /// Total orders dropped from the store so far because they reached a terminal status
+    pub fn pruned_count(&self) -> u64 {
+        self.pruned
+    }
+
+    /// Insert or replace the order tracked under `order.id`, immediately applying the
+    /// `TimeInForce` rule: `Ioc`/`Fok` orders left with quantity remaining are
+    /// canceled, and a `Gtx` (post-only) order left unfilled is expired, since none of
+    /// them are meant to rest in the book past their initial attempt. Only `Gtc`
+    /// orders are left active and eligible for the `ttl_nanos` TTL.
+    pub fn upsert(&mut self, order: Order, ttl_nanos: Option<u64>) {
+        let mut managed = ManagedOrder { order, ttl_nanos };
+        self.apply_time_in_force(&mut managed);
+        self.orders.insert(managed.order.id, managed);
+    }
+
+    fn apply_time_in_force(&mut self, managed: &mut ManagedOrder) {
+        if !managed.order.is_active() || managed.order.remaining() == 0 {
+            return;
+        }
+
+        let new_status = match managed.order.time_in_force {
+            TimeInForce::Ioc => Some(OrderStatus::Canceled),
+            TimeInForce::Fok => Some(OrderStatus::Canceled),
+            TimeInForce::Gtx => Some(OrderStatus::Expired),
+            TimeInForce::Gtc | TimeInForce::Gtd => None,
+        };
+
+        if let Some(status) = new_status {
+            managed.order.status = status;
+            if status == OrderStatus::Expired {
+                self.expired += 1;
+            }
+        }
+    }
+
+    /// Expire any `Gtc` order whose TTL has elapsed as of `now`, then drop every
+    /// order that is no longer active (terminal status or just-expired). Call once
+    /// per tick.
+    pub fn retain_active(&mut self, now: Timestamp) {
+        let len_before = self.orders.len();
+        let mut newly_expired = 0u64;
+
+        self.orders.retain(|_, managed| {
+            if managed.order.is_active() {
+                if let Some(ttl) = managed.ttl_nanos {
+                    if managed.order.timestamp.saturating_add(ttl) < now {
+                        managed.order.status = OrderStatus::Expired;
+                        newly_expired += 1;
+                    }
+                }
+            }
+            managed.order.is_active()
+        });
+
+        self.expired += newly_expired;
+        self.pruned += (len_before - self.orders.len()) as u64;
+    }
+
+    /// Fold `other` into this store, last-writer-wins per order id, then re-apply the
+    /// same expiry/fulfillment pruning. Used to reconcile an order snapshot pulled
+    /// after an exchange session reconnects.
+    pub fn merge(&mut self, other: OrderStore, now: Timestamp) {
+        for (id, managed) in other.orders {
+            self.orders.insert(id, managed);
+        }
+        self.retain_active(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_order(id: OrderId, time_in_force: TimeInForce, filled_qty: Quantity) -> Order {
+        let mut order = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(1.0),
+        );
+        order.id = id;
+        order.time_in_force = time_in_force;
+        order.filled_qty = filled_qty;
+        if order.remaining() == 0 {
+            order.status = OrderStatus::Filled;
+        }
+        order
+    }
+
+    #[test]
+    fn test_ioc_order_left_unfilled_is_canceled() {
+        let mut store = OrderStore::new();
+        store.upsert(new_order(1, TimeInForce::Ioc, to_qty(0.3)), None);
+
+        assert_eq!(store.get(1).unwrap().status, OrderStatus::Canceled);
+    }
+
+    #[test]
+    fn test_gtc_order_survives_registration() {
+        let mut store = OrderStore::new();
+        store.upsert(new_order(1, TimeInForce::Gtc, 0), Some(1_000));
+
+        assert_eq!(store.get(1).unwrap().status, OrderStatus::New);
+        assert_eq!(store.expired_count(), 0);
+    }
+
+    #[test]
+    fn test_ttl_expiry_prunes_on_retain() {
+        let mut store = OrderStore::new();
+        let mut order = new_order(1, TimeInForce::Gtc, 0);
+        order.timestamp = 1_000;
+        store.upsert(order, Some(500));
+
+        store.retain_active(1_600);
+
+        assert!(store.get(1).is_none());
+        assert_eq!(store.expired_count(), 1);
+        assert_eq!(store.pruned_count(), 1);
+    }
+
+    #[test]
+    fn test_retain_active_drops_terminal_orders() {
+        let mut store = OrderStore::new();
+        store.upsert(new_order(1, TimeInForce::Gtc, to_qty(1.0)), None); // fully filled
+
+        assert_eq!(store.len(), 1);
+        store.retain_active(0);
+        assert!(store.is_empty());
+        assert_eq!(store.pruned_count(), 1);
+    }
+
+    #[test]
+    fn test_merge_is_last_writer_wins_and_reprunes() {
+        let mut primary = OrderStore::new();
+        primary.upsert(new_order(1, TimeInForce::Gtc, 0), None);
+
+        let mut snapshot = OrderStore::new();
+        snapshot.upsert(new_order(1, TimeInForce::Gtc, to_qty(1.0)), None); // now fully filled
+        snapshot.upsert(new_order(2, TimeInForce::Gtc, 0), None);
+
+        primary.merge(snapshot, 0);
+
+        assert!(primary.get(1).is_none()); // filled order pruned
+        assert!(primary.get(2).is_some());
+    }
+}
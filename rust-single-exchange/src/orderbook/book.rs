@@ -1,10 +1,23 @@
 //! Order book implementation with L2/L3 support
 
-use super::PriceLevel;
+use super::{BookCheckpoint, BookError, BookEvent, Fill, LevelUpdate, MarketSpec, OutEvent, PegOrder, PegReference, PriceLevel};
 use crate::core::types::*;
 use hashbrown::HashMap;
 use std::cmp::Reverse;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
+
+/// Outcome of checking a price level's front resting order for expiry, see
+/// [`OrderBook::drop_expired_front`].
+enum ExpiredFrontStatus {
+    /// The front order was removed (or was already gone); caller should re-check
+    /// the new front before matching.
+    Dropped,
+    /// The front order is expired but the per-call drop budget is exhausted —
+    /// caller must treat the level as unfillable rather than match against it.
+    Blocked,
+    /// The front order (if any) is not expired; caller may match against it.
+    Ready,
+}
 
 /// L2 Order Book - Price level aggregated
 pub struct OrderBook {
@@ -26,12 +39,41 @@ pub struct OrderBook {
 
     last_update: Timestamp,
     sequence: SequenceNum,
+
+    // Bounded queue of fill/out events produced by `match_order`
+    event_queue: VecDeque<BookEvent>,
+
+    // Monotonic counter for `LevelUpdate::seq`, local to this book and distinct
+    // from the exchange-assigned `sequence` above
+    level_seq: SequenceNum,
+    // Bounded queue of level-quantity changes, for the checkpoint/delta
+    // streaming protocol (`book_checkpoint`/`pop_level_update`)
+    level_updates: VecDeque<LevelUpdate>,
+
+    spec: MarketSpec,
+
+    // Oracle/mid-pegged orders, keyed by a synthetic id assigned on registration
+    pegs: HashMap<OrderId, PegOrder>,
+    // Each peg's last-inserted effective price, so `reprice_pegs` can remove the
+    // stale contribution before re-inserting at the new one
+    peg_price: HashMap<OrderId, Price>,
+    next_peg_id: OrderId,
 }
 
 impl OrderBook {
     pub const MAX_DEPTH: usize = 100;
+    /// Oldest events are dropped once the queue reaches this size
+    pub const EVENT_QUEUE_CAPACITY: usize = 4096;
+    /// Oldest level updates are dropped once the queue reaches this size
+    pub const LEVEL_UPDATE_QUEUE_CAPACITY: usize = 4096;
 
+    /// Build a book with a permissive [`MarketSpec`] (tick/lot of 1 native unit, no
+    /// minimum) — use [`Self::with_spec`] to enforce a symbol's real increments.
     pub fn new(symbol: Symbol) -> Self {
+        Self::with_spec(symbol, MarketSpec::default())
+    }
+
+    pub fn with_spec(symbol: Symbol, spec: MarketSpec) -> Self {
         OrderBook {
             symbol,
             bids: BTreeMap::new(),
@@ -42,62 +84,117 @@ impl OrderBook {
             cache_dirty: true,
             last_update: 0,
             sequence: 0,
+            event_queue: VecDeque::new(),
+            level_seq: 0,
+            level_updates: VecDeque::new(),
+            spec,
+            pegs: HashMap::new(),
+            peg_price: HashMap::new(),
+            next_peg_id: 0,
         }
     }
 
+    pub fn spec(&self) -> MarketSpec {
+        self.spec
+    }
+
+    /// Round `price` down to the nearest tick this book accepts
+    pub fn round_to_tick(&self, price: Price) -> Price {
+        self.spec.round_to_tick(price)
+    }
+
+    /// Round `quantity` down to the nearest lot this book accepts
+    pub fn round_to_lot(&self, quantity: Quantity) -> Quantity {
+        self.spec.round_to_lot(quantity)
+    }
+
     // ========================================================================
     // L2 Updates
     // ========================================================================
 
     /// Update bid at price level
-    pub fn update_bid(&mut self, price: Price, quantity: Quantity) {
+    pub fn update_bid(&mut self, price: Price, quantity: Quantity) -> Result<(), BookError> {
+        self.spec.validate_price(price)?;
         if quantity == 0 {
             self.bids.remove(&Reverse(price));
         } else {
+            self.spec.validate_quantity(quantity)?;
             self.bids.insert(Reverse(price), PriceLevel::new(price, quantity));
         }
+        Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Buy, price, quantity);
         self.cache_dirty = true;
         self.last_update = now_nanos();
+        Ok(())
     }
 
     /// Update ask at price level
-    pub fn update_ask(&mut self, price: Price, quantity: Quantity) {
+    pub fn update_ask(&mut self, price: Price, quantity: Quantity) -> Result<(), BookError> {
+        self.spec.validate_price(price)?;
         if quantity == 0 {
             self.asks.remove(&price);
         } else {
+            self.spec.validate_quantity(quantity)?;
             self.asks.insert(price, PriceLevel::new(price, quantity));
         }
+        Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Sell, price, quantity);
         self.cache_dirty = true;
         self.last_update = now_nanos();
+        Ok(())
     }
 
     /// Clear all bids
     pub fn clear_bids(&mut self) {
+        for (Reverse(price), _) in self.bids.iter() {
+            Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Buy, *price, 0);
+        }
         self.bids.clear();
         self.cache_dirty = true;
     }
 
     /// Clear all asks
     pub fn clear_asks(&mut self) {
+        for (price, _) in self.asks.iter() {
+            Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Sell, *price, 0);
+        }
         self.asks.clear();
         self.cache_dirty = true;
     }
 
-    /// Apply full snapshot
-    pub fn apply_snapshot(&mut self, bids: Vec<(Price, Quantity)>, asks: Vec<(Price, Quantity)>) {
+    /// Apply full snapshot. Validated up front: if any level violates the book's
+    /// `MarketSpec` the snapshot is rejected in full and the existing book is left
+    /// untouched, rather than applying a partially-invalid replacement.
+    pub fn apply_snapshot(
+        &mut self,
+        bids: Vec<(Price, Quantity)>,
+        asks: Vec<(Price, Quantity)>,
+    ) -> Result<(), BookError> {
+        for &(price, qty) in bids.iter().chain(asks.iter()) {
+            self.spec.validate_price(price)?;
+            self.spec.validate_quantity(qty)?;
+        }
+
+        for (Reverse(price), _) in self.bids.iter() {
+            Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Buy, *price, 0);
+        }
+        for (price, _) in self.asks.iter() {
+            Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Sell, *price, 0);
+        }
         self.bids.clear();
         self.asks.clear();
 
         for (price, qty) in bids {
             self.bids.insert(Reverse(price), PriceLevel::new(price, qty));
+            Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Buy, price, qty);
         }
 
         for (price, qty) in asks {
             self.asks.insert(price, PriceLevel::new(price, qty));
+            Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Sell, price, qty);
         }
 
         self.cache_dirty = true;
         self.last_update = now_nanos();
+        Ok(())
     }
 
     // ========================================================================
@@ -105,45 +202,62 @@ impl OrderBook {
     // ========================================================================
 
     /// Add individual order
-    pub fn add_order(&mut self, order: Order) {
+    pub fn add_order(&mut self, order: Order) -> Result<(), BookError> {
+        self.spec.validate_price(order.price)?;
+        self.spec.validate_quantity(order.quantity)?;
+
         let price = order.price;
         let qty = order.quantity;
         let side = order.side;
+        let id = order.id;
 
         self.orders.insert(order.id, order);
 
         match side {
             Side::Buy => {
-                self.bids
+                let level = self
+                    .bids
                     .entry(Reverse(price))
                     .and_modify(|level| {
                         level.quantity += qty;
                         level.order_count += 1;
+                        level.last_update = now_nanos();
                     })
                     .or_insert_with(|| PriceLevel {
                         price,
                         quantity: qty,
                         order_count: 1,
                         last_update: now_nanos(),
+                        orders: VecDeque::new(),
                     });
+                level.orders.push_back(id);
+                let new_qty = level.quantity;
+                Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Buy, price, new_qty);
             }
             Side::Sell => {
-                self.asks
+                let level = self
+                    .asks
                     .entry(price)
                     .and_modify(|level| {
                         level.quantity += qty;
                         level.order_count += 1;
+                        level.last_update = now_nanos();
                     })
                     .or_insert_with(|| PriceLevel {
                         price,
                         quantity: qty,
                         order_count: 1,
                         last_update: now_nanos(),
+                        orders: VecDeque::new(),
                     });
+                level.orders.push_back(id);
+                let new_qty = level.quantity;
+                Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Sell, price, new_qty);
             }
         }
 
         self.cache_dirty = true;
+        Ok(())
     }
 
     /// Remove individual order
@@ -157,18 +271,26 @@ impl OrderBook {
                     if let Some(level) = self.bids.get_mut(&Reverse(price)) {
                         level.quantity -= qty;
                         level.order_count -= 1;
-                        if level.quantity <= 0 || level.order_count == 0 {
+                        level.orders.retain(|id| *id != order_id);
+                        let emptied = level.quantity <= 0 || level.order_count == 0;
+                        let new_qty = if emptied { 0 } else { level.quantity };
+                        if emptied {
                             self.bids.remove(&Reverse(price));
                         }
+                        Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Buy, price, new_qty);
                     }
                 }
                 Side::Sell => {
                     if let Some(level) = self.asks.get_mut(&price) {
                         level.quantity -= qty;
                         level.order_count -= 1;
-                        if level.quantity <= 0 || level.order_count == 0 {
+                        level.orders.retain(|id| *id != order_id);
+                        let emptied = level.quantity <= 0 || level.order_count == 0;
+                        let new_qty = if emptied { 0 } else { level.quantity };
+                        if emptied {
                             self.asks.remove(&price);
                         }
+                        Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Sell, price, new_qty);
                     }
                 }
             }
@@ -179,6 +301,534 @@ impl OrderBook {
         None
     }
 
+    /// Insert `order` as a post-only maker, guaranteeing it never takes liquidity.
+    /// A buy crossing the current `best_ask()` (or a sell crossing `best_bid()`) is
+    /// rejected with [`BookError::CrossingPostOnly`] when `slide` is `false`; with
+    /// `slide` is `true` it is instead repriced to the best non-crossing tick —
+    /// `best_ask - tick_size` for a buy, `best_bid + tick_size` for a sell — mirroring
+    /// the `post_only_slide_limit` behavior Mango's perp book uses for its post-only
+    /// mode. Returns the price the order actually rests at.
+    pub fn place_post_only(&mut self, mut order: Order, slide: bool) -> Result<Price, BookError> {
+        let tick_size = self.spec.tick_size.max(1);
+
+        let crossing = match order.side {
+            Side::Buy => self.best_ask().is_some_and(|ask| order.price >= ask),
+            Side::Sell => self.best_bid().is_some_and(|bid| order.price <= bid),
+        };
+
+        if crossing {
+            if !slide {
+                return Err(BookError::CrossingPostOnly { price: order.price });
+            }
+            order.price = match order.side {
+                Side::Buy => self.best_ask().expect("crossing implies a best ask") - tick_size,
+                Side::Sell => self.best_bid().expect("crossing implies a best bid") + tick_size,
+            };
+        }
+
+        let resting_price = order.price;
+        self.add_order(order)?;
+        Ok(resting_price)
+    }
+
+    // ========================================================================
+    // Matching
+    // ========================================================================
+
+    /// Resting orders dropped per [`Self::match_order`]/[`Self::sweep_expired`] call
+    /// once they're found stale, so a single call can't blow up latency purging a
+    /// backlog of expired orders (mirroring Mango's sweep cap)
+    pub const DROP_EXPIRED_ORDER_LIMIT: usize = 5;
+
+    /// Match `incoming` against the resting book in price-time priority, filling
+    /// against the opposing side best-price-first and, within a level, oldest order
+    /// first. Market orders use an implicit limit of `i64::MAX` for buys and `1` for
+    /// sells so they sweep the whole book; limit orders stop once `incoming.price`
+    /// is no longer crossed. Fully-filled resting orders (and any level they empty)
+    /// are removed from the book. Every match pushes a `Fill` onto the bounded
+    /// `event_queue`, as does every resting order fully removed by the match —
+    /// whether filled or found expired along the way (capped at
+    /// `DROP_EXPIRED_ORDER_LIMIT` per call). Returns the taker's final state
+    /// alongside its fills: a `Fok` order that can't be fully matched as of `now` is
+    /// rejected outright (no fills, nothing inserted); an `Ioc` order left with a
+    /// remainder after one pass is canceled.
+    pub fn match_order(&mut self, mut incoming: Order, now: Timestamp) -> (Order, Vec<Fill>) {
+        let limit = match (incoming.order_type, incoming.side) {
+            (OrderType::Market, Side::Buy) => i64::MAX,
+            (OrderType::Market, Side::Sell) => 1,
+            (_, _) => incoming.price,
+        };
+
+        if incoming.time_in_force == TimeInForce::Fok {
+            let fillable = match incoming.side {
+                Side::Buy => self.fillable_quantity(&self.asks, incoming.remaining(), limit, now, |p, l| p <= l),
+                Side::Sell => {
+                    self.fillable_quantity(&self.bids, incoming.remaining(), limit, now, |Reverse(p), l| p >= l)
+                }
+            };
+            if fillable < incoming.remaining() {
+                incoming.status = OrderStatus::Rejected;
+                return (incoming, Vec::new());
+            }
+        }
+
+        let mut fills = Vec::new();
+        let mut dropped = 0usize;
+
+        match incoming.side {
+            Side::Buy => {
+                while incoming.remaining() > 0 {
+                    let Some((&ask_price, _)) = self.asks.iter().next() else {
+                        break;
+                    };
+                    if ask_price > limit {
+                        break;
+                    }
+                    if !self.fill_level_buy(&mut incoming, ask_price, &mut fills, now, &mut dropped) {
+                        break;
+                    }
+                }
+            }
+            Side::Sell => {
+                while incoming.remaining() > 0 {
+                    let Some((&Reverse(bid_price), _)) = self.bids.iter().next() else {
+                        break;
+                    };
+                    if bid_price < limit {
+                        break;
+                    }
+                    if !self.fill_level_sell(&mut incoming, bid_price, &mut fills, now, &mut dropped) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if incoming.time_in_force == TimeInForce::Ioc && incoming.remaining() > 0 {
+            incoming.status = OrderStatus::Canceled;
+        } else if incoming.remaining() == 0 {
+            incoming.status = OrderStatus::Filled;
+        } else if !fills.is_empty() {
+            incoming.status = OrderStatus::PartiallyFilled;
+        }
+
+        self.cache_dirty = true;
+        (incoming, fills)
+    }
+
+    /// Sum of level quantity reachable on `levels` up to `target`, stopping once the
+    /// price no longer satisfies `within_limit` — the same best-price-first
+    /// traversal `vwap_ask`/`vwap_bid` use, but reporting reachable quantity instead
+    /// of an average price. Used to pre-check `Fok` feasibility without mutating the
+    /// book. Excludes resting orders already expired as of `now` so a level's
+    /// unswept expired quantity can't make a `Fok` order pass precheck only to be
+    /// dropped (not filled) once `drop_expired_front` sweeps it during the real match.
+    fn fillable_quantity<K: Copy>(
+        &self,
+        levels: &BTreeMap<K, PriceLevel>,
+        target: Quantity,
+        limit: Price,
+        now: Timestamp,
+        within_limit: impl Fn(K, Price) -> bool,
+    ) -> Quantity {
+        let mut remaining = target;
+        for (&key, level) in levels.iter() {
+            if !within_limit(key, limit) {
+                break;
+            }
+            let level_fillable: Quantity = level
+                .orders
+                .iter()
+                .filter_map(|id| self.orders.get(id))
+                .filter(|order| !order.is_expired(now))
+                .map(|order| order.remaining())
+                .sum();
+            remaining -= remaining.min(level_fillable);
+            if remaining <= 0 {
+                break;
+            }
+        }
+        target - remaining
+    }
+
+    /// Drop expired resting orders lazily as they're encountered at the front of a
+    /// price level, up to the shared per-call `DROP_EXPIRED_ORDER_LIMIT` budget in
+    /// `dropped`. Distinguishes "dropped" from "expired but budget exhausted" so a
+    /// caller never falls through to matching a known-expired maker just because the
+    /// drop budget ran out — that would let a taker fill against expired quantity
+    /// that `fillable_quantity`'s `Fok` precheck already excluded.
+    fn drop_expired_front(
+        orders: &mut HashMap<OrderId, Order>,
+        level: &mut PriceLevel,
+        now: Timestamp,
+        dropped: &mut usize,
+        event_queue: &mut VecDeque<BookEvent>,
+    ) -> ExpiredFrontStatus {
+        let Some(&maker_id) = level.orders.front() else {
+            return ExpiredFrontStatus::Ready;
+        };
+        let Some(maker) = orders.get(&maker_id) else {
+            level.orders.pop_front();
+            return ExpiredFrontStatus::Dropped;
+        };
+        if !maker.is_expired(now) {
+            return ExpiredFrontStatus::Ready;
+        }
+        if *dropped >= Self::DROP_EXPIRED_ORDER_LIMIT {
+            return ExpiredFrontStatus::Blocked;
+        }
+
+        level.quantity -= maker.remaining();
+        level.order_count -= 1;
+        orders.remove(&maker_id);
+        level.orders.pop_front();
+        *dropped += 1;
+
+        Self::push_event(event_queue, BookEvent::Out(OutEvent { order_id: maker_id, ts: now }));
+        ExpiredFrontStatus::Dropped
+    }
+
+    /// Drain fills from the best ask level at `price` against `incoming` until the
+    /// level is exhausted or `incoming` is fully filled. Returns `false` if the level
+    /// no longer exists (nothing left to do).
+    fn fill_level_buy(
+        &mut self,
+        incoming: &mut Order,
+        price: Price,
+        fills: &mut Vec<Fill>,
+        now: Timestamp,
+        dropped: &mut usize,
+    ) -> bool {
+        let Some(level) = self.asks.get_mut(&price) else {
+            return false;
+        };
+
+        while incoming.remaining() > 0 {
+            match Self::drop_expired_front(&mut self.orders, level, now, dropped, &mut self.event_queue) {
+                ExpiredFrontStatus::Dropped => continue,
+                ExpiredFrontStatus::Blocked => break,
+                ExpiredFrontStatus::Ready => {}
+            }
+
+            let Some(&maker_id) = level.orders.front() else {
+                break;
+            };
+            let Some(maker) = self.orders.get_mut(&maker_id) else {
+                level.orders.pop_front();
+                continue;
+            };
+
+            let qty = incoming.remaining().min(maker.remaining());
+            let ts = now_nanos();
+
+            incoming.filled_qty += qty;
+            maker.filled_qty += qty;
+            level.quantity -= qty;
+            level.last_update = ts;
+
+            Self::push_event(
+                &mut self.event_queue,
+                BookEvent::Fill(Fill { maker_id, taker_id: incoming.id, price, quantity: qty, ts }),
+            );
+            fills.push(Fill { maker_id, taker_id: incoming.id, price, quantity: qty, ts });
+
+            if maker.remaining() == 0 {
+                maker.status = OrderStatus::Filled;
+                self.orders.remove(&maker_id);
+                level.orders.pop_front();
+                level.order_count -= 1;
+                Self::push_event(&mut self.event_queue, BookEvent::Out(OutEvent { order_id: maker_id, ts }));
+            }
+        }
+
+        let remaining_qty = if level.orders.is_empty() {
+            self.asks.remove(&price);
+            0
+        } else {
+            level.quantity
+        };
+        Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Sell, price, remaining_qty);
+        true
+    }
+
+    /// Symmetric to [`Self::fill_level_buy`] for the bid side.
+    fn fill_level_sell(
+        &mut self,
+        incoming: &mut Order,
+        price: Price,
+        fills: &mut Vec<Fill>,
+        now: Timestamp,
+        dropped: &mut usize,
+    ) -> bool {
+        let Some(level) = self.bids.get_mut(&Reverse(price)) else {
+            return false;
+        };
+
+        while incoming.remaining() > 0 {
+            match Self::drop_expired_front(&mut self.orders, level, now, dropped, &mut self.event_queue) {
+                ExpiredFrontStatus::Dropped => continue,
+                ExpiredFrontStatus::Blocked => break,
+                ExpiredFrontStatus::Ready => {}
+            }
+
+            let Some(&maker_id) = level.orders.front() else {
+                break;
+            };
+            let Some(maker) = self.orders.get_mut(&maker_id) else {
+                level.orders.pop_front();
+                continue;
+            };
+
+            let qty = incoming.remaining().min(maker.remaining());
+            let ts = now_nanos();
+
+            incoming.filled_qty += qty;
+            maker.filled_qty += qty;
+            level.quantity -= qty;
+            level.last_update = ts;
+
+            Self::push_event(
+                &mut self.event_queue,
+                BookEvent::Fill(Fill { maker_id, taker_id: incoming.id, price, quantity: qty, ts }),
+            );
+            fills.push(Fill { maker_id, taker_id: incoming.id, price, quantity: qty, ts });
+
+            if maker.remaining() == 0 {
+                maker.status = OrderStatus::Filled;
+                self.orders.remove(&maker_id);
+                level.orders.pop_front();
+                level.order_count -= 1;
+                Self::push_event(&mut self.event_queue, BookEvent::Out(OutEvent { order_id: maker_id, ts }));
+            }
+        }
+
+        let remaining_qty = if level.orders.is_empty() {
+            self.bids.remove(&Reverse(price));
+            0
+        } else {
+            level.quantity
+        };
+        Self::push_level_update(&mut self.level_updates, &mut self.level_seq, Side::Buy, price, remaining_qty);
+        true
+    }
+
+    /// Sweep expired resting orders off the front of every price level, up to
+    /// `DROP_EXPIRED_ORDER_LIMIT` total regardless of `limit` — call periodically
+    /// (independent of matching activity) to bound staleness even on quiet symbols.
+    /// `limit` is accepted for callers that want to cap work further than the
+    /// built-in budget; pass `DROP_EXPIRED_ORDER_LIMIT` to use the full budget.
+    pub fn sweep_expired(&mut self, now: Timestamp, limit: usize) {
+        let budget = limit.min(Self::DROP_EXPIRED_ORDER_LIMIT);
+        let mut dropped = 0usize;
+
+        let bid_prices: Vec<Reverse<Price>> = self.bids.keys().copied().collect();
+        for key in bid_prices {
+            if dropped >= budget {
+                break;
+            }
+            let Some(level) = self.bids.get_mut(&key) else { continue };
+            while dropped < budget
+                && Self::drop_expired_front(&mut self.orders, level, now, &mut dropped, &mut self.event_queue)
+            {}
+            if level.orders.is_empty() {
+                self.bids.remove(&key);
+            }
+        }
+
+        let ask_prices: Vec<Price> = self.asks.keys().copied().collect();
+        for key in ask_prices {
+            if dropped >= budget {
+                break;
+            }
+            let Some(level) = self.asks.get_mut(&key) else { continue };
+            while dropped < budget
+                && Self::drop_expired_front(&mut self.orders, level, now, &mut dropped, &mut self.event_queue)
+            {}
+            if level.orders.is_empty() {
+                self.asks.remove(&key);
+            }
+        }
+
+        if dropped > 0 {
+            self.cache_dirty = true;
+        }
+    }
+
+    /// Push an event onto the bounded queue, dropping the oldest entry if full.
+    /// Takes the queue directly (rather than `&mut self`) so it can be called while
+    /// another field (e.g. a `PriceLevel` borrowed out of `self.asks`/`self.bids`) is
+    /// already mutably borrowed.
+    fn push_event(event_queue: &mut VecDeque<BookEvent>, event: BookEvent) {
+        if event_queue.len() >= Self::EVENT_QUEUE_CAPACITY {
+            event_queue.pop_front();
+        }
+        event_queue.push_back(event);
+    }
+
+    /// Pop the oldest queued `Fill`/`Out` event, if any.
+    pub fn pop_event(&mut self) -> Option<BookEvent> {
+        self.event_queue.pop_front()
+    }
+
+    /// Number of events currently queued
+    pub fn event_count(&self) -> usize {
+        self.event_queue.len()
+    }
+
+    /// Bump `level_seq` and queue a [`LevelUpdate`] for `side`/`price`, dropping
+    /// the oldest queued update if `level_updates` is already at capacity.
+    fn push_level_update(
+        level_updates: &mut VecDeque<LevelUpdate>,
+        level_seq: &mut SequenceNum,
+        side: Side,
+        price: Price,
+        new_qty: Quantity,
+    ) {
+        *level_seq += 1;
+        if level_updates.len() >= Self::LEVEL_UPDATE_QUEUE_CAPACITY {
+            level_updates.pop_front();
+        }
+        level_updates.push_back(LevelUpdate { seq: *level_seq, side, price, new_qty });
+    }
+
+    /// Pop the oldest queued [`LevelUpdate`], if any.
+    pub fn pop_level_update(&mut self) -> Option<LevelUpdate> {
+        self.level_updates.pop_front()
+    }
+
+    /// Number of level updates currently queued
+    pub fn level_update_count(&self) -> usize {
+        self.level_updates.len()
+    }
+
+    /// Full L2 snapshot of the book, with the level-update sequence number
+    /// current as of this call. A subscriber syncs by fetching this, then
+    /// applying [`Self::pop_level_update`]s whose `seq` exceeds it, discarding
+    /// any at or below it.
+    pub fn book_checkpoint(&self) -> BookCheckpoint {
+        BookCheckpoint {
+            seq: self.level_seq,
+            bids: self.bids.iter().map(|(Reverse(price), level)| (*price, level.quantity)).collect(),
+            asks: self.asks.iter().map(|(price, level)| (*price, level.quantity)).collect(),
+        }
+    }
+
+    // ========================================================================
+    // Pegged orders
+    // ========================================================================
+
+    /// Resolve a [`PegReference`] to a concrete price: `Mid` reads the book's
+    /// current `mid_price()` (`None` if the book is one-sided or empty), `Oracle`
+    /// just returns the supplied value.
+    pub fn resolve_peg_reference(&self, reference: PegReference) -> Option<Price> {
+        match reference {
+            PegReference::Mid => self.mid_price(),
+            PegReference::Oracle(price) => Some(price),
+        }
+    }
+
+    /// Register a new pegged order. It contributes nothing to the book until the
+    /// next [`Self::reprice_pegs`] call establishes its first effective price.
+    pub fn add_peg_order(&mut self, peg: PegOrder) -> OrderId {
+        let id = self.next_peg_id;
+        self.next_peg_id += 1;
+        self.pegs.insert(id, peg);
+        id
+    }
+
+    /// Remove a pegged order and its current price-level contribution, if any.
+    pub fn remove_peg_order(&mut self, id: OrderId) -> Option<PegOrder> {
+        let peg = self.pegs.remove(&id)?;
+        if let Some(old_price) = self.peg_price.remove(&id) {
+            self.remove_peg_contribution(peg.side, old_price, peg.quantity);
+            self.cache_dirty = true;
+        }
+        Some(peg)
+    }
+
+    /// The effective price a peg order would have at `reference`: `reference +
+    /// peg_offset`, clamped so it never moves past `peg_limit` in the order's favor.
+    fn peg_effective_price(reference: Price, peg: &PegOrder) -> Price {
+        let target = reference + peg.peg_offset;
+        match peg.side {
+            Side::Buy => target.min(peg.peg_limit),
+            Side::Sell => target.max(peg.peg_limit),
+        }
+    }
+
+    /// Recompute every pegged order's effective price against `reference`, moving
+    /// its contribution to the book's bid/ask levels accordingly. Call this whenever
+    /// the reference (mid or oracle) moves.
+    pub fn reprice_pegs(&mut self, reference: Price) {
+        let ids: Vec<OrderId> = self.pegs.keys().copied().collect();
+
+        for id in ids {
+            let peg = *self.pegs.get(&id).expect("id collected from self.pegs.keys() above");
+            let new_price = Self::peg_effective_price(reference, &peg);
+
+            if let Some(old_price) = self.peg_price.remove(&id) {
+                if old_price == new_price {
+                    self.peg_price.insert(id, old_price);
+                    continue;
+                }
+                self.remove_peg_contribution(peg.side, old_price, peg.quantity);
+            }
+
+            self.add_peg_contribution(peg.side, new_price, peg.quantity);
+            self.peg_price.insert(id, new_price);
+        }
+
+        self.cache_dirty = true;
+    }
+
+    fn add_peg_contribution(&mut self, side: Side, price: Price, quantity: Quantity) {
+        match side {
+            Side::Buy => {
+                self.bids
+                    .entry(Reverse(price))
+                    .and_modify(|level| {
+                        level.quantity += quantity;
+                        level.order_count += 1;
+                        level.last_update = now_nanos();
+                    })
+                    .or_insert_with(|| PriceLevel::new(price, quantity));
+            }
+            Side::Sell => {
+                self.asks
+                    .entry(price)
+                    .and_modify(|level| {
+                        level.quantity += quantity;
+                        level.order_count += 1;
+                        level.last_update = now_nanos();
+                    })
+                    .or_insert_with(|| PriceLevel::new(price, quantity));
+            }
+        }
+    }
+
+    fn remove_peg_contribution(&mut self, side: Side, price: Price, quantity: Quantity) {
+        match side {
+            Side::Buy => {
+                if let Some(level) = self.bids.get_mut(&Reverse(price)) {
+                    level.quantity -= quantity;
+                    level.order_count -= 1;
+                    if level.quantity <= 0 || level.order_count == 0 {
+                        self.bids.remove(&Reverse(price));
+                    }
+                }
+            }
+            Side::Sell => {
+                if let Some(level) = self.asks.get_mut(&price) {
+                    level.quantity -= quantity;
+                    level.order_count -= 1;
+                    if level.quantity <= 0 || level.order_count == 0 {
+                        self.asks.remove(&price);
+                    }
+                }
+            }
+        }
+    }
+
     // ========================================================================
     // Queries
     // ========================================================================
@@ -335,11 +985,11 @@ impl OrderBook {
         self.ask_cache.clear();
 
         for (_, level) in self.bids.iter().take(Self::MAX_DEPTH) {
-            self.bid_cache.push(*level);
+            self.bid_cache.push(level.clone());
         }
 
         for (_, level) in self.asks.iter().take(Self::MAX_DEPTH) {
-            self.ask_cache.push(*level);
+            self.ask_cache.push(level.clone());
         }
 
         self.cache_dirty = false;
@@ -374,10 +1024,10 @@ mod tests {
     fn test_orderbook_basic() {
         let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
 
-        book.update_bid(to_price(50000.0), to_qty(1.0));
-        book.update_bid(to_price(49999.0), to_qty(2.0));
-        book.update_ask(to_price(50001.0), to_qty(1.5));
-        book.update_ask(to_price(50002.0), to_qty(2.5));
+        book.update_bid(to_price(50000.0), to_qty(1.0)).unwrap();
+        book.update_bid(to_price(49999.0), to_qty(2.0)).unwrap();
+        book.update_ask(to_price(50001.0), to_qty(1.5)).unwrap();
+        book.update_ask(to_price(50002.0), to_qty(2.5)).unwrap();
 
         assert_eq!(book.best_bid(), Some(to_price(50000.0)));
         assert_eq!(book.best_ask(), Some(to_price(50001.0)));
@@ -388,8 +1038,8 @@ mod tests {
     fn test_orderbook_imbalance() {
         let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
 
-        book.update_bid(to_price(100.0), to_qty(10.0));
-        book.update_ask(to_price(101.0), to_qty(5.0));
+        book.update_bid(to_price(100.0), to_qty(10.0)).unwrap();
+        book.update_ask(to_price(101.0), to_qty(5.0)).unwrap();
 
         let imbalance = book.imbalance(1);
         assert!(imbalance > 0.0); // More bids than asks
@@ -399,13 +1049,323 @@ mod tests {
     fn test_orderbook_vwap() {
         let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
 
-        book.update_ask(to_price(100.0), to_qty(1.0));
-        book.update_ask(to_price(101.0), to_qty(1.0));
-        book.update_ask(to_price(102.0), to_qty(1.0));
+        book.update_ask(to_price(100.0), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(101.0), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(102.0), to_qty(1.0)).unwrap();
 
         // VWAP to buy 2 units: (100*1 + 101*1) / 2 = 100.5
         let vwap = book.vwap_ask(to_qty(2.0)).unwrap();
         let expected = to_price(100.5);
         assert!((vwap - expected).abs() < to_price(0.01));
     }
+
+    fn resting_order(id: OrderId, side: Side, price: f64, qty: f64) -> Order {
+        let mut order = Order::new(Symbol::new("BTCUSDT"), side, OrderType::Limit, to_price(price), to_qty(qty));
+        order.id = id;
+        order
+    }
+
+    #[test]
+    fn test_match_order_respects_fifo_within_level() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+        book.add_order(resting_order(2, Side::Sell, 100.0, 1.0)).unwrap();
+
+        let taker = resting_order(3, Side::Buy, 100.0, 1.0);
+        let (_, fills) = book.match_order(taker, 0);
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].maker_id, 1); // first-arrived order fills first
+        assert!(book.orders.contains_key(&2));
+        assert!(book.orders.get(&1).is_none());
+    }
+
+    #[test]
+    fn test_match_order_partial_fill_leaves_remainder_resting() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 2.0)).unwrap();
+
+        let (_, fills) = book.match_order(resting_order(2, Side::Buy, 100.0, 1.0), 0);
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, to_qty(1.0));
+        let remaining = book.orders.get(&1).unwrap();
+        assert_eq!(remaining.remaining(), to_qty(1.0));
+        assert_eq!(book.best_ask_qty(), Some(to_qty(1.0)));
+    }
+
+    #[test]
+    fn test_market_buy_sweeps_multiple_levels() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+        book.add_order(resting_order(2, Side::Sell, 101.0, 1.0)).unwrap();
+
+        let mut taker = resting_order(3, Side::Buy, 0.0, 2.0);
+        taker.order_type = OrderType::Market;
+        let (_, fills) = book.match_order(taker, 0);
+
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].price, to_price(100.0));
+        assert_eq!(fills[1].price, to_price(101.0));
+        assert!(book.asks.is_empty());
+    }
+
+    #[test]
+    fn test_match_order_emits_fill_and_out_events() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+
+        book.match_order(resting_order(2, Side::Buy, 100.0, 1.0), 0);
+
+        assert_eq!(book.event_count(), 2);
+        assert!(matches!(book.pop_event(), Some(BookEvent::Fill(_))));
+        assert!(matches!(book.pop_event(), Some(BookEvent::Out(_))));
+        assert!(book.pop_event().is_none());
+    }
+
+    #[test]
+    fn test_match_order_drops_expired_resting_orders_bounded_by_limit() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        for id in 1..=8 {
+            let mut order = resting_order(id, Side::Sell, 100.0, 1.0);
+            order.expires_at = Some(500);
+            book.add_order(order).unwrap();
+        }
+
+        // Tiny taker: once the 5-order drop budget is spent, it fills entirely
+        // against whatever is left at the front rather than triggering more drops.
+        let mut taker = resting_order(100, Side::Buy, 100.0, 0.1);
+        taker.order_type = OrderType::Market;
+        let (_, fills) = book.match_order(taker, 1_000);
+
+        // All 8 resting orders are expired, but only DROP_EXPIRED_ORDER_LIMIT (5) are
+        // swept in this single call; the taker's quantity is filled against the 6th.
+        assert_eq!(fills.len(), 1);
+        assert_eq!(book.orders.len(), 3);
+    }
+
+    #[test]
+    fn test_sweep_expired_removes_stale_orders_up_to_limit() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        for id in 1..=3 {
+            let mut order = resting_order(id, Side::Sell, 100.0, 1.0);
+            order.expires_at = Some(500);
+            book.add_order(order).unwrap();
+        }
+
+        book.sweep_expired(1_000, OrderBook::DROP_EXPIRED_ORDER_LIMIT);
+
+        assert!(book.orders.is_empty());
+        assert!(book.best_ask().is_none());
+    }
+
+    #[test]
+    fn test_ioc_order_left_with_remainder_is_canceled() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+
+        let mut taker = resting_order(2, Side::Buy, 100.0, 2.0);
+        taker.time_in_force = TimeInForce::Ioc;
+        let (taker, fills) = book.match_order(taker, 0);
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(taker.status, OrderStatus::Canceled);
+    }
+
+    #[test]
+    fn test_fok_order_rejected_without_mutating_book_when_unfillable() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+
+        let mut taker = resting_order(2, Side::Buy, 100.0, 2.0);
+        taker.time_in_force = TimeInForce::Fok;
+        let (taker, fills) = book.match_order(taker, 0);
+
+        assert!(fills.is_empty());
+        assert_eq!(taker.status, OrderStatus::Rejected);
+        assert_eq!(book.best_ask_qty(), Some(to_qty(1.0))); // untouched
+        assert!(book.orders.contains_key(&1));
+    }
+
+    #[test]
+    fn test_fok_order_fills_completely_when_book_can_cover_it() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 2.0)).unwrap();
+
+        let mut taker = resting_order(2, Side::Buy, 100.0, 1.5);
+        taker.time_in_force = TimeInForce::Fok;
+        let (taker, fills) = book.match_order(taker, 0);
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, to_qty(1.5));
+        assert_eq!(taker.status, OrderStatus::Filled);
+    }
+
+    #[test]
+    fn test_update_bid_rejects_price_off_tick() {
+        let mut book = OrderBook::with_spec(Symbol::new("BTCUSDT"), MarketSpec::new(to_price(0.5), 1, 0));
+
+        let err = book.update_bid(to_price(50000.25), to_qty(1.0)).unwrap_err();
+        assert!(matches!(err, BookError::InvalidTick { .. }));
+    }
+
+    #[test]
+    fn test_add_order_rejects_quantity_below_minimum() {
+        let mut book = OrderBook::with_spec(Symbol::new("BTCUSDT"), MarketSpec::new(1, 1, to_qty(1.0)));
+
+        let err = book.add_order(resting_order(1, Side::Sell, 100.0, 0.5)).unwrap_err();
+        assert!(matches!(err, BookError::BelowMinimumSize { .. }));
+    }
+
+    #[test]
+    fn test_round_to_tick_and_lot() {
+        let spec = MarketSpec::new(to_price(0.5), to_qty(0.1), 0);
+        let book = OrderBook::with_spec(Symbol::new("BTCUSDT"), spec);
+
+        assert_eq!(book.round_to_tick(to_price(50000.37)), to_price(50000.0));
+        assert_eq!(book.round_to_lot(to_qty(1.37)), to_qty(1.3));
+    }
+
+    #[test]
+    fn test_reprice_pegs_tracks_reference_and_is_visible_in_best_bid() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        let peg_id = book.add_peg_order(PegOrder {
+            side: Side::Buy,
+            peg_offset: -to_price(1.0),
+            peg_limit: to_price(60000.0),
+            quantity: to_qty(2.0),
+        });
+
+        book.reprice_pegs(to_price(50000.0));
+        assert_eq!(book.best_bid(), Some(to_price(49999.0)));
+        assert_eq!(book.best_bid_qty(), Some(to_qty(2.0)));
+
+        // Reference moves: the old level's contribution must be fully withdrawn
+        book.reprice_pegs(to_price(50100.0));
+        assert_eq!(book.best_bid(), Some(to_price(50099.0)));
+        assert!(book.bid_level(0).is_none() || book.bid_level(0).unwrap().price != to_price(49999.0));
+
+        book.remove_peg_order(peg_id);
+        book.reprice_pegs(to_price(50100.0));
+        assert_eq!(book.best_bid(), None);
+    }
+
+    #[test]
+    fn test_peg_limit_floors_an_ask_peg_against_a_crashing_reference() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_peg_order(PegOrder {
+            side: Side::Sell,
+            peg_offset: 0,
+            peg_limit: to_price(50010.0),
+            quantity: to_qty(1.0),
+        });
+
+        // Reference starts above the floor: the raw target is used as-is
+        book.reprice_pegs(to_price(50020.0));
+        assert_eq!(book.best_ask(), Some(to_price(50020.0)));
+
+        // Reference crashes far below peg_limit: the ask must not chase it down
+        book.reprice_pegs(to_price(100.0));
+        assert_eq!(book.best_ask(), Some(to_price(50010.0)));
+    }
+
+    #[test]
+    fn test_place_post_only_rejects_crossing_order_without_slide() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+
+        let crossing_buy = resting_order(2, Side::Buy, 100.0, 1.0);
+        let err = book.place_post_only(crossing_buy, false).unwrap_err();
+
+        assert!(matches!(err, BookError::CrossingPostOnly { .. }));
+        assert!(book.orders.get(&2).is_none());
+    }
+
+    #[test]
+    fn test_place_post_only_slides_crossing_buy_below_best_ask() {
+        let spec = MarketSpec::new(to_price(1.0), 1, 0);
+        let mut book = OrderBook::with_spec(Symbol::new("BTCUSDT"), spec);
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+
+        let crossing_buy = resting_order(2, Side::Buy, 100.0, 1.0);
+        let resting_price = book.place_post_only(crossing_buy, true).unwrap();
+
+        assert_eq!(resting_price, to_price(99.0));
+        assert_eq!(book.best_bid(), Some(to_price(99.0)));
+    }
+
+    #[test]
+    fn test_place_post_only_slides_crossing_sell_above_best_bid() {
+        let spec = MarketSpec::new(to_price(1.0), 1, 0);
+        let mut book = OrderBook::with_spec(Symbol::new("BTCUSDT"), spec);
+        book.add_order(resting_order(1, Side::Buy, 100.0, 1.0)).unwrap();
+
+        let crossing_sell = resting_order(2, Side::Sell, 100.0, 1.0);
+        let resting_price = book.place_post_only(crossing_sell, true).unwrap();
+
+        assert_eq!(resting_price, to_price(101.0));
+        assert_eq!(book.best_ask(), Some(to_price(101.0)));
+    }
+
+    #[test]
+    fn test_place_post_only_rests_at_submitted_price_when_non_crossing() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+
+        let non_crossing_buy = resting_order(2, Side::Buy, 99.0, 1.0);
+        let resting_price = book.place_post_only(non_crossing_buy, true).unwrap();
+
+        assert_eq!(resting_price, to_price(99.0));
+        assert_eq!(book.best_bid(), Some(to_price(99.0)));
+    }
+
+    #[test]
+    fn test_checkpoint_reflects_current_book_and_advances_seq_with_updates() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.update_bid(to_price(100.0), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(101.0), to_qty(2.0)).unwrap();
+
+        let checkpoint = book.book_checkpoint();
+        assert_eq!(checkpoint.bids, vec![(to_price(100.0), to_qty(1.0))]);
+        assert_eq!(checkpoint.asks, vec![(to_price(101.0), to_qty(2.0))]);
+
+        book.update_bid(to_price(100.0), to_qty(3.0)).unwrap();
+        let advanced = book.book_checkpoint();
+        assert!(advanced.seq > checkpoint.seq);
+    }
+
+    #[test]
+    fn test_level_updates_are_queued_and_drained_in_order() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.update_bid(to_price(100.0), to_qty(1.0)).unwrap();
+        book.update_bid(to_price(100.0), to_qty(0.0)).unwrap();
+
+        assert_eq!(book.level_update_count(), 2);
+
+        let first = book.pop_level_update().unwrap();
+        assert_eq!(first.side, Side::Buy);
+        assert_eq!(first.new_qty, to_qty(1.0));
+
+        let second = book.pop_level_update().unwrap();
+        assert_eq!(second.new_qty, 0);
+        assert!(second.seq > first.seq);
+        assert_eq!(book.level_update_count(), 0);
+    }
+
+    #[test]
+    fn test_remove_order_emits_level_update_reflecting_remaining_depth() {
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.add_order(resting_order(1, Side::Sell, 100.0, 1.0)).unwrap();
+        book.add_order(resting_order(2, Side::Sell, 100.0, 1.0)).unwrap();
+        while book.pop_level_update().is_some() {}
+
+        book.remove_order(1);
+        let update = book.pop_level_update().unwrap();
+        assert_eq!(update.side, Side::Sell);
+        assert_eq!(update.new_qty, to_qty(1.0)); // order 2 still resting
+
+        book.remove_order(2);
+        let update = book.pop_level_update().unwrap();
+        assert_eq!(update.new_qty, 0); // level now empty
+    }
 }
@@ -0,0 +1,183 @@
+//! Order book implementation
+
+use crate::core::types::*;
+use hashbrown::HashMap;
+use ordered_float::OrderedFloat;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+mod book;
+
+pub use book::OrderBook;
+
+/// Per-symbol price/size increments an [`OrderBook`] enforces on every mutation,
+/// mirroring the tick/lot/min-size checks an exchange would apply at the matching
+/// engine rather than trusting the feed.
+#[derive(Debug, Clone, Copy)]
+pub struct MarketSpec {
+    /// Prices must be an exact multiple of this
+    pub tick_size: Price,
+    /// Quantities must be an exact multiple of this
+    pub lot_size: Quantity,
+    /// Quantities below this are rejected outright
+    pub min_size: Quantity,
+}
+
+impl MarketSpec {
+    pub fn new(tick_size: Price, lot_size: Quantity, min_size: Quantity) -> Self {
+        MarketSpec { tick_size, lot_size, min_size }
+    }
+
+    /// Reject prices that aren't an exact multiple of `tick_size`
+    pub fn validate_price(&self, price: Price) -> Result<(), BookError> {
+        if self.tick_size > 0 && price % self.tick_size != 0 {
+            return Err(BookError::InvalidTick { price, tick_size: self.tick_size });
+        }
+        Ok(())
+    }
+
+    /// Reject quantities that are below `min_size` or not an exact multiple of `lot_size`
+    pub fn validate_quantity(&self, quantity: Quantity) -> Result<(), BookError> {
+        if quantity < self.min_size {
+            return Err(BookError::BelowMinimumSize { quantity, min_size: self.min_size });
+        }
+        if self.lot_size > 0 && quantity % self.lot_size != 0 {
+            return Err(BookError::InvalidLotSize { quantity, lot_size: self.lot_size });
+        }
+        Ok(())
+    }
+
+    /// Round `price` down to the nearest multiple of `tick_size`
+    pub fn round_to_tick(&self, price: Price) -> Price {
+        if self.tick_size <= 0 {
+            return price;
+        }
+        (price / self.tick_size) * self.tick_size
+    }
+
+    /// Round `quantity` down to the nearest multiple of `lot_size`
+    pub fn round_to_lot(&self, quantity: Quantity) -> Quantity {
+        if self.lot_size <= 0 {
+            return quantity;
+        }
+        (quantity / self.lot_size) * self.lot_size
+    }
+}
+
+impl Default for MarketSpec {
+    /// A permissive spec (tick/lot of 1 native unit, no minimum) for callers that
+    /// don't need validation, e.g. tests and benchmarks
+    fn default() -> Self {
+        MarketSpec { tick_size: 1, lot_size: 1, min_size: 0 }
+    }
+}
+
+/// Errors returned by [`OrderBook`]'s mutating methods when an update violates the
+/// book's [`MarketSpec`]
+#[derive(Debug, Clone, Copy, Error)]
+pub enum BookError {
+    #[error("price {price} is not a multiple of tick size {tick_size}")]
+    InvalidTick { price: Price, tick_size: Price },
+    #[error("quantity {quantity} is not a multiple of lot size {lot_size}")]
+    InvalidLotSize { quantity: Quantity, lot_size: Quantity },
+    #[error("quantity {quantity} is below minimum size {min_size}")]
+    BelowMinimumSize { quantity: Quantity, min_size: Quantity },
+    #[error("post-only order at price {price} would have crossed the book")]
+    CrossingPostOnly { price: Price },
+}
+
+/// A price level in the order book
+#[derive(Debug, Clone, Default)]
+pub struct PriceLevel {
+    pub price: Price,
+    pub quantity: Quantity,
+    pub order_count: u32,
+    pub last_update: Timestamp,
+    /// Resting order ids at this level in arrival order, for price-time priority
+    pub orders: std::collections::VecDeque<OrderId>,
+}
+
+impl PriceLevel {
+    pub fn new(price: Price, quantity: Quantity) -> Self {
+        PriceLevel {
+            price,
+            quantity,
+            order_count: 1,
+            last_update: now_nanos(),
+            orders: std::collections::VecDeque::new(),
+        }
+    }
+}
+
+/// A single match between a resting (maker) order and an incoming (taker) order
+#[derive(Debug, Clone, Copy)]
+pub struct Fill {
+    pub maker_id: OrderId,
+    pub taker_id: OrderId,
+    pub price: Price,
+    pub quantity: Quantity,
+    pub ts: Timestamp,
+}
+
+/// A resting order was fully removed from the book by matching
+#[derive(Debug, Clone, Copy)]
+pub struct OutEvent {
+    pub order_id: OrderId,
+    pub ts: Timestamp,
+}
+
+/// An event produced by [`OrderBook::match_order`], queued for consumers to drain
+#[derive(Debug, Clone, Copy)]
+pub enum BookEvent {
+    Fill(Fill),
+    Out(OutEvent),
+}
+
+/// Full L2 snapshot of a book, with the local level-update sequence number
+/// current as of the snapshot. A subscriber syncs by fetching this via
+/// [`OrderBook::book_checkpoint`], then draining [`OrderBook::pop_level_update`]
+/// and applying only updates whose `seq` is greater than this checkpoint's,
+/// discarding any at or below it.
+#[derive(Debug, Clone)]
+pub struct BookCheckpoint {
+    pub seq: SequenceNum,
+    pub bids: Vec<(Price, Quantity)>,
+    pub asks: Vec<(Price, Quantity)>,
+}
+
+/// A single price level's quantity changing, with `new_qty` of `0` meaning the
+/// level was deleted. `seq` is a monotonic counter local to this book
+/// (distinct from the exchange-assigned [`OrderBook::sequence`]), incremented
+/// once per emitted update, queued for consumers to drain via
+/// [`OrderBook::pop_level_update`].
+#[derive(Debug, Clone, Copy)]
+pub struct LevelUpdate {
+    pub seq: SequenceNum,
+    pub side: Side,
+    pub price: Price,
+    pub new_qty: Quantity,
+}
+
+/// What a [`PegOrder`]'s price floats relative to, resolved to a concrete [`Price`]
+/// by [`OrderBook::resolve_peg_reference`] before calling [`OrderBook::reprice_pegs`]
+#[derive(Debug, Clone, Copy)]
+pub enum PegReference {
+    /// The book's own `mid_price()`
+    Mid,
+    /// An externally supplied reference, e.g. an oracle price
+    Oracle(Price),
+}
+
+/// A resting order whose price tracks a reference (mid or oracle) instead of being
+/// fixed at submission time, as in Mango's oracle-peg orders. `peg_offset` is added
+/// to the reference to get the order's raw target price; `peg_limit` then bounds how
+/// far that target may drift in the order's favor (a buy peg is capped at
+/// `peg_limit`, a sell peg is floored at it), so a runaway reference can't push the
+/// order to an absurd price.
+#[derive(Debug, Clone, Copy)]
+pub struct PegOrder {
+    pub side: Side,
+    pub peg_offset: i64,
+    pub peg_limit: Price,
+    pub quantity: Quantity,
+}
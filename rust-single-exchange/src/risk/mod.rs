@@ -2,7 +2,7 @@
 
 use crate::core::types::*;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
 
 /// Risk limits configuration
 #[derive(Debug, Clone)]
@@ -17,6 +17,7 @@ pub struct RiskLimits {
     pub max_drawdown: f64,
     pub max_deviation_bps: f64,
     pub kill_switch_enabled: bool,
+    pub maintenance_margin_rate: f64,
 }
 
 impl Default for RiskLimits {
@@ -32,10 +33,31 @@ impl Default for RiskLimits {
             max_drawdown: 2000.0,
             max_deviation_bps: 100.0,
             kill_switch_enabled: true,
+            maintenance_margin_rate: 0.005,
         }
     }
 }
 
+/// Maker/taker/creator fee rates as fractions of notional (e.g. `0.0001` for
+/// 1bps), converted from [`crate::utils::config::FeeConfig`]'s fixed-point
+/// hundredth-of-a-basis-point units via `to_fee_model()`. `Default` is
+/// all-zero, matching this file's "0 disables the check/cost" convention.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeeModel {
+    pub maker_fee_rate: f64,
+    pub taker_fee_rate: f64,
+    pub creator_fee_rate: f64,
+}
+
+impl FeeModel {
+    /// Effective rate for a fill, combining the maker/taker rate with the
+    /// per-strategy creator/rebate rate
+    pub fn rate_for(&self, is_maker: bool) -> f64 {
+        let base = if is_maker { self.maker_fee_rate } else { self.taker_fee_rate };
+        base + self.creator_fee_rate
+    }
+}
+
 /// Risk check result
 #[derive(Debug, Clone)]
 pub struct RiskCheckResult {
@@ -74,6 +96,8 @@ pub enum RiskViolation {
     DrawdownLimit,
     PriceDeviation,
     KillSwitchActive,
+    ReduceOnlyMode,
+    InsufficientMargin,
 }
 
 /// Position tracking
@@ -122,6 +146,25 @@ pub struct RiskManager {
     // Kill switch
     kill_switch_active: AtomicBool,
 
+    // Reduce-only mode: reject anything that would grow absolute exposure
+    reduce_only: AtomicBool,
+
+    // Margin / leverage. `wallet_balance == 0.0` means margin tracking is
+    // unconfigured and `check_margin` is a no-op, matching the "0 disables
+    // this check" convention used by the other limits in this file.
+    leverage: f64,
+    wallet_balance: f64,
+    used_margin: f64,
+    liquidation_price: Option<Price>,
+
+    // Reference mid price for `check_price_deviation`; 0 means unset/disabled,
+    // matching the "0 disables this check" convention used elsewhere in this file
+    reference_price: AtomicI64,
+
+    // Fee schedule applied to realized PnL in `on_fill`; all-zero (default)
+    // disables fee deduction
+    fee_model: FeeModel,
+
     // Statistics
     orders_checked: AtomicU64,
     orders_rejected: AtomicU64,
@@ -138,11 +181,24 @@ impl RiskManager {
             daily_realized_pnl: 0.0,
             peak_equity: 0.0,
             kill_switch_active: AtomicBool::new(false),
+            reduce_only: AtomicBool::new(false),
+            leverage: 1.0,
+            wallet_balance: 0.0,
+            used_margin: 0.0,
+            liquidation_price: None,
+            reference_price: AtomicI64::new(0),
+            fee_model: FeeModel::default(),
             orders_checked: AtomicU64::new(0),
             orders_rejected: AtomicU64::new(0),
         }
     }
 
+    /// Configure the maker/taker/creator fee schedule `on_fill` charges
+    /// against realized PnL
+    pub fn set_fee_model(&mut self, fee_model: FeeModel) {
+        self.fee_model = fee_model;
+    }
+
     /// Check if order passes risk checks
     pub fn check_order(&self, order: &Order) -> RiskCheckResult {
         self.orders_checked.fetch_add(1, Ordering::Relaxed);
@@ -162,6 +218,24 @@ impl RiskManager {
             return result;
         }
 
+        // Check reduce-only mode
+        if let Some(result) = self.check_reduce_only(order) {
+            self.orders_rejected.fetch_add(1, Ordering::Relaxed);
+            return result;
+        }
+
+        // Check margin
+        if let Some(result) = self.check_margin(order) {
+            self.orders_rejected.fetch_add(1, Ordering::Relaxed);
+            return result;
+        }
+
+        // Check price deviation
+        if let Some(result) = self.check_price_deviation(order) {
+            self.orders_rejected.fetch_add(1, Ordering::Relaxed);
+            return result;
+        }
+
         // Check order size
         if let Some(result) = self.check_order_size(order) {
             self.orders_rejected.fetch_add(1, Ordering::Relaxed);
@@ -213,6 +287,99 @@ impl RiskManager {
         None
     }
 
+    /// While reduce-only mode is active, reject any order that would move the
+    /// position further from flat, but allow orders that work it back toward
+    /// flat. Lets an operator stop opening new risk while winding down existing
+    /// inventory instead of hard-killing the strategy via the kill switch.
+    fn check_reduce_only(&self, order: &Order) -> Option<RiskCheckResult> {
+        if !self.reduce_only.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let potential_pos = match order.side {
+            Side::Buy => self.position.quantity + order.quantity,
+            Side::Sell => self.position.quantity - order.quantity,
+        };
+
+        if potential_pos.abs() > self.position.quantity.abs() {
+            return Some(RiskCheckResult::fail(
+                RiskViolation::ReduceOnlyMode,
+                format!(
+                    "Reduce-only mode active: order would increase position from {} to {}",
+                    from_qty(self.position.quantity),
+                    from_qty(potential_pos)
+                ),
+            ));
+        }
+
+        None
+    }
+
+    /// Reject orders whose additional margin requirement would exceed
+    /// available margin. A no-op until `wallet_balance` is configured via
+    /// [`Self::set_wallet_balance`].
+    fn check_margin(&self, order: &Order) -> Option<RiskCheckResult> {
+        if self.wallet_balance == 0.0 || self.leverage <= 0.0 {
+            return None;
+        }
+
+        let potential_pos = match order.side {
+            Side::Buy => self.position.quantity + order.quantity,
+            Side::Sell => self.position.quantity - order.quantity,
+        };
+
+        let potential_margin =
+            (from_qty(potential_pos.abs()) * from_price(order.price)) / self.leverage;
+        let additional_margin = (potential_margin - self.used_margin).max(0.0);
+        let available = self.available_margin();
+
+        if additional_margin > available {
+            return Some(RiskCheckResult::fail(
+                RiskViolation::InsufficientMargin,
+                format!(
+                    "Insufficient margin: required={:.2} available={:.2}",
+                    additional_margin, available
+                ),
+            ));
+        }
+
+        None
+    }
+
+    /// Reject limit orders priced more than `max_deviation_bps` away from the
+    /// last reference price fed in via [`Self::update_reference_price`],
+    /// mirroring CoW's "order outside market price" validation. A no-op until
+    /// a reference price has been set, or for market orders (which have no
+    /// meaningful limit price to compare).
+    fn check_price_deviation(&self, order: &Order) -> Option<RiskCheckResult> {
+        if self.limits.max_deviation_bps <= 0.0 || order.order_type == OrderType::Market {
+            return None;
+        }
+
+        let reference = self.reference_price.load(Ordering::Relaxed);
+        if reference == 0 {
+            return None;
+        }
+
+        let deviation_bps =
+            (order.price - reference).abs() as f64 / reference as f64 * 10000.0;
+
+        if deviation_bps > self.limits.max_deviation_bps {
+            return Some(RiskCheckResult::fail(
+                RiskViolation::PriceDeviation,
+                format!(
+                    "Order price {} deviates {:.1}bps from reference {} (max {:.1}bps)",
+                    from_price(order.price),
+                    deviation_bps,
+                    from_price(reference),
+                    self.limits.max_deviation_bps
+                ),
+            ));
+        }
+
+        None
+    }
+
     fn check_order_size(&self, order: &Order) -> Option<RiskCheckResult> {
         if self.limits.max_order_qty > 0 && order.quantity > self.limits.max_order_qty {
             return Some(RiskCheckResult::fail(
@@ -302,8 +469,16 @@ impl RiskManager {
         None
     }
 
-    /// Update position after fill
-    pub fn on_fill(&mut self, order: &Order, filled_qty: Quantity, fill_price: Price) {
+    /// Update position after fill, and attribute the fill to its order in
+    /// `open_orders` so `check_open_orders`/exposure tracking reflect the
+    /// remaining (unfilled) quantity rather than the order's original size.
+    /// The order is only dropped from `open_orders` once fully filled, so a
+    /// partial fill leaves it tracked with its updated `filled_qty`/`status`.
+    /// `is_maker` selects the fee schedule's maker or taker rate, so loss
+    /// limits gate on net-of-fee PnL rather than gross.
+    pub fn on_fill(&mut self, order: &Order, filled_qty: Quantity, fill_price: Price, is_maker: bool) {
+        self.apply_partial_fill(order.id, filled_qty);
+
         let old_qty = self.position.quantity;
 
         match order.side {
@@ -355,7 +530,14 @@ impl RiskManager {
             }
         }
 
+        let fee = from_qty(filled_qty) * from_price(fill_price) * self.fee_model.rate_for(is_maker);
+        if fee != 0.0 {
+            self.position.realized_pnl -= fee;
+            self.daily_realized_pnl -= fee;
+        }
+
         self.position.last_update = now_nanos();
+        self.recompute_margin();
 
         // Check drawdown
         let equity = self.daily_realized_pnl + self.position.unrealized_pnl;
@@ -369,6 +551,72 @@ impl RiskManager {
         }
     }
 
+    /// Charge (or credit) the current position a funding payment at
+    /// `funding_rate` against `mark_price`, same realized-PnL accounting as
+    /// [`Self::on_fill`]'s fee deduction. A positive `funding_rate` costs a
+    /// long position and pays a short one (and vice versa for negative
+    /// rates); flat positions owe nothing. No-op on spot, where a caller
+    /// simply never has a funding rate to feed in.
+    pub fn apply_funding_payment(&mut self, funding_rate: f64, mark_price: Price) {
+        if self.position.is_flat() || funding_rate == 0.0 {
+            return;
+        }
+
+        let payment = -from_qty(self.position.quantity) * from_price(mark_price) * funding_rate;
+        self.position.realized_pnl += payment;
+        self.daily_realized_pnl += payment;
+    }
+
+    /// Recompute `used_margin` and `liquidation_price` from the current
+    /// position, leverage, and avg price, as in lfest's `Account`/`Margin`.
+    fn recompute_margin(&mut self) {
+        if self.leverage <= 0.0 {
+            self.used_margin = 0.0;
+            self.liquidation_price = None;
+            return;
+        }
+
+        self.used_margin = self.position.notional_value(self.position.avg_price) / self.leverage;
+
+        self.liquidation_price = if self.position.is_flat() {
+            None
+        } else {
+            let entry = from_price(self.position.avg_price);
+            let adverse = 1.0 / self.leverage - self.limits.maintenance_margin_rate;
+            let liq_price = if self.position.is_long() {
+                entry * (1.0 - adverse)
+            } else {
+                entry * (1.0 + adverse)
+            };
+            Some(to_price(liq_price))
+        };
+    }
+
+    /// Wallet balance plus unrealized P&L, less margin already tied up in the
+    /// open position
+    pub fn available_margin(&self) -> f64 {
+        self.wallet_balance + self.position.unrealized_pnl - self.used_margin
+    }
+
+    /// Price at which the current position would be liquidated, or `None`
+    /// while flat
+    pub fn liquidation_price(&self) -> Option<Price> {
+        self.liquidation_price
+    }
+
+    /// Configure account leverage used for margin and liquidation-price
+    /// calculations
+    pub fn set_leverage(&mut self, leverage: f64) {
+        self.leverage = leverage;
+        self.recompute_margin();
+    }
+
+    /// Configure wallet balance used for margin checks. Setting this is what
+    /// activates [`Self::check_margin`] in `check_order`.
+    pub fn set_wallet_balance(&mut self, balance: f64) {
+        self.wallet_balance = balance;
+    }
+
     /// Track sent order
     pub fn on_order_sent(&mut self, order: Order) {
         self.open_orders.insert(order.id, order);
@@ -379,6 +627,47 @@ impl RiskManager {
         self.open_orders.remove(&order_id);
     }
 
+    /// Attribute `filled_qty` to `order_id`'s entry in `open_orders`, summing
+    /// cumulative filled quantity to detect completion, and remove it once
+    /// fully filled. A no-op if the order isn't tracked (e.g. already closed
+    /// out, or never registered via [`Self::on_order_sent`]).
+    fn apply_partial_fill(&mut self, order_id: OrderId, filled_qty: Quantity) {
+        let Some(tracked) = self.open_orders.get_mut(&order_id) else {
+            return;
+        };
+
+        tracked.filled_qty += filled_qty;
+        tracked.timestamp = now_nanos();
+        tracked.status = if tracked.filled_qty >= tracked.quantity {
+            OrderStatus::Filled
+        } else {
+            OrderStatus::PartiallyFilled
+        };
+
+        if tracked.status == OrderStatus::Filled {
+            self.open_orders.remove(&order_id);
+        }
+    }
+
+    /// Remaining unfilled quantity for a tracked open order, or `None` if the
+    /// order isn't open (fully filled, canceled, or unknown)
+    pub fn remaining_qty(&self, order_id: OrderId) -> Option<Quantity> {
+        self.open_orders.get(&order_id).map(|order| order.remaining())
+    }
+
+    /// Fraction of the order filled so far (0.0 to 1.0), or `None` if the
+    /// order isn't open. Lets a strategy decide whether to re-quote the
+    /// unfilled remainder rather than treating a partial fill as done.
+    pub fn fill_ratio(&self, order_id: OrderId) -> Option<f64> {
+        self.open_orders.get(&order_id).map(|order| {
+            if order.quantity == 0 {
+                1.0
+            } else {
+                from_qty(order.filled_qty) / from_qty(order.quantity)
+            }
+        })
+    }
+
     /// Get current position
     pub fn get_position(&self) -> Quantity {
         self.position.quantity
@@ -399,6 +688,23 @@ impl RiskManager {
         self.kill_switch_active.load(Ordering::Relaxed)
     }
 
+    /// Enable or disable reduce-only mode
+    pub fn set_reduce_only(&self, enabled: bool) {
+        self.reduce_only.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check if reduce-only mode is active
+    pub fn is_reduce_only(&self) -> bool {
+        self.reduce_only.load(Ordering::Relaxed)
+    }
+
+    /// Feed in the latest reference mid price (e.g. from the live order
+    /// book), used by [`Self::check_price_deviation`] to catch fat-finger
+    /// quotes and stale-quote crossing
+    pub fn update_reference_price(&self, price: Price) {
+        self.reference_price.store(price, Ordering::Relaxed);
+    }
+
     /// Reset daily statistics
     pub fn reset_daily_stats(&mut self) {
         self.daily_realized_pnl = 0.0;
@@ -455,4 +761,253 @@ mod tests {
         assert!(!result.passed);
         assert_eq!(result.violation, Some(RiskViolation::PositionLimit));
     }
+
+    #[test]
+    fn test_reduce_only_rejects_order_that_increases_position() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        let buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.05),
+        );
+        rm.on_fill(&buy, to_qty(0.05), to_price(50000.0), true);
+        rm.set_reduce_only(true);
+
+        let more_buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.01),
+        );
+
+        let result = rm.check_order(&more_buy);
+        assert!(!result.passed);
+        assert_eq!(result.violation, Some(RiskViolation::ReduceOnlyMode));
+    }
+
+    #[test]
+    fn test_reduce_only_allows_order_that_reduces_position() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        let buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.05),
+        );
+        rm.on_fill(&buy, to_qty(0.05), to_price(50000.0), true);
+        rm.set_reduce_only(true);
+
+        let sell = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Sell,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.02),
+        );
+
+        let result = rm.check_order(&sell);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_reduce_only_has_no_effect_when_disabled() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        let buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.05),
+        );
+        rm.on_fill(&buy, to_qty(0.05), to_price(50000.0), true);
+        assert!(!rm.is_reduce_only());
+
+        let more_buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.01),
+        );
+
+        let result = rm.check_order(&more_buy);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_liquidation_price_computed_after_fill() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        rm.set_leverage(10.0);
+        rm.set_wallet_balance(1000.0);
+
+        let buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.05),
+        );
+        rm.on_fill(&buy, to_qty(0.05), to_price(50000.0), true);
+
+        // long liquidation price = entry * (1 - (1/leverage - maintenance_margin_rate))
+        //                        = 50000 * (1 - (0.1 - 0.005)) = 45250
+        let liq = rm.liquidation_price().expect("position is open");
+        assert!((from_price(liq) - 45250.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_check_margin_rejects_order_exceeding_available_margin() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        rm.set_leverage(10.0);
+        rm.set_wallet_balance(300.0);
+
+        let buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.05),
+        );
+        rm.on_fill(&buy, to_qty(0.05), to_price(50000.0), true);
+
+        let more_buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.1),
+        );
+
+        let result = rm.check_order(&more_buy);
+        assert!(!result.passed);
+        assert_eq!(result.violation, Some(RiskViolation::InsufficientMargin));
+    }
+
+    #[test]
+    fn test_check_margin_allows_order_within_available_margin() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        rm.set_leverage(10.0);
+        rm.set_wallet_balance(1000.0);
+
+        let buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.05),
+        );
+        rm.on_fill(&buy, to_qty(0.05), to_price(50000.0), true);
+
+        let more_buy = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.01),
+        );
+
+        let result = rm.check_order(&more_buy);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_price_deviation_rejects_order_far_from_reference() {
+        let rm = RiskManager::new(RiskLimits::default());
+        rm.update_reference_price(to_price(50000.0));
+
+        let order = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(51000.0), // 200bps away, exceeds default 100bps limit
+            to_qty(0.01),
+        );
+
+        let result = rm.check_order(&order);
+        assert!(!result.passed);
+        assert_eq!(result.violation, Some(RiskViolation::PriceDeviation));
+    }
+
+    #[test]
+    fn test_price_deviation_allows_order_within_band() {
+        let rm = RiskManager::new(RiskLimits::default());
+        rm.update_reference_price(to_price(50000.0));
+
+        let order = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50010.0), // 2bps away
+            to_qty(0.01),
+        );
+
+        let result = rm.check_order(&order);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_partial_fill_updates_remaining_qty_and_keeps_order_open() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        let mut order = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.1),
+        );
+        order.id = 1;
+        rm.on_order_sent(order.clone());
+
+        rm.on_fill(&order, to_qty(0.04), to_price(50000.0), true);
+
+        assert_eq!(rm.remaining_qty(1), Some(to_qty(0.06)));
+        assert!((rm.fill_ratio(1).unwrap() - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_full_fill_removes_order_from_open_orders() {
+        let mut rm = RiskManager::new(RiskLimits::default());
+        let mut order = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(50000.0),
+            to_qty(0.1),
+        );
+        order.id = 1;
+        rm.on_order_sent(order.clone());
+
+        rm.on_fill(&order, to_qty(0.04), to_price(50000.0), true);
+        rm.on_fill(&order, to_qty(0.06), to_price(50000.0), true);
+
+        assert_eq!(rm.remaining_qty(1), None);
+        assert_eq!(rm.fill_ratio(1), None);
+    }
+
+    #[test]
+    fn test_remaining_qty_none_for_untracked_order() {
+        let rm = RiskManager::new(RiskLimits::default());
+        assert_eq!(rm.remaining_qty(999), None);
+        assert_eq!(rm.fill_ratio(999), None);
+    }
+
+    #[test]
+    fn test_price_deviation_disabled_without_reference_price() {
+        let rm = RiskManager::new(RiskLimits::default());
+
+        let order = Order::new(
+            Symbol::new("BTCUSDT"),
+            Side::Buy,
+            OrderType::Limit,
+            to_price(999999.0),
+            to_qty(0.01),
+        );
+
+        let result = rm.check_order(&order);
+        assert!(result.passed);
+    }
 }
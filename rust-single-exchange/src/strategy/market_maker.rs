@@ -0,0 +1,750 @@
+//! Market making strategy implementations
+
+use super::rate::LatestRate;
+use super::{MarketMaker, MarketMakerParams, QuoteDecision, QuoteLevel, Signal};
+use crate::core::types::*;
+use crate::orderbook::OrderBook;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+
+/// Basic market making strategy
+pub struct BasicMarketMaker {
+    params: MarketMakerParams,
+    enabled: bool,
+
+    // Active quotes
+    active_bid_id: OrderId,
+    active_ask_id: OrderId,
+    active_bid_price: Price,
+    active_ask_price: Price,
+
+    // Statistics
+    quotes_sent: u64,
+    fills: u64,
+    last_quote_time: Timestamp,
+
+    /// External reference price to anchor quotes to instead of the local book mid.
+    /// Infallible so it's always safe to call; wire in a [`super::rate::FallbackRate`]
+    /// to degrade from a live feed to a [`super::rate::FixedRate`] backstop.
+    reference_rate: Option<Box<dyn LatestRate<Error = Infallible> + Send>>,
+}
+
+impl BasicMarketMaker {
+    pub fn new(params: MarketMakerParams) -> Self {
+        BasicMarketMaker {
+            params,
+            enabled: false,
+            active_bid_id: 0,
+            active_ask_id: 0,
+            active_bid_price: 0,
+            active_ask_price: 0,
+            quotes_sent: 0,
+            fills: 0,
+            last_quote_time: 0,
+            reference_rate: None,
+        }
+    }
+
+    /// Anchor quotes to `rate` (e.g. a [`super::rate::FallbackRate`] wrapping a live
+    /// ticker with a fixed backstop) instead of the local book mid. See
+    /// `MarketMakerParams::reference_spread_bps` for the spread quoted around it.
+    pub fn with_reference_rate<R>(mut self, rate: R) -> Self
+    where
+        R: LatestRate<Error = Infallible> + Send + 'static,
+    {
+        self.reference_rate = Some(Box::new(rate));
+        self
+    }
+
+    fn calculate_fair_value(&mut self, book: &OrderBook) -> Option<Price> {
+        if let Some(rate) = &mut self.reference_rate {
+            return Some(rate.latest_rate().unwrap().price);
+        }
+
+        book.mid_price()
+    }
+
+    fn calculate_spread(&self, _book: &OrderBook, signal: &Signal) -> f64 {
+        if self.reference_rate.is_some() {
+            return self
+                .params
+                .reference_spread_bps
+                .clamp(self.params.min_spread_bps, self.params.max_spread_bps);
+        }
+
+        let mut spread = self.params.target_spread_bps;
+
+        // Adjust for volatility
+        if signal.volatility > 0.0 {
+            spread *= 1.0 + signal.volatility;
+        }
+
+        spread.clamp(self.params.min_spread_bps, self.params.max_spread_bps)
+    }
+
+    fn calculate_inventory_skew(&self, position: Quantity) -> f64 {
+        if self.params.max_position == 0 {
+            return 0.0;
+        }
+        position as f64 / self.params.max_position as f64
+    }
+
+    fn calculate_order_size(&self, side: Side, position: Quantity) -> Quantity {
+        let mut size = self.params.default_order_size;
+
+        if self.params.max_position > 0 {
+            match side {
+                Side::Buy if position > 0 => {
+                    // Already long, reduce buy size
+                    let ratio = 1.0 - (position as f64 / self.params.max_position as f64);
+                    size = (size as f64 * ratio.max(0.0)) as Quantity;
+                }
+                Side::Sell if position < 0 => {
+                    // Already short, reduce sell size
+                    let ratio = 1.0 + (position as f64 / self.params.max_position as f64);
+                    size = (size as f64 * ratio.max(0.0)) as Quantity;
+                }
+                _ => {}
+            }
+        }
+
+        size.clamp(self.params.min_order_size, self.params.max_order_size)
+    }
+
+    /// Per-level price offset (from the touch) for each of `layers` ladder levels,
+    /// each stepped out by `layer_step_bps` beyond the previous one
+    fn ladder_offsets(&self, fair_value: Price) -> Vec<Price> {
+        let layers = self.params.layers.max(1);
+        (0..layers)
+            .map(|level| (fair_value as f64 * self.params.layer_step_bps * level as f64 / 10000.0) as Price)
+            .collect()
+    }
+
+    /// Per-level order size for each of `layers` ladder levels: level N size is
+    /// `base_size * layer_size_mult^N`, clamped to `[min_order_size, max_order_size]`
+    /// and further capped so the running total never pushes `position` past
+    /// `max_position` on that side
+    fn ladder_sizes(&self, side: Side, base_size: Quantity, position: Quantity) -> Vec<Quantity> {
+        let layers = self.params.layers.max(1);
+        let room = match side {
+            Side::Buy => (self.params.max_position - position).max(0),
+            Side::Sell => (self.params.max_position + position).max(0),
+        };
+
+        let mut sizes = Vec::with_capacity(layers);
+        let mut used = 0;
+        for level in 0..layers {
+            let raw = (base_size as f64 * self.params.layer_size_mult.powi(level as i32)) as Quantity;
+            let clamped = raw.clamp(self.params.min_order_size, self.params.max_order_size);
+            let size = clamped.min((room - used).max(0));
+            sizes.push(size);
+            used += size;
+        }
+        sizes
+    }
+
+    /// Build the full quote ladder given the touch prices/sizes, stepping each
+    /// successive level out by `ladder_offsets` and sizing it via `ladder_sizes`
+    fn build_levels(
+        &self,
+        fair_value: Price,
+        touch_bid: Price,
+        touch_ask: Price,
+        bid_size: Quantity,
+        ask_size: Quantity,
+        position: Quantity,
+    ) -> Vec<QuoteLevel> {
+        let offsets = self.ladder_offsets(fair_value);
+        let bid_sizes = self.ladder_sizes(Side::Buy, bid_size, position);
+        let ask_sizes = self.ladder_sizes(Side::Sell, ask_size, position);
+
+        offsets
+            .iter()
+            .enumerate()
+            .map(|(i, step)| QuoteLevel {
+                bid_price: touch_bid - step,
+                bid_size: bid_sizes[i],
+                ask_price: touch_ask + step,
+                ask_size: ask_sizes[i],
+            })
+            .collect()
+    }
+}
+
+impl MarketMaker for BasicMarketMaker {
+    fn compute_quotes(
+        &mut self,
+        book: &OrderBook,
+        position: Quantity,
+        signal: &Signal,
+    ) -> QuoteDecision {
+        let mut decision = QuoteDecision::default();
+
+        if !self.enabled {
+            decision.reason = "Strategy disabled".to_string();
+            return decision;
+        }
+
+        if !book.is_valid() {
+            decision.reason = "Invalid orderbook".to_string();
+            return decision;
+        }
+
+        let fair_value = match self.calculate_fair_value(book) {
+            Some(fv) => fv,
+            None => {
+                decision.reason = "Cannot determine fair value".to_string();
+                return decision;
+            }
+        };
+
+        // Calculate spread
+        let spread_bps = self.calculate_spread(book, signal);
+        let half_spread = (fair_value as f64 * spread_bps / 20000.0) as Price;
+
+        // Calculate inventory skew
+        let skew = self.calculate_inventory_skew(position);
+        let skew_adjustment =
+            (fair_value as f64 * skew * self.params.inventory_skew / 10000.0) as Price;
+
+        // Bias quotes against paying funding: lowering both sides makes
+        // shedding a paying long (or building a paid short) more attractive,
+        // independent of which side `position` currently sits on, since
+        // funding is charged/paid on the position's current sign either way.
+        let funding_adjustment =
+            (fair_value as f64 * signal.funding_rate * self.params.funding_skew_weight / 10000.0) as Price;
+
+        decision.bid_price = fair_value - half_spread - skew_adjustment - funding_adjustment;
+        decision.ask_price = fair_value + half_spread - skew_adjustment - funding_adjustment;
+
+        // Ensure no crossing
+        if decision.bid_price >= decision.ask_price {
+            decision.reason = "Prices would cross".to_string();
+            return decision;
+        }
+
+        // Calculate sizes
+        decision.bid_size = self.calculate_order_size(Side::Buy, position);
+        decision.ask_size = self.calculate_order_size(Side::Sell, position);
+
+        if decision.bid_size == 0 && decision.ask_size == 0 {
+            decision.reason = "Order sizes are zero".to_string();
+            return decision;
+        }
+
+        decision.levels = self.build_levels(
+            fair_value,
+            decision.bid_price,
+            decision.ask_price,
+            decision.bid_size,
+            decision.ask_size,
+            position,
+        );
+
+        // Check if we should skip quoting
+        let now = now_nanos();
+        if now - self.last_quote_time < self.params.min_quote_life_us * 1000 {
+            let bid_diff = (decision.bid_price - self.active_bid_price).abs();
+            let ask_diff = (decision.ask_price - self.active_ask_price).abs();
+            let threshold = fair_value / 10000; // 1 bps
+
+            if bid_diff < threshold && ask_diff < threshold {
+                decision.reason = "Prices unchanged".to_string();
+                return decision;
+            }
+        }
+
+        decision.should_quote = true;
+        self.last_quote_time = now;
+        self.quotes_sent += 1;
+
+        decision
+    }
+
+    fn on_fill(&mut self, _order: &Order, _filled_qty: Quantity, _fill_price: Price) {
+        self.fills += 1;
+    }
+
+    fn on_cancel(&mut self, order_id: OrderId) {
+        if order_id == self.active_bid_id {
+            self.active_bid_id = 0;
+            self.active_bid_price = 0;
+        } else if order_id == self.active_ask_id {
+            self.active_ask_id = 0;
+            self.active_ask_price = 0;
+        }
+    }
+
+    fn params(&self) -> &MarketMakerParams {
+        &self.params
+    }
+
+    fn update_params(&mut self, params: MarketMakerParams) {
+        self.params = params;
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Floor enforced on an estimated `k` so `ln(1 + gamma/k)` never blows up
+const MIN_ESTIMATED_K: f64 = 1e-3;
+/// Minimum samples each estimator needs before it overrides the seeded default
+const MIN_VOL_SAMPLES: usize = 30;
+const MIN_ARRIVAL_SAMPLES: usize = 20;
+
+/// EWMA estimator of mid-price volatility, expressed as variance-per-second, fed one
+/// log-return per `compute_quotes` call. `var_t = lambda*var_{t-1} + (1-lambda)*r_t^2`.
+struct VolatilityEstimator {
+    lambda: f64,
+    var_per_sec: Option<f64>,
+    samples: usize,
+    last_mid: Option<f64>,
+    last_ts: Option<Timestamp>,
+}
+
+impl VolatilityEstimator {
+    fn new(lambda: f64) -> Self {
+        VolatilityEstimator {
+            lambda,
+            var_per_sec: None,
+            samples: 0,
+            last_mid: None,
+            last_ts: None,
+        }
+    }
+
+    /// Fold in a new mid observation, returning the variance-per-second estimate
+    fn update(&mut self, mid: f64, ts: Timestamp) -> Option<f64> {
+        if mid > 0.0 {
+            if let (Some(last_mid), Some(last_ts)) = (self.last_mid, self.last_ts) {
+                if last_mid > 0.0 && ts > last_ts {
+                    let dt_secs = (ts - last_ts) as f64 / 1e9;
+                    if dt_secs > 0.0 {
+                        let log_return = (mid / last_mid).ln();
+                        let r2_per_sec = log_return * log_return / dt_secs;
+                        self.var_per_sec = Some(match self.var_per_sec {
+                            Some(v) => self.lambda * v + (1.0 - self.lambda) * r2_per_sec,
+                            None => r2_per_sec,
+                        });
+                        self.samples += 1;
+                    }
+                }
+            }
+            self.last_mid = Some(mid);
+            self.last_ts = Some(ts);
+        }
+        self.var_per_sec
+    }
+
+    fn has_enough_samples(&self) -> bool {
+        self.samples >= MIN_VOL_SAMPLES
+    }
+}
+
+/// Estimates order-arrival decay `k` from realized fills: fills are bucketed by their
+/// distance from the mid/reservation price at fill time, an arrival rate lambda(delta)
+/// per bucket is computed over the rolling window of recent fills, and `k` is the
+/// (negated) slope of a least-squares fit of `ln(lambda(delta)) = a - k*delta`.
+struct ArrivalRateEstimator {
+    bucket_width: f64,
+    num_buckets: usize,
+    window_capacity: usize,
+    fills: VecDeque<(f64, Timestamp)>,
+}
+
+impl ArrivalRateEstimator {
+    fn new(bucket_width: f64, num_buckets: usize, window_capacity: usize) -> Self {
+        ArrivalRateEstimator {
+            bucket_width,
+            num_buckets,
+            window_capacity,
+            fills: VecDeque::with_capacity(window_capacity),
+        }
+    }
+
+    fn record_fill(&mut self, distance: f64, ts: Timestamp) {
+        if self.fills.len() == self.window_capacity {
+            self.fills.pop_front();
+        }
+        self.fills.push_back((distance.abs(), ts));
+    }
+
+    fn has_enough_samples(&self) -> bool {
+        self.fills.len() >= MIN_ARRIVAL_SAMPLES
+    }
+
+    /// Fit `ln(lambda(delta)) = a - k*delta` over the current window via simple
+    /// least squares, returning the slope magnitude clamped to `MIN_ESTIMATED_K`
+    fn estimate_k(&self) -> Option<f64> {
+        if !self.has_enough_samples() {
+            return None;
+        }
+
+        let earliest = self.fills.front()?.1;
+        let latest = self.fills.back()?.1;
+        let window_secs = (latest - earliest) as f64 / 1e9;
+        if window_secs <= 0.0 {
+            return None;
+        }
+
+        let mut counts = vec![0u64; self.num_buckets];
+        for (distance, _) in &self.fills {
+            let bucket = (distance / self.bucket_width) as usize;
+            if bucket < self.num_buckets {
+                counts[bucket] += 1;
+            }
+        }
+
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        for (bucket, &count) in counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let delta_mid = (bucket as f64 + 0.5) * self.bucket_width;
+            let lambda = count as f64 / window_secs;
+            xs.push(delta_mid);
+            ys.push(lambda.ln());
+        }
+
+        if xs.len() < 2 {
+            return None;
+        }
+
+        let n = xs.len() as f64;
+        let mean_x = xs.iter().sum::<f64>() / n;
+        let mean_y = ys.iter().sum::<f64>() / n;
+
+        let mut covariance = 0.0;
+        let mut variance_x = 0.0;
+        for i in 0..xs.len() {
+            covariance += (xs[i] - mean_x) * (ys[i] - mean_y);
+            variance_x += (xs[i] - mean_x).powi(2);
+        }
+
+        if variance_x.abs() < 1e-12 {
+            return None;
+        }
+
+        let slope = covariance / variance_x;
+        Some((-slope).abs().max(MIN_ESTIMATED_K))
+    }
+}
+
+/// Avellaneda-Stoikov optimal market making strategy
+pub struct AvellanedaStoikovMM {
+    base: BasicMarketMaker,
+
+    // A-S specific parameters
+    gamma: f64,  // Risk aversion
+    sigma: f64,  // Volatility (seeded default, overridden once the estimator has enough data)
+    k: f64,      // Order arrival intensity (seeded default, overridden likewise)
+    t_horizon: f64,  // Time horizon
+
+    start_time: Timestamp,
+    last_reservation: Price,
+
+    vol_estimator: VolatilityEstimator,
+    arrival_estimator: ArrivalRateEstimator,
+}
+
+impl AvellanedaStoikovMM {
+    /// EWMA decay for the online volatility estimate
+    const VOL_LAMBDA: f64 = 0.94;
+    /// Width, in quote-currency units, of each fill-distance bucket used to fit `k`
+    const ARRIVAL_BUCKET_WIDTH: f64 = 0.5;
+    const ARRIVAL_NUM_BUCKETS: usize = 20;
+    const ARRIVAL_WINDOW_CAPACITY: usize = 500;
+
+    pub fn new(params: MarketMakerParams, gamma: f64, sigma: f64, k: f64, t_horizon: f64) -> Self {
+        AvellanedaStoikovMM {
+            base: BasicMarketMaker::new(params),
+            gamma,
+            sigma,
+            k,
+            t_horizon,
+            start_time: 0,
+            last_reservation: 0,
+            vol_estimator: VolatilityEstimator::new(Self::VOL_LAMBDA),
+            arrival_estimator: ArrivalRateEstimator::new(
+                Self::ARRIVAL_BUCKET_WIDTH,
+                Self::ARRIVAL_NUM_BUCKETS,
+                Self::ARRIVAL_WINDOW_CAPACITY,
+            ),
+        }
+    }
+
+    /// Feed a new mid observation into the online sigma estimator, overriding `self.sigma`
+    /// once enough samples have accrued. The per-second variance is scaled up to
+    /// `t_horizon` units before taking the square root, since `t_remaining` below is a
+    /// fraction of `t_horizon` rather than raw seconds.
+    fn update_volatility_estimate(&mut self, mid: Price, ts: Timestamp) {
+        if let Some(var_per_sec) = self.vol_estimator.update(from_price(mid), ts) {
+            if self.vol_estimator.has_enough_samples() {
+                self.sigma = (var_per_sec * self.t_horizon).sqrt();
+            }
+        }
+    }
+
+    /// Record a fill's distance from the reservation price quoted at the time, feeding
+    /// the online arrival-rate estimator and overriding `self.k` once it has enough data
+    fn update_arrival_estimate(&mut self, fill_price: Price, ts: Timestamp) {
+        if self.last_reservation <= 0 {
+            return;
+        }
+        let distance = from_price((fill_price - self.last_reservation).abs());
+        self.arrival_estimator.record_fill(distance, ts);
+        if let Some(k) = self.arrival_estimator.estimate_k() {
+            self.k = k;
+        }
+    }
+
+    fn calculate_reservation_price(&self, mid: Price, position: Quantity, t_remaining: f64) -> Price {
+        // r(s,q,t) = s - q * gamma * sigma^2 * (T - t)
+        let adjustment = position as f64 * self.gamma * self.sigma.powi(2) * t_remaining;
+        mid - (mid as f64 * adjustment) as Price
+    }
+
+    fn calculate_optimal_spread(&self, t_remaining: f64) -> f64 {
+        // delta = gamma * sigma^2 * (T - t) + (2/gamma) * ln(1 + gamma/k)
+        let term1 = self.gamma * self.sigma.powi(2) * t_remaining;
+        let term2 = (2.0 / self.gamma) * (1.0 + self.gamma / self.k).ln();
+        (term1 + term2) * 10000.0 // Convert to bps
+    }
+}
+
+impl MarketMaker for AvellanedaStoikovMM {
+    fn compute_quotes(
+        &mut self,
+        book: &OrderBook,
+        position: Quantity,
+        signal: &Signal,
+    ) -> QuoteDecision {
+        let mut decision = QuoteDecision::default();
+
+        if !self.base.enabled || !book.is_valid() {
+            decision.reason = "Disabled or invalid book".to_string();
+            return decision;
+        }
+
+        // Initialize start time
+        if self.start_time == 0 {
+            self.start_time = signal.timestamp;
+        }
+
+        // Calculate time remaining
+        let elapsed_secs = (signal.timestamp - self.start_time) as f64 / 1e9;
+        let t_elapsed = elapsed_secs / self.t_horizon;
+        let t_remaining = (1.0 - (t_elapsed % 1.0)).max(0.01);
+
+        let mid = match book.mid_price() {
+            Some(m) => m,
+            None => {
+                decision.reason = "No mid price".to_string();
+                return decision;
+            }
+        };
+
+        self.update_volatility_estimate(mid, signal.timestamp);
+
+        // Calculate reservation price
+        let reservation = self.calculate_reservation_price(mid, position, t_remaining);
+        self.last_reservation = reservation;
+
+        // Calculate optimal spread
+        let spread_bps = self.calculate_optimal_spread(t_remaining)
+            .clamp(self.base.params.min_spread_bps, self.base.params.max_spread_bps);
+        let half_spread = (mid as f64 * spread_bps / 20000.0) as Price;
+
+        // Same funding bias as `BasicMarketMaker`: lower both sides when
+        // paying funding, independent of which side `position` sits on.
+        let funding_adjustment =
+            (mid as f64 * signal.funding_rate * self.base.params.funding_skew_weight / 10000.0) as Price;
+
+        decision.bid_price = reservation - half_spread - funding_adjustment;
+        decision.ask_price = reservation + half_spread - funding_adjustment;
+
+        if decision.bid_price >= decision.ask_price {
+            decision.reason = "Prices would cross".to_string();
+            return decision;
+        }
+
+        decision.bid_size = self.base.calculate_order_size(Side::Buy, position);
+        decision.ask_size = self.base.calculate_order_size(Side::Sell, position);
+
+        if decision.bid_size > 0 || decision.ask_size > 0 {
+            decision.should_quote = true;
+            decision.levels = self.base.build_levels(
+                mid,
+                decision.bid_price,
+                decision.ask_price,
+                decision.bid_size,
+                decision.ask_size,
+                position,
+            );
+        }
+
+        decision
+    }
+
+    fn on_fill(&mut self, order: &Order, filled_qty: Quantity, fill_price: Price) {
+        self.update_arrival_estimate(fill_price, now_nanos());
+        self.base.on_fill(order, filled_qty, fill_price);
+    }
+
+    fn on_cancel(&mut self, order_id: OrderId) {
+        self.base.on_cancel(order_id);
+    }
+
+    fn params(&self) -> &MarketMakerParams {
+        self.base.params()
+    }
+
+    fn update_params(&mut self, params: MarketMakerParams) {
+        self.base.update_params(params);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.base.set_enabled(enabled);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.base.is_enabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_mm_quotes() {
+        let params = MarketMakerParams::default();
+        let mut mm = BasicMarketMaker::new(params);
+        mm.set_enabled(true);
+
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.update_bid(to_price(50000.0), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(50001.0), to_qty(1.0)).unwrap();
+
+        let signal = Signal::default();
+        let decision = mm.compute_quotes(&book, 0, &signal);
+
+        assert!(decision.should_quote);
+        assert!(decision.bid_price < to_price(50000.0));
+        assert!(decision.ask_price > to_price(50001.0));
+    }
+
+    #[test]
+    fn test_basic_mm_anchors_quotes_to_reference_rate() {
+        let mut params = MarketMakerParams::default();
+        params.reference_spread_bps = 100.0; // 1%
+        let mut mm = BasicMarketMaker::new(params)
+            .with_reference_rate(crate::strategy::rate::FixedRate::new(to_price(40000.0)));
+        mm.set_enabled(true);
+
+        // Book mid is far from the reference rate; quotes should follow the latter.
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.update_bid(to_price(50000.0), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(50001.0), to_qty(1.0)).unwrap();
+
+        let signal = Signal::default();
+        let decision = mm.compute_quotes(&book, 0, &signal);
+
+        assert!(decision.should_quote);
+        assert!(decision.bid_price < to_price(40000.0));
+        assert!(decision.ask_price > to_price(40000.0));
+        assert!(decision.ask_price < to_price(50000.0));
+    }
+
+    #[test]
+    fn test_basic_mm_multi_layer_quotes() {
+        let mut params = MarketMakerParams::default();
+        params.layers = 3;
+        params.layer_step_bps = 5.0;
+        params.layer_size_mult = 2.0;
+
+        let mut mm = BasicMarketMaker::new(params);
+        mm.set_enabled(true);
+
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.update_bid(to_price(50000.0), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(50001.0), to_qty(1.0)).unwrap();
+
+        let signal = Signal::default();
+        let decision = mm.compute_quotes(&book, 0, &signal);
+
+        assert!(decision.should_quote);
+        assert_eq!(decision.levels.len(), 3);
+        assert_eq!(decision.levels[0].bid_price, decision.bid_price);
+        assert_eq!(decision.levels[0].ask_price, decision.ask_price);
+
+        // Each successive level steps further from the touch and is sized larger
+        for i in 1..decision.levels.len() {
+            assert!(decision.levels[i].bid_price < decision.levels[i - 1].bid_price);
+            assert!(decision.levels[i].ask_price > decision.levels[i - 1].ask_price);
+            assert!(decision.levels[i].bid_size >= decision.levels[i - 1].bid_size);
+        }
+    }
+
+    #[test]
+    fn test_volatility_estimator_tracks_realized_variance() {
+        let mut estimator = VolatilityEstimator::new(0.9);
+        let mut ts = 0u64;
+        let mut var = None;
+
+        // Alternate the mid up/down by a fixed log-return every second
+        for i in 0..(MIN_VOL_SAMPLES + 5) {
+            ts += 1_000_000_000;
+            let mid = if i % 2 == 0 { 100.0 } else { 100.5 };
+            var = estimator.update(mid, ts);
+        }
+
+        assert!(estimator.has_enough_samples());
+        assert!(var.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_arrival_rate_estimator_requires_minimum_samples() {
+        let mut estimator = ArrivalRateEstimator::new(0.5, 20, 500);
+        assert_eq!(estimator.estimate_k(), None);
+
+        for i in 0..MIN_ARRIVAL_SAMPLES {
+            estimator.record_fill(1.0 + i as f64 * 0.1, (i as u64 + 1) * 1_000_000_000);
+        }
+
+        assert!(estimator.has_enough_samples());
+    }
+
+    #[test]
+    fn test_avellaneda_stoikov_overrides_seeded_sigma_after_enough_samples() {
+        let params = MarketMakerParams::default();
+        let mut mm = AvellanedaStoikovMM::new(params, 0.1, 0.3, 1.5, 1.0);
+        mm.set_enabled(true);
+
+        let mut book = OrderBook::new(Symbol::new("BTCUSDT"));
+        book.update_bid(to_price(50000.0), to_qty(1.0)).unwrap();
+        book.update_ask(to_price(50001.0), to_qty(1.0)).unwrap();
+
+        let seeded_sigma = mm.sigma;
+
+        let mut ts = 1_000_000_000u64;
+        for i in 0..(MIN_VOL_SAMPLES + 5) {
+            ts += 1_000_000_000;
+            let price = if i % 2 == 0 { 50000.0 } else { 50050.0 };
+            book.update_bid(to_price(price), to_qty(1.0)).unwrap();
+            book.update_ask(to_price(price + 1.0), to_qty(1.0)).unwrap();
+
+            let signal = Signal { timestamp: ts, ..Signal::default() };
+            mm.compute_quotes(&book, 0, &signal);
+        }
+
+        assert_ne!(mm.sigma, seeded_sigma);
+    }
+}
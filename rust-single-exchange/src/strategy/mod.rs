@@ -4,8 +4,10 @@ use crate::core::types::*;
 use crate::orderbook::OrderBook;
 
 mod market_maker;
+pub mod rate;
 
 pub use market_maker::{BasicMarketMaker, AvellanedaStoikovMM};
+pub use rate::{FallbackRate, FixedRate, LatestRate, Rate, TickerRate, TickerRateError};
 
 /// Signal data for strategy decisions
 #[derive(Debug, Clone, Copy, Default)]
@@ -14,10 +16,27 @@ pub struct Signal {
     pub volatility: f64,
     pub momentum: f64,
     pub inventory_pressure: f64,
+    /// Current funding rate (fraction of notional per interval; positive
+    /// means longs pay shorts), fed in from a futures venue's mark-price
+    /// stream. Zero on spot, where there's no funding to skew against.
+    pub funding_rate: f64,
     pub timestamp: Timestamp,
 }
 
+/// A single level of a multi-layer quote ladder
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuoteLevel {
+    pub bid_price: Price,
+    pub bid_size: Quantity,
+    pub ask_price: Price,
+    pub ask_size: Quantity,
+}
+
 /// Quote decision from strategy
+///
+/// `bid_price`/`ask_price`/`bid_size`/`ask_size` always mirror `levels[0]` (the
+/// touch) for callers that only care about the best quote; `levels` additionally
+/// carries any deeper ladder levels requested via `MarketMakerParams::layers`.
 #[derive(Debug, Clone, Default)]
 pub struct QuoteDecision {
     pub should_quote: bool,
@@ -25,6 +44,7 @@ pub struct QuoteDecision {
     pub ask_price: Price,
     pub bid_size: Quantity,
     pub ask_size: Quantity,
+    pub levels: Vec<QuoteLevel>,
     pub reason: String,
 }
 
@@ -36,11 +56,28 @@ pub struct MarketMakerParams {
     pub target_spread_bps: f64,
     pub max_position: Quantity,
     pub inventory_skew: f64,
+    /// Weight applied to [`Signal::funding_rate`] when biasing quotes, on the
+    /// same bps-multiplier scale as `inventory_skew`: positive funding skews
+    /// both sides down (favoring trades that shed a paying long or build a
+    /// paid short), negative funding skews the other way. Zero (the default)
+    /// disables the bias entirely, matching spot venues where funding is
+    /// never fed in.
+    pub funding_skew_weight: f64,
     pub default_order_size: Quantity,
     pub min_order_size: Quantity,
     pub max_order_size: Quantity,
     pub quote_refresh_us: u64,
     pub min_quote_life_us: u64,
+    /// Number of price levels quoted per side (1 = single top-of-book quote)
+    pub layers: usize,
+    /// Extra spread, in bps, each successive layer steps out beyond the touch
+    pub layer_step_bps: f64,
+    /// Geometric size multiplier applied per layer: level N size = base_size * mult^N
+    pub layer_size_mult: f64,
+    /// Spread, in bps, applied around a strategy's [`rate::LatestRate`] when quoting off
+    /// an external reference price instead of the local book mid. Unused unless a
+    /// reference rate is wired in via `BasicMarketMaker::with_reference_rate`.
+    pub reference_spread_bps: f64,
 }
 
 impl Default for MarketMakerParams {
@@ -51,11 +88,16 @@ impl Default for MarketMakerParams {
             target_spread_bps: 10.0,
             max_position: to_qty(1.0),
             inventory_skew: 0.5,
+            funding_skew_weight: 0.0,
             default_order_size: to_qty(0.001),
             min_order_size: to_qty(0.0001),
             max_order_size: to_qty(0.1),
             quote_refresh_us: 100_000,
             min_quote_life_us: 50_000,
+            layers: 1,
+            layer_step_bps: 2.0,
+            layer_size_mult: 1.5,
+            reference_spread_bps: 200.0, // 2%
         }
     }
 }
@@ -0,0 +1,198 @@
+//! Pluggable reference-price feeds a market maker can anchor quotes to,
+//! instead of (or as a fallback for) the local book's own mid price.
+
+use crate::core::types::*;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// A reference price sampled at `timestamp`
+#[derive(Debug, Clone, Copy)]
+pub struct Rate {
+    pub price: Price,
+    pub timestamp: Timestamp,
+}
+
+/// A pluggable source of reference prices a strategy can anchor quotes to.
+/// Takes `&mut self` since most real implementations (e.g. [`TickerRate`])
+/// need to check freshness or drain buffered state on every call.
+pub trait LatestRate {
+    type Error;
+
+    fn latest_rate(&mut self) -> Result<Rate, Self::Error>;
+}
+
+/// A constant reference rate; never fails. Used standalone in tests/backtests,
+/// or as the backup leg of a [`FallbackRate`] so a strategy always has
+/// something to quote against even if a live feed drops.
+pub struct FixedRate {
+    rate: Rate,
+}
+
+impl FixedRate {
+    pub fn new(price: Price) -> Self {
+        FixedRate { rate: Rate { price, timestamp: now_nanos() } }
+    }
+}
+
+impl LatestRate for FixedRate {
+    type Error = Infallible;
+
+    fn latest_rate(&mut self) -> Result<Rate, Self::Error> {
+        Ok(self.rate)
+    }
+}
+
+/// Why a live rate feed couldn't produce a price
+#[derive(Debug, Clone, Copy, Error)]
+pub enum TickerRateError {
+    #[error("reference rate feed has not received a tick yet")]
+    NoData,
+    #[error("reference rate feed is stale ({0:?} since last tick)")]
+    Stale(Duration),
+}
+
+/// A reference rate fed by another exchange's live WebSocket ticker: feed a
+/// [`Tick`] into [`Self::update`] from an [`ExchangeCallbacks::on_tick`](crate::exchange::ExchangeCallbacks::on_tick)
+/// callback (see [`Self::callback`]) and [`Self::latest_rate`] reports the
+/// mid of the most recent one, erroring once it's older than `max_staleness`
+/// so a disconnected/stale feed doesn't silently quote a stale price forever.
+pub struct TickerRate {
+    mid: Arc<AtomicI64>,
+    last_update_ns: Arc<AtomicI64>,
+    max_staleness: Duration,
+}
+
+impl TickerRate {
+    pub fn new(max_staleness: Duration) -> Self {
+        TickerRate {
+            mid: Arc::new(AtomicI64::new(0)),
+            last_update_ns: Arc::new(AtomicI64::new(0)),
+            max_staleness,
+        }
+    }
+
+    /// Fold in a new tick, updating the mid this rate reports
+    pub fn update(&self, tick: Tick) {
+        self.mid.store((tick.bid + tick.ask) / 2, Ordering::Relaxed);
+        self.last_update_ns.store(now_nanos() as i64, Ordering::Relaxed);
+    }
+
+    /// A cheap, `Send + Sync` handle suitable for `ExchangeCallbacks::on_tick`,
+    /// so a `BinanceClient`/`KrakenClient` ticker subscription can drive this
+    /// rate directly. The symbol a tick belongs to doesn't matter here — a
+    /// reference rate tracks one external feed regardless of which symbol
+    /// it's subscribed under.
+    pub fn callback(&self) -> impl Fn(Symbol, Tick) + Send + Sync {
+        let mid = self.mid.clone();
+        let last_update_ns = self.last_update_ns.clone();
+        move |_symbol: Symbol, tick: Tick| {
+            mid.store((tick.bid + tick.ask) / 2, Ordering::Relaxed);
+            last_update_ns.store(now_nanos() as i64, Ordering::Relaxed);
+        }
+    }
+}
+
+impl LatestRate for TickerRate {
+    type Error = TickerRateError;
+
+    fn latest_rate(&mut self) -> Result<Rate, Self::Error> {
+        let last_update_ns = self.last_update_ns.load(Ordering::Relaxed);
+        if last_update_ns == 0 {
+            return Err(TickerRateError::NoData);
+        }
+
+        let age = Duration::from_nanos((now_nanos() as i64 - last_update_ns).max(0) as u64);
+        if age > self.max_staleness {
+            return Err(TickerRateError::Stale(age));
+        }
+
+        Ok(Rate { price: self.mid.load(Ordering::Relaxed), timestamp: last_update_ns as Timestamp })
+    }
+}
+
+/// Tries `primary` first, falling back to `backup` if it errors (e.g. a
+/// [`TickerRate`] gone stale after the venue disconnects). `backup` must be
+/// infallible, so the whole combinator is too — a strategy that wires one of
+/// these in never needs its own error-handling path, it just always gets a
+/// [`Rate`] back.
+pub struct FallbackRate<P, B> {
+    primary: P,
+    backup: B,
+}
+
+impl<P, B> FallbackRate<P, B>
+where
+    P: LatestRate,
+    B: LatestRate<Error = Infallible>,
+{
+    pub fn new(primary: P, backup: B) -> Self {
+        FallbackRate { primary, backup }
+    }
+}
+
+impl<P, B> LatestRate for FallbackRate<P, B>
+where
+    P: LatestRate,
+    B: LatestRate<Error = Infallible>,
+{
+    type Error = Infallible;
+
+    fn latest_rate(&mut self) -> Result<Rate, Self::Error> {
+        match self.primary.latest_rate() {
+            Ok(rate) => Ok(rate),
+            Err(_) => self.backup.latest_rate(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_rate_never_fails() {
+        let mut rate = FixedRate::new(to_price(50000.0));
+        assert_eq!(rate.latest_rate().unwrap().price, to_price(50000.0));
+    }
+
+    #[test]
+    fn ticker_rate_errors_before_first_update() {
+        let mut rate = TickerRate::new(Duration::from_secs(5));
+        assert!(matches!(rate.latest_rate(), Err(TickerRateError::NoData)));
+    }
+
+    #[test]
+    fn ticker_rate_reports_mid_after_update() {
+        let rate = TickerRate::new(Duration::from_secs(5));
+        rate.update(Tick {
+            bid: to_price(99.0),
+            ask: to_price(101.0),
+            ..Tick::default()
+        });
+
+        let mut rate = rate;
+        assert_eq!(rate.latest_rate().unwrap().price, to_price(100.0));
+    }
+
+    #[test]
+    fn fallback_rate_uses_backup_when_primary_has_no_data() {
+        let primary = TickerRate::new(Duration::from_secs(5));
+        let backup = FixedRate::new(to_price(42.0));
+        let mut combined = FallbackRate::new(primary, backup);
+
+        assert_eq!(combined.latest_rate().unwrap().price, to_price(42.0));
+    }
+
+    #[test]
+    fn fallback_rate_prefers_primary_once_it_has_data() {
+        let primary = TickerRate::new(Duration::from_secs(5));
+        primary.update(Tick { bid: to_price(10.0), ask: to_price(10.0), ..Tick::default() });
+        let backup = FixedRate::new(to_price(42.0));
+        let mut combined = FallbackRate::new(primary, backup);
+
+        assert_eq!(combined.latest_rate().unwrap().price, to_price(10.0));
+    }
+}
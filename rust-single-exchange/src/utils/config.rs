@@ -2,7 +2,7 @@
 
 use crate::core::types::*;
 use crate::exchange::ExchangeConfig;
-use crate::risk::RiskLimits;
+use crate::risk::{FeeModel, RiskLimits};
 use crate::strategy::MarketMakerParams;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -14,12 +14,22 @@ pub struct AppConfig {
     pub exchange: ExchangeConfigFile,
     pub strategy: StrategyConfig,
     pub risk: RiskConfig,
+    #[serde(default)]
+    pub fees: FeeConfig,
+    #[serde(default)]
+    pub futures: FuturesConfig,
     pub system: SystemConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingConfig {
     pub symbol: String,
+    /// Symbols to run an independent `TradingEngine` for concurrently, one
+    /// process per exchange connection rather than one process per symbol.
+    /// Empty means "just `symbol`", so existing single-symbol configs keep
+    /// working unchanged.
+    #[serde(default)]
+    pub symbols: Vec<String>,
     pub base_asset: String,
     pub quote_asset: String,
     pub paper_trading: bool,
@@ -45,29 +55,155 @@ pub struct StrategyConfig {
     pub min_spread_bps: f64,
     pub max_spread_bps: f64,
     pub target_spread_bps: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_position: f64,
     pub inventory_skew: f64,
+    /// See `MarketMakerParams::funding_skew_weight`; zero (the default)
+    /// disables the bias, matching spot configs that never set it
+    #[serde(default)]
+    pub funding_skew_weight: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub default_order_size: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub min_order_size: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_order_size: f64,
     #[serde(default = "default_quote_refresh")]
     pub quote_refresh_us: u64,
+    #[serde(default = "default_layers")]
+    pub layers: usize,
+    #[serde(default = "default_layer_step_bps")]
+    pub layer_step_bps: f64,
+    #[serde(default = "default_layer_size_mult")]
+    pub layer_size_mult: f64,
+    /// See `MarketMakerParams::reference_spread_bps`
+    #[serde(default = "default_reference_spread_bps")]
+    pub reference_spread_bps: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskConfig {
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_position_qty: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_position_value: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_order_qty: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_order_value: f64,
     pub max_orders_per_second: u32,
     pub max_open_orders: u32,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_daily_loss: f64,
+    #[serde(deserialize_with = "deserialize_flexible_f64")]
     pub max_drawdown: f64,
     #[serde(default = "default_true")]
     pub kill_switch_enabled: bool,
 }
 
+/// Accepts either a native number or a quoted decimal/`0x`-prefixed hex
+/// string for a quantity/price field, so configs sourced from an exchange
+/// API (which often express sizes as strings) deserialize without a manual
+/// conversion step. Mirrors CoW Protocol's `HexOrDecimalU256` pattern,
+/// generalized to `f64` since this config has no fixed-point type of its own.
+fn deserialize_flexible_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum FlexibleNumber {
+        Number(f64),
+        Text(String),
+    }
+
+    match FlexibleNumber::deserialize(deserializer)? {
+        FlexibleNumber::Number(n) => Ok(n),
+        FlexibleNumber::Text(s) => parse_flexible_f64(&s).map_err(serde::de::Error::custom),
+    }
+}
+
+/// Shared parsing logic behind [`deserialize_flexible_f64`], also used by
+/// `apply_env_overrides` for the same flexible fields read from environment
+/// variables (which are always strings)
+fn parse_flexible_f64(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16)
+            .map(|v| v as f64)
+            .map_err(|e| e.to_string()),
+        None => s.parse::<f64>().map_err(|e| e.to_string()),
+    }
+}
+
+/// One hundred percent, expressed in hundredth-of-a-basis-point units
+/// (100% = 10,000bps = 1,000,000 hundredth-bps). Mirrors Chainflip's
+/// `ONE_IN_HUNDREDTH_PIPS` fixed-point convention for representing sub-bps
+/// fee rates as integers rather than floats.
+pub const ONE_IN_HUNDREDTH_BPS: i64 = 1_000_000;
+
+/// Maker/taker/creator fee schedule, in hundredth-of-a-basis-point units.
+/// `AppConfig::load` rejects a config whose rates sum past `max_total_fee_hbps`,
+/// mirroring the `MaxSwapFee`/`MaxCreatorFee` bound checks in Zeitgeist/Chainflip.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeConfig {
+    pub maker_fee_hbps: i64,
+    pub taker_fee_hbps: i64,
+    /// Optional per-strategy creator/rebate fee, added on top of the maker/taker rate
+    #[serde(default)]
+    pub creator_fee_hbps: i64,
+    #[serde(default = "default_max_total_fee_hbps")]
+    pub max_total_fee_hbps: i64,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        FeeConfig {
+            maker_fee_hbps: 0,
+            taker_fee_hbps: 0,
+            creator_fee_hbps: 0,
+            max_total_fee_hbps: default_max_total_fee_hbps(),
+        }
+    }
+}
+
+/// Futures-specific configuration: funding-rate subscription is driven by
+/// `--futures` on the command line (see `main`'s `Args::futures`), but dated
+/// (as opposed to perpetual) contracts additionally need to know when the
+/// current contract expires and what to roll into.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuturesConfig {
+    /// Unix timestamp (seconds) the current dated contract settles at.
+    /// `None` (the default) means a perpetual contract, so no rollover is
+    /// ever scheduled.
+    #[serde(default)]
+    pub contract_expiry_unix: Option<i64>,
+    /// Symbol to roll an expiring dated contract's position into; required
+    /// for rollover to actually trigger once `contract_expiry_unix` is set
+    #[serde(default)]
+    pub next_contract_symbol: Option<String>,
+    /// How far ahead of `contract_expiry_unix` to flatten the expiring
+    /// position and roll it into `next_contract_symbol`, i.e. the width of
+    /// the settlement window rollover is triggered into
+    #[serde(default = "default_rollover_window_secs")]
+    pub rollover_window_secs: u64,
+    /// How often the rollover scheduler re-checks contract expiry against
+    /// `rollover_window_secs`
+    #[serde(default = "default_rollover_check_interval_secs")]
+    pub rollover_check_interval_secs: u64,
+}
+
+impl Default for FuturesConfig {
+    fn default() -> Self {
+        FuturesConfig {
+            contract_expiry_unix: None,
+            next_contract_symbol: None,
+            rollover_window_secs: default_rollover_window_secs(),
+            rollover_check_interval_secs: default_rollover_check_interval_secs(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemConfig {
     #[serde(default = "default_log_level")]
@@ -86,16 +222,24 @@ pub struct SystemConfig {
 fn default_timeout() -> u64 { 5000 }
 fn default_rate_limit() -> u32 { 10 }
 fn default_quote_refresh() -> u64 { 100_000 }
+fn default_layers() -> usize { 1 }
+fn default_layer_step_bps() -> f64 { 2.0 }
+fn default_layer_size_mult() -> f64 { 1.5 }
+fn default_reference_spread_bps() -> f64 { 200.0 }
 fn default_true() -> bool { true }
 fn default_log_level() -> String { "INFO".to_string() }
 fn default_log_dir() -> String { "./logs".to_string() }
 fn default_buffer_size() -> usize { 65536 }
+fn default_max_total_fee_hbps() -> i64 { 1000 }
+fn default_rollover_window_secs() -> u64 { 3600 }
+fn default_rollover_check_interval_secs() -> u64 { 60 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         AppConfig {
             trading: TradingConfig {
                 symbol: "BTCUSDT".to_string(),
+                symbols: Vec::new(),
                 base_asset: "BTC".to_string(),
                 quote_asset: "USDT".to_string(),
                 paper_trading: true,
@@ -116,10 +260,15 @@ impl Default for AppConfig {
                 target_spread_bps: 10.0,
                 max_position: 0.1,
                 inventory_skew: 0.5,
+                funding_skew_weight: 0.0,
                 default_order_size: 0.001,
                 min_order_size: 0.0001,
                 max_order_size: 0.01,
                 quote_refresh_us: 100_000,
+                layers: 1,
+                layer_step_bps: 2.0,
+                layer_size_mult: 1.5,
+                reference_spread_bps: 200.0,
             },
             risk: RiskConfig {
                 max_position_qty: 0.1,
@@ -132,6 +281,8 @@ impl Default for AppConfig {
                 max_drawdown: 200.0,
                 kill_switch_enabled: true,
             },
+            fees: FeeConfig::default(),
+            futures: FuturesConfig::default(),
             system: SystemConfig {
                 log_level: "INFO".to_string(),
                 log_dir: "./logs".to_string(),
@@ -144,16 +295,44 @@ impl Default for AppConfig {
 }
 
 impl AppConfig {
-    /// Load configuration from file
+    /// Load configuration from file, dispatching the format on file
+    /// extension (`.toml`, `.yaml`/`.yml`, else JSON) so operators can use
+    /// human-friendly TOML configs instead of hand-editing JSON
     pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
         let content = std::fs::read_to_string(path)?;
-        let config: AppConfig = serde_json::from_str(&content)?;
+        let config: AppConfig = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content)?,
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&content)?,
+            _ => serde_json::from_str(&content)?,
+        };
+        config.validate_fees()?;
         Ok(config)
     }
 
-    /// Save configuration to file
+    /// Reject a fee schedule whose maker/taker/creator rates sum past
+    /// `max_total_fee_hbps`
+    fn validate_fees(&self) -> anyhow::Result<()> {
+        let total = self.fees.maker_fee_hbps + self.fees.taker_fee_hbps + self.fees.creator_fee_hbps;
+        if total > self.fees.max_total_fee_hbps {
+            anyhow::bail!(
+                "configured fee {} hundredth-bps exceeds max_total_fee_hbps {}",
+                total,
+                self.fees.max_total_fee_hbps
+            );
+        }
+        Ok(())
+    }
+
+    /// Save configuration to file, in the format implied by its extension
+    /// (`.toml`, `.yaml`/`.yml`, else JSON)
     pub fn save<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
-        let content = serde_json::to_string_pretty(self)?;
+        let path = path.as_ref();
+        let content = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::to_string_pretty(self)?,
+            Some("yaml") | Some("yml") => serde_yaml::to_string(self)?,
+            _ => serde_json::to_string_pretty(self)?,
+        };
         std::fs::write(path, content)?;
         Ok(())
     }
@@ -172,6 +351,41 @@ impl AppConfig {
         if let Ok(level) = std::env::var("LOG_LEVEL") {
             self.system.log_level = level;
         }
+        if let Ok(v) = std::env::var("MAX_POSITION") {
+            if let Ok(parsed) = parse_flexible_f64(&v) {
+                self.strategy.max_position = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("DEFAULT_ORDER_SIZE") {
+            if let Ok(parsed) = parse_flexible_f64(&v) {
+                self.strategy.default_order_size = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("MAX_ORDER_SIZE") {
+            if let Ok(parsed) = parse_flexible_f64(&v) {
+                self.strategy.max_order_size = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("MAX_DAILY_LOSS") {
+            if let Ok(parsed) = parse_flexible_f64(&v) {
+                self.risk.max_daily_loss = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("MAX_DRAWDOWN") {
+            if let Ok(parsed) = parse_flexible_f64(&v) {
+                self.risk.max_drawdown = parsed;
+            }
+        }
+    }
+
+    /// Symbols to run concurrently: `trading.symbols` if set, else the
+    /// single legacy `trading.symbol`
+    pub fn trading_symbols(&self) -> Vec<String> {
+        if self.trading.symbols.is_empty() {
+            vec![self.trading.symbol.clone()]
+        } else {
+            self.trading.symbols.clone()
+        }
     }
 
     /// Convert to exchange config
@@ -198,11 +412,16 @@ impl AppConfig {
             target_spread_bps: self.strategy.target_spread_bps,
             max_position: to_qty(self.strategy.max_position),
             inventory_skew: self.strategy.inventory_skew,
+            funding_skew_weight: self.strategy.funding_skew_weight,
             default_order_size: to_qty(self.strategy.default_order_size),
             min_order_size: to_qty(self.strategy.min_order_size),
             max_order_size: to_qty(self.strategy.max_order_size),
             quote_refresh_us: self.strategy.quote_refresh_us,
             min_quote_life_us: 50_000,
+            layers: self.strategy.layers,
+            layer_step_bps: self.strategy.layer_step_bps,
+            layer_size_mult: self.strategy.layer_size_mult,
+            reference_spread_bps: self.strategy.reference_spread_bps,
         }
     }
 
@@ -221,4 +440,14 @@ impl AppConfig {
             kill_switch_enabled: self.risk.kill_switch_enabled,
         }
     }
+
+    /// Convert to a fee model, turning hundredth-of-a-basis-point units into
+    /// fractions of notional
+    pub fn to_fee_model(&self) -> FeeModel {
+        FeeModel {
+            maker_fee_rate: self.fees.maker_fee_hbps as f64 / ONE_IN_HUNDREDTH_BPS as f64,
+            taker_fee_rate: self.fees.taker_fee_hbps as f64 / ONE_IN_HUNDREDTH_BPS as f64,
+            creator_fee_rate: self.fees.creator_fee_hbps as f64 / ONE_IN_HUNDREDTH_BPS as f64,
+        }
+    }
 }
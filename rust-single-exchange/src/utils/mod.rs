@@ -0,0 +1,9 @@
+//! Shared utilities: configuration and logging
+
+mod config;
+mod logger;
+
+pub use config::{
+    AppConfig, ExchangeConfigFile, FuturesConfig, RiskConfig, StrategyConfig, SystemConfig, TradingConfig,
+};
+pub use logger::{init_logging, PerfTimer};